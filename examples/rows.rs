@@ -1,4 +1,5 @@
 use comfy_table::Table;
+use squill_core::arrow::field_type_name;
 use squill_core::query;
 use squill_drivers::blocking_conn::Connection;
 use squill_drivers::blocking_conn::Rows;
@@ -26,23 +27,31 @@ fn main() -> Result<()> {
 
     println!();
 
-    // Same as above, but displaying the results in a table and accessing the columns values by name
+    // Same as above, but displaying the results in a table with the types of the columns and accessing the columns
+    // values by name
     //
-    //   ──────────────────
-    //   id   name
-    //   ══════════════════
-    //   1    Employee #1
-    //   ──────────────────
-    //   2    Employee #2
-    //   ──────────────────
-    //   3    Employee #3
-    //   ──────────────────
+    //   ─────────────────────────────
+    //   id (bigint)   name (varchar)
+    //   ═════════════════════════════
+    //   1             Employee #1
+    //   ─────────────────────────────
+    //   2             Employee #2
+    //   ─────────────────────────────
+    //   3             Employee #3
+    //   ─────────────────────────────
     let mut rows: Rows = query!(stmt, 3)?.into();
     let mut table = Table::new();
     table.load_preset(comfy_table::presets::UTF8_HORIZONTAL_ONLY);
     let mut next_row = rows.next();
     if let Some(Ok(ref first_row)) = next_row.as_ref() {
-        table.set_header(first_row.schema().fields().iter().map(|f| f.name().to_string()).collect::<Vec<String>>());
+        table.set_header(
+            first_row
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| format!("{} ({})", f.name(), field_type_name(f)))
+                .collect::<Vec<String>>(),
+        );
         while let Some(Ok(row)) = next_row {
             let employee: (i64, String) = (row.get("id"), row.get("name"));
             table.add_row(vec![employee.0.to_string(), employee.1.to_string()]);
@@ -1,18 +1,22 @@
 use crate::statement::Statement;
+use crate::transaction::Transaction;
 use arrow_array::RecordBatch;
 use arrow_schema::SchemaRef;
 use crossbeam_channel::TryRecvError;
-use futures::future::{err, BoxFuture};
+use futures::future::BoxFuture;
 use squill_core::driver;
 use squill_core::driver::{DriverConnection, DriverStatement};
 use squill_core::error::Error;
 use squill_core::factory::Factory;
 use squill_core::parameters::Parameters;
 use squill_core::row::Row;
+use squill_core::uri::redact_uri;
 use squill_core::{debug_clean_statement, Result};
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 use std::thread;
-use tokio::sync::{mpsc, oneshot};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex, OwnedMutexGuard};
 use tracing::{debug, error, event, trace, Level};
 
 /// Convert [std::error::Error] into an [Error].
@@ -57,8 +61,25 @@ macro_rules! await_on {
 // inconstancy between the blocking and non-blocking versions of the library.
 
 /// A non-blocking connection to a data source.
+///
+/// A connection can be cloned to be shared across tasks, all the clones are handles to the same connection thread and
+/// their operations are serialized: while a [Statement] prepared from a handle is alive, the operations of the other
+/// handles wait for the statement to be dropped.
+///
+/// Closing a handle closes the connection for all of them, [Connection::close] should only be called once and the
+/// operations on the other handles will fail afterwards.
+///
+/// # Thread safety
+///
+/// A connection is `Send` and `Sync`: the driver stays on the connection thread and the connection only holds the
+/// sender of the commands, so it can be moved to or shared with any task. The same goes for its statements,
+/// transactions and streams, they only borrow the connection.
+#[derive(Clone)]
 pub struct Connection {
     pub(crate) command_tx: crossbeam_channel::Sender<Command>,
+
+    /// A lock shared by the clones of the connection to make sure only one of them is using the connection thread.
+    lock: Arc<Mutex<()>>,
 }
 
 impl Connection {
@@ -67,21 +88,25 @@ impl Connection {
             crossbeam_channel::bounded(1);
         let uri: String = uri.into();
         let (open_tx, open_rx) = oneshot::channel();
-        debug!("Opening: {}", uri);
+        debug!("Opening: {}", redact_uri(&uri));
 
         let thread_spawn_result = thread::Builder::new()
             // .name(params.thread_name.clone())
             .spawn(move || match Factory::open(&uri) {
                 Ok(driver_conn) => {
-                    if open_tx.send(Ok(Self { command_tx })).is_err() {
-                        error!("Channel communication failed.");
+                    if open_tx.send(Ok(Self { command_tx, lock: Arc::new(Mutex::new(())) })).is_err() {
+                        // Nobody is waiting for the connection anymore (i.e. `open_timeout` has expired).
+                        debug!("Connection opened after being abandoned, closing it.");
+                        if let Err(e) = driver_conn.close() {
+                            error!("Connection did not close cleanly: {}", e);
+                        }
                     } else if let Err(e) = Self::main_command_loop(driver_conn, command_rx) {
                         error!("Connection did not close cleanly: {}", e);
                     }
                 }
                 Err(e) => {
                     if open_tx.send(Err(e)).is_err() {
-                        error!("Channel communication error while opening the connection.");
+                        debug!("Failed to open a connection that was abandoned.");
                     }
                 }
             });
@@ -99,21 +124,45 @@ impl Connection {
         }
     }
 
+    /// Open a connection, giving up if it's not opened before the given timeout.
+    ///
+    /// Returns [Error::Timeout] if the connection is not opened in time, i.e. when a server is not answering. Opening a
+    /// connection is a blocking operation of the driver that cannot be interrupted, so the connection thread keeps
+    /// trying to open the connection in the background and closes the connection as soon as it is opened.
+    pub fn open_timeout<T: Into<String>>(uri: T, timeout: Duration) -> BoxFuture<'static, Result<Self>> {
+        let open = Self::open(uri);
+        Box::pin(async move { tokio::time::timeout(timeout, open).await.map_err(|_| Error::Timeout)? })
+    }
+
     /// Check if the connection is alive.
     pub fn ping(&mut self) -> BoxFuture<'static, Result<()>> {
-        let (tx, rx) = oneshot::channel();
-        if let Err(e) = self.command_tx.send(Command::Ping { tx }) {
-            return Box::pin(err::<(), Error>(Error::InternalError { error: e.into() }));
-        }
-        await_on!(rx)
+        let command_tx = self.command_tx.clone();
+        let lock = self.lock.clone();
+        Box::pin(async move {
+            let _guard = lock.lock_owned().await;
+            let (tx, rx) = oneshot::channel();
+            if let Err(e) = command_tx.send(Command::Ping { tx }) {
+                return Err(Error::InternalError { error: e.into() });
+            }
+            await_on!(rx).await
+        })
     }
 
+    /// Close the connection.
+    ///
+    /// When the connection has been cloned, closing it closes all the clones. The connection is only closed once the
+    /// operations in flight on the clones are done: if a [Statement] (or a [Transaction]) of a clone is alive, closing
+    /// waits for it to be dropped, so a query whose rows are being consumed by another task is never cut short. The
+    /// operations requested on the clones after the connection is closed fail.
     pub fn close(self) -> BoxFuture<'static, Result<()>> {
-        let (tx, rx) = oneshot::channel();
-        if let Err(e) = self.command_tx.send(Command::Close { tx }) {
-            return Box::pin(err::<(), Error>(Error::InternalError { error: e.into() }));
-        }
-        await_on!(rx)
+        Box::pin(async move {
+            let _guard = self.lock.lock().await;
+            let (tx, rx) = oneshot::channel();
+            if let Err(e) = self.command_tx.send(Command::Close { tx }) {
+                return Err(Error::InternalError { error: e.into() });
+            }
+            await_on!(rx).await
+        })
     }
 
     /// Prepare a statement.
@@ -121,18 +170,12 @@ impl Connection {
     /// Because of the lifetime of the statement, the connection is no longer usable until the statement is dropped.
     /// The called must use the [Statement] returned to execute or query the results.
     pub fn prepare<S: Into<String>>(&mut self, statement: S) -> BoxFuture<'_, Result<Statement<'_>>> {
-        let (tx, rx) = oneshot::channel();
         let statement = statement.into();
         event!(Level::DEBUG, message = %{ debug_clean_statement(&statement) });
-        if let Err(e) = self.command_tx.send(Command::PrepareStatement { statement, tx }) {
-            return Box::pin(err::<Statement<'_>, Error>(Error::DriverError { error: e.into() }));
-        }
         Box::pin(async move {
-            match rx.await {
-                Ok(Ok(())) => Ok(Statement::new(self.command_tx.clone())),
-                Ok(Err(e)) => Err(Error::DriverError { error: e }),
-                Err(e) => Err(Error::InternalError { error: e.into() }),
-            }
+            // The lock is held by the statement until it is dropped.
+            let guard = self.lock.clone().lock_owned().await;
+            prepare_statement(&self.command_tx, statement, Some(guard)).await
         })
     }
 
@@ -148,13 +191,29 @@ impl Connection {
         statement: S,
         parameters: Option<Parameters>,
     ) -> BoxFuture<'_, Result<u64>> {
-        let (tx, rx) = oneshot::channel();
         let statement = statement.into();
         event!(Level::DEBUG, message = %{ debug_clean_statement(&statement) });
-        if let Err(e) = self.command_tx.send(Command::Execute { statement, parameters, tx }) {
-            return Box::pin(err::<u64, Error>(Error::DriverError { error: e.into() }));
-        }
-        await_on!(rx)
+        Box::pin(async move {
+            let _guard = self.lock.lock().await;
+            execute_statement(&self.command_tx, statement, parameters).await
+        })
+    }
+
+    /// Begin a transaction.
+    ///
+    /// The connection is no longer usable until the transaction is dropped, including by its clones which wait for the
+    /// transaction to end. See [Transaction] for more information.
+    pub fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_>>> {
+        Box::pin(async move {
+            // The lock is held by the transaction until it is dropped.
+            let guard = self.lock.clone().lock_owned().await;
+            let (tx, rx) = oneshot::channel();
+            if let Err(e) = self.command_tx.send(Command::Begin { tx }) {
+                return Err(Error::InternalError { error: e.into() });
+            }
+            await_on!(rx).await?;
+            Ok(Transaction::new(self.command_tx.clone(), guard))
+        })
     }
 
     /// Execute a query expecting to return at most one row.
@@ -212,16 +271,53 @@ impl Connection {
     }
 }
 
+/// Prepare a statement on the connection thread.
+///
+/// The `guard` is the lock of the connection held by the statement, if any (a statement prepared from a [Transaction]
+/// doesn't hold the lock because it is already held by the transaction).
+pub(crate) async fn prepare_statement<'c>(
+    command_tx: &crossbeam_channel::Sender<Command>,
+    statement: String,
+    guard: Option<OwnedMutexGuard<()>>,
+) -> Result<Statement<'c>> {
+    let (tx, rx) = oneshot::channel();
+    if let Err(e) = command_tx.send(Command::PrepareStatement { statement, tx }) {
+        return Err(Error::DriverError { error: e.into() });
+    }
+    match rx.await {
+        Ok(Ok(schema)) => Ok(Statement::new(command_tx.clone(), schema, guard)),
+        Ok(Err(e)) => Err(Error::DriverError { error: e }),
+        Err(e) => Err(Error::InternalError { error: e.into() }),
+    }
+}
+
+/// Prepare and execute a statement on the connection thread.
+pub(crate) async fn execute_statement(
+    command_tx: &crossbeam_channel::Sender<Command>,
+    statement: String,
+    parameters: Option<Parameters>,
+) -> Result<u64> {
+    let (tx, rx) = oneshot::channel();
+    if let Err(e) = command_tx.send(Command::Execute { statement, parameters, tx }) {
+        return Err(Error::DriverError { error: e.into() });
+    }
+    await_on!(rx).await
+}
+
 pub(crate) enum Command {
     Ping { tx: oneshot::Sender<driver::Result<()>> },
+    Begin { tx: oneshot::Sender<driver::Result<()>> },
+    Commit { tx: oneshot::Sender<driver::Result<()>> },
+    Rollback { tx: oneshot::Sender<driver::Result<()>> },
     Close { tx: oneshot::Sender<driver::Result<()>> },
     DropStatement { tx: oneshot::Sender<driver::Result<()>> },
     DropCursor,
     Execute { statement: String, parameters: Option<Parameters>, tx: oneshot::Sender<driver::Result<u64>> },
     ExecutePreparedStatement { parameters: Option<Parameters>, tx: oneshot::Sender<driver::Result<u64>> },
+    ExecuteManyPreparedStatement { parameters: Vec<Parameters>, tx: oneshot::Sender<driver::Result<u64>> },
     FetchCursor { tx: mpsc::Sender<driver::Result<Option<RecordBatch>>> },
     GetSchema { tx: oneshot::Sender<driver::Result<SchemaRef>> },
-    PrepareStatement { statement: String, tx: oneshot::Sender<driver::Result<()>> },
+    PrepareStatement { statement: String, tx: oneshot::Sender<driver::Result<Option<SchemaRef>>> },
     QueryPreparedStatement { parameters: Option<Parameters>, tx: oneshot::Sender<driver::Result<()>> },
 }
 
@@ -229,11 +325,15 @@ impl Display for Command {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Command::Ping { .. } => write!(f, "Ping"),
+            Command::Begin { .. } => write!(f, "Begin"),
+            Command::Commit { .. } => write!(f, "Commit"),
+            Command::Rollback { .. } => write!(f, "Rollback"),
             Command::Close { .. } => write!(f, "Close"),
             Command::DropStatement { .. } => write!(f, "DropStatement"),
             Command::DropCursor => write!(f, "DropCursor"),
             Command::Execute { statement, .. } => write!(f, "Execute: {}", statement),
             Command::ExecutePreparedStatement { .. } => write!(f, "ExecutePreparedStatement"),
+            Command::ExecuteManyPreparedStatement { .. } => write!(f, "ExecuteManyPreparedStatement"),
             Command::FetchCursor { .. } => write!(f, "FetchCursor"),
             Command::GetSchema { .. } => write!(f, "GetSchema"),
             Command::PrepareStatement { statement, .. } => write!(f, "PrepareStatement: {}", statement),
@@ -248,11 +348,13 @@ impl Display for Command {
 // The loop must be broken if an error occurs while sending a response back to the caller because the connection is no
 // longer usable if we were not able to send a response from a command.
 
-macro_rules! blocking_send_response_and_break_on_error {
+// The response to a fetch cannot be sent if the stream was dropped while the batch was fetched (e.g. the future
+// polling the stream was cancelled by a timeout). This is not an error: the stream sends a `DropCursor` command when
+// dropped, so the cursor loop must keep waiting for it to leave the connection in a usable state.
+macro_rules! blocking_send_fetch_response {
     ($tx:expr, $value:expr) => {
         if $tx.blocking_send($value).is_err() {
-            error!("Channel communication failed while sending statement fetching response.");
-            break;
+            debug!("Fetch response discarded, the stream has been dropped.");
         }
     };
 }
@@ -305,6 +407,22 @@ impl Connection {
                     send_response(tx, result)?;
                 }
 
+                //
+                // Begin, commit or rollback a transaction.
+                //
+                // The transaction statements are executed by the connection thread so the state of the transaction is
+                // kept by the driver connection for the commands that follow.
+                //
+                Ok(Command::Begin { tx }) => {
+                    send_response(tx, Self::execute_transaction_statement(&mut *driver_conn, "BEGIN"))?;
+                }
+                Ok(Command::Commit { tx }) => {
+                    send_response(tx, Self::execute_transaction_statement(&mut *driver_conn, "COMMIT"))?;
+                }
+                Ok(Command::Rollback { tx }) => {
+                    send_response(tx, Self::execute_transaction_statement(&mut *driver_conn, "ROLLBACK"))?;
+                }
+
                 //
                 // Close the connection.
                 //
@@ -312,9 +430,15 @@ impl Connection {
                     let result = driver_conn.close();
                     // We don't care if the receiver is closed, because we are closing the
                     // connection anyway.
-                    send_response(tx, result)?;
+                    let response = send_response(tx, result);
+                    // The clones of the connection may still send commands until they are dropped. Their commands are
+                    // dropped without a response so the callers fail instead of waiting forever (the commands left in
+                    // the channel would not be dropped if the thread exited while some clones are still alive).
+                    for command in command_rx.iter() {
+                        trace!("Command rejected by a closed connection: {}", command);
+                    }
                     // Once the connection is closed, we need to break the loop and exit the thread.
-                    break;
+                    return response;
                 }
 
                 //
@@ -344,7 +468,8 @@ impl Connection {
                 //
                 Ok(Command::PrepareStatement { statement, tx }) => match driver_conn.prepare(&statement) {
                     Ok(mut stmt) => {
-                        send_response(tx, Ok(()))?;
+                        // Some drivers only know the schema once the statement is executed (i.e. DuckDB).
+                        send_response(tx, Ok(stmt.prepared_schema()))?;
                         Self::stmt_command_loop(&mut *stmt, command_rx.clone())?;
                     }
                     Err(e) => {
@@ -377,6 +502,12 @@ impl Connection {
         Ok(())
     }
 
+    /// Execute a statement controlling a transaction (i.e. `BEGIN`, `COMMIT` or `ROLLBACK`).
+    fn execute_transaction_statement(driver_conn: &mut dyn DriverConnection, statement: &str) -> driver::Result<()> {
+        driver_conn.prepare(statement)?.execute(None)?;
+        Ok(())
+    }
+
     ///
     /// Processing commands for a statement.
     ///
@@ -395,6 +526,14 @@ impl Connection {
                     send_response::<u64>(tx, res)?;
                 }
 
+                //
+                // Execute a prepared statement for each set of parameters.
+                //
+                Ok(Command::ExecuteManyPreparedStatement { parameters, tx }) => {
+                    let res = driver_stmt.execute_many(parameters);
+                    send_response::<u64>(tx, res)?;
+                }
+
                 //
                 // Query a prepared statement.
                 //
@@ -461,13 +600,13 @@ impl Connection {
                 Ok(Command::FetchCursor { tx }) => {
                     match driver_iter.next() {
                         Some(Ok(batch)) => {
-                            blocking_send_response_and_break_on_error!(tx, Ok(Some(batch)));
+                            blocking_send_fetch_response!(tx, Ok(Some(batch)));
                         }
                         None => {
                             // The iterator is exhausted.
                             // We are not expecting to receive any more fetch commands for it but still we need to wait
                             // for the DropCursor command to break the loop.
-                            blocking_send_response_and_break_on_error!(tx, Ok(None));
+                            blocking_send_fetch_response!(tx, Ok(None));
                         }
                         Some(Err(e)) => {
                             // An error occurred while fetching the next record batch.
@@ -475,7 +614,7 @@ impl Connection {
                             // receive any more fetch commands for it but still we need to wait
                             // for the DropCursor command to break the loop.
                             error!("Error getting next record batch: {:?}", e);
-                            blocking_send_response_and_break_on_error!(tx, Err(e));
+                            blocking_send_fetch_response!(tx, Err(e));
                         }
                     }
                 }
@@ -515,7 +654,8 @@ impl Connection {
 mod tests {
     use crate::Connection;
     use futures::StreamExt;
-    use squill_core::{assert_ok, assert_some, assert_some_ok, params};
+    use squill_core::{assert_ok, assert_some, assert_some_ok, params, Error};
+    use std::time::{Duration, Instant};
 
     #[tokio::test]
     async fn test_open() {
@@ -523,6 +663,19 @@ mod tests {
         assert!(Connection::open("mock://").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_open_timeout() {
+        assert_ok!(Connection::open_timeout("mock://", Duration::from_secs(5)).await);
+
+        // A server accepting the TCP connections but never answering.
+        squill_postgres::register_driver();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("postgres://user@{}/db", listener.local_addr().unwrap());
+        let started = Instant::now();
+        assert!(matches!(Connection::open_timeout(uri, Duration::from_millis(100)).await, Err(Error::Timeout)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
     #[tokio::test]
     async fn test_prepare() {
         let mut conn = Connection::open("mock://").await.unwrap();
@@ -545,6 +698,16 @@ mod tests {
         assert!(assert_ok!(conn.prepare("INSERT 1").await).execute(params!(1)).await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_execute_many() {
+        let mut conn = assert_ok!(Connection::open("mock://").await);
+        let mut stmt = assert_ok!(conn.prepare("INSERT ?").await);
+        let parameters = (1..=1000).map(|id| assert_some!(params!(id)));
+        assert_eq!(assert_ok!(stmt.execute_many(parameters).await), 1000);
+        assert!(stmt.execute_many(vec![assert_some!(params!(1, 2))]).await.is_err()); // bind parameters mismatch
+        assert_eq!(assert_ok!(stmt.execute_many(vec![]).await), 0);
+    }
+
     #[tokio::test]
     async fn test_query_rows() {
         let mut conn = assert_ok!(Connection::open("mock://").await);
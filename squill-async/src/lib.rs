@@ -1,17 +1,25 @@
 pub mod connection;
 pub mod statement;
 pub mod streams;
+mod timeout;
+pub mod transaction;
 
 pub use connection::Connection;
 pub use statement::Statement;
 pub use streams::RecordBatchStream;
 pub use streams::RowStream;
+pub use timeout::timeout;
+pub use transaction::Transaction;
 
 #[cfg(test)]
 mod async_tests {
-    use crate::Connection;
+    use crate::connection::Command;
+    use crate::{Connection, RecordBatchStream, RowStream};
+    use arrow_array::{Int32Array, RecordBatch};
     use futures::StreamExt;
-    use squill_core::{assert_ok, assert_ok_some, assert_some_ok};
+    use squill_core::{assert_ok, assert_ok_some, assert_some, assert_some_ok, Error};
+    use std::sync::Arc;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn test_ping() {
@@ -19,6 +27,111 @@ mod async_tests {
         assert_ok!(conn.ping().await);
     }
 
+    #[tokio::test]
+    async fn test_timeout() {
+        let mut conn = assert_ok!(Connection::open("mock://?delay_ms=200").await);
+
+        // The batch is not available while the driver is waiting.
+        let mut stmt = assert_ok!(conn.prepare("SELECT 1").await);
+        let mut stream = assert_ok!(stmt.query(None).await);
+        assert!(futures::poll!(stream.next()).is_pending());
+        assert_some_ok!(stream.next().await);
+        drop(stream);
+        drop(stmt);
+
+        // An aggressive timeout aborts the query.
+        let result = crate::timeout(Duration::from_millis(20), conn.query_row("SELECT 1", None)).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+
+        // The connection remains usable after the timeout.
+        assert_ok!(conn.ping().await);
+        let row = assert_ok_some!(crate::timeout(Duration::from_secs(5), conn.query_row("SELECT 1", None)).await);
+        assert_eq!(row.get::<_, i32>(0), 1);
+    }
+
+    #[tokio::test]
+    async fn test_row_stream_take() {
+        // Simulate the connection thread with an endless cursor of batches of 2 rows.
+        let (command_tx, command_rx) = crossbeam_channel::bounded(1);
+        let cursor = std::thread::spawn(move || {
+            let mut fetched = 0;
+            loop {
+                match command_rx.recv() {
+                    Ok(Command::FetchCursor { tx }) => {
+                        fetched += 1;
+                        let batch =
+                            RecordBatch::try_from_iter(vec![("id", Arc::new(Int32Array::from(vec![1, 2])) as _)]);
+                        let _ = tx.blocking_send(Ok(Some(batch.unwrap())));
+                    }
+                    Ok(Command::DropCursor) => return Some(fetched),
+                    _ => return None,
+                }
+            }
+        });
+
+        let mut rows = RowStream::from(RecordBatchStream::new(command_tx.clone())).take(3);
+        let mut ids = Vec::new();
+        while let Some(row) = rows.next().await {
+            ids.push(assert_ok!(row).get::<_, i32>(0));
+        }
+        assert_eq!(ids, vec![1, 2, 1]);
+        // The cursor is dropped before the stream, once 2 batches have been fetched.
+        assert_eq!(cursor.join().unwrap(), Some(2));
+        assert!(rows.next().await.is_none());
+
+        // Using the mock driver.
+        let mut conn = assert_ok!(Connection::open("mock://").await);
+        let mut stmt = assert_ok!(conn.prepare("SELECT 3").await);
+        assert_eq!(assert_ok!(stmt.query_rows(None).await).take(2).count().await, 2);
+        assert_eq!(assert_ok!(stmt.query_rows(None).await).take(5).count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_clone() {
+        let mut conn = assert_ok!(Connection::open("mock://?delay_ms=20").await);
+        let tasks: Vec<_> = (1..=2)
+            .map(|count| {
+                let mut conn = conn.clone();
+                tokio::spawn(async move {
+                    let mut stmt = assert_ok!(conn.prepare(format!("SELECT {}", count)).await);
+                    let mut rows = assert_ok!(stmt.query_rows(None).await);
+                    let mut num_rows = 0;
+                    while let Some(row) = rows.next().await {
+                        assert_ok!(row);
+                        num_rows += 1;
+                    }
+                    num_rows
+                })
+            })
+            .collect();
+        for (index, task) in tasks.into_iter().enumerate() {
+            assert_eq!(assert_ok!(task.await), index + 1);
+        }
+        assert_ok!(conn.ping().await);
+        assert_ok!(conn.close().await);
+    }
+
+    #[tokio::test]
+    async fn test_clone_close() {
+        let mut conn = assert_ok!(Connection::open("mock://").await);
+        let other = conn.clone();
+
+        // Closing a clone waits for the statement being consumed to be dropped.
+        let mut stmt = assert_ok!(conn.prepare("SELECT 3").await);
+        let mut rows = assert_ok!(stmt.query_rows(None).await);
+        assert_ok!(assert_some!(rows.next().await));
+        let mut close = other.close();
+        assert!(futures::poll!(&mut close).is_pending());
+        assert_ok!(assert_some!(rows.next().await));
+        assert!(futures::poll!(&mut close).is_pending());
+        drop(rows);
+        drop(stmt);
+        assert_ok!(close.await);
+
+        // The connection is closed for all the clones.
+        assert!(conn.ping().await.is_err());
+    }
+
     #[tokio::test]
     async fn test_statement_query_map_row() {
         let mut conn = assert_ok!(Connection::open("mock://").await);
@@ -88,6 +201,12 @@ mod async_tests {
         assert_eq!(users[0].username, "user1");
         assert_eq!(users[1].id, 2);
         assert_eq!(users[1].username, "user2");
+
+        // A decoding error in the mapping function is returned instead of panicking.
+        assert!(matches!(
+            conn.query_map_rows("SELECT 2", None, |row| Ok(row.try_get::<_, bool>("username")?)).await,
+            Err(Error::Column { index: 1, .. })
+        ));
     }
 
     #[tokio::test]
@@ -100,4 +219,25 @@ mod async_tests {
         assert_eq!(schema.field(0).name(), "id");
         assert_eq!(schema.field(1).name(), "username");
     }
+
+    #[tokio::test]
+    async fn test_statement_prepared_schema() {
+        let mut conn = assert_ok!(Connection::open("mock://").await);
+        let mut stmt = assert_ok!(conn.prepare("SELECT 1").await);
+        let schema = assert_some!(stmt.prepared_schema());
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(schema.field(1).name(), "username");
+        assert_eq!(assert_ok!(stmt.schema().await), schema);
+
+        // The statement remains usable.
+        assert_some_ok!(assert_ok!(stmt.query(None).await).next().await);
+    }
+
+    // The driver handles stay on the connection thread, see the thread safety section of `Connection`.
+    static_assertions::assert_impl_all!(Connection: Send, Sync, Clone);
+    static_assertions::assert_impl_all!(crate::Statement<'static>: Send, Sync);
+    static_assertions::assert_impl_all!(crate::Transaction<'static>: Send, Sync);
+    static_assertions::assert_impl_all!(RecordBatchStream<'static>: Send, Sync);
+    static_assertions::assert_impl_all!(RowStream<'static>: Send, Sync);
 }
@@ -6,7 +6,7 @@ use futures::StreamExt;
 use squill_core::parameters::Parameters;
 use squill_core::row::Row;
 use squill_core::{Error, Result};
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, OwnedMutexGuard};
 use tracing::debug;
 
 /// A prepared statement.
@@ -27,13 +27,36 @@ pub struct Statement<'c> {
     ///   the connection thread is only expecting command for the statement and will only process commands at the
     ///   connection level once the statement is dropped.
     phantom: std::marker::PhantomData<&'c ()>,
+
+    /// The schema of the statement as known by the driver once prepared (`None` if the driver only knows it once the
+    /// statement is executed).
+    schema: Option<SchemaRef>,
+
+    /// The lock of the connection, preventing the clones of the connection from using it until the statement is
+    /// dropped (`None` if the statement was prepared from a transaction holding the lock).
+    _guard: Option<OwnedMutexGuard<()>>,
 }
 
 impl Statement<'_> {
-    pub(crate) fn new(command_tx: crossbeam_channel::Sender<Command>) -> Self {
-        Self { command_tx, phantom: std::marker::PhantomData }
+    pub(crate) fn new(
+        command_tx: crossbeam_channel::Sender<Command>,
+        schema: Option<SchemaRef>,
+        guard: Option<OwnedMutexGuard<()>>,
+    ) -> Self {
+        Self { command_tx, phantom: std::marker::PhantomData, schema, _guard: guard }
     }
 
+    /// Get the schema of the statement as it was known once the statement was prepared.
+    ///
+    /// Unlike [Statement::schema], this doesn't need a round-trip to the connection thread. Returns `None` if the
+    /// driver only knows the schema once the statement is executed (i.e. DuckDB). Some drivers only know the exact
+    /// types of the columns once the rows are fetched (i.e. SQLite reports the expressions as `Null`), in which case
+    /// [Statement::schema] must be used after the query to get them.
+    pub fn prepared_schema(&self) -> Option<SchemaRef> {
+        self.schema.clone()
+    }
+
+    /// Get the current schema of the statement from the driver.
     pub fn schema(&self) -> BoxFuture<'_, Result<SchemaRef>> {
         let (tx, rx) = oneshot::channel();
         if let Err(e) = self.command_tx.send(Command::GetSchema { tx }) {
@@ -50,6 +73,21 @@ impl Statement<'_> {
         await_on!(rx)
     }
 
+    /// Execute the statement once for each set of parameters.
+    ///
+    /// All the executions are done with a single command sent to the connection thread, which is more efficient than
+    /// calling [Statement::execute] in a loop.
+    ///
+    /// Returns the total number of rows affected.
+    pub fn execute_many<I: IntoIterator<Item = Parameters>>(&mut self, parameters: I) -> BoxFuture<'_, Result<u64>> {
+        let (tx, rx) = oneshot::channel();
+        let parameters = parameters.into_iter().collect();
+        if let Err(e) = self.command_tx.send(Command::ExecuteManyPreparedStatement { parameters, tx }) {
+            return Box::pin(err::<u64, Error>(Error::DriverError { error: e.into() }));
+        }
+        await_on!(rx)
+    }
+
     pub fn query<'s: 'i, 'i>(
         &'s mut self,
         parameters: Option<Parameters>,
@@ -79,19 +79,37 @@ impl Drop for RecordBatchStream<'_> {
 
 /// A non-blocking stream of rows.
 pub struct RowStream<'i> {
-    // The iterator used to poll the RecordBatch.
-    iterator: RecordBatchStream<'i>,
+    // The iterator used to poll the RecordBatch (`None` once released because the limit of rows has been reached).
+    iterator: Option<RecordBatchStream<'i>>,
 
     // The last record batch that was polled.
     last_record_batch: Option<Arc<RecordBatch>>,
 
     // The index of the next row to poll in the last record batch.
     index_in_batch: usize,
+
+    // The number of rows that can still be returned when the stream is limited by `RowStream::take`.
+    remaining: Option<usize>,
+}
+
+impl<'i> RowStream<'i> {
+    /// Limit the stream to the first `n` rows not returned yet.
+    ///
+    /// Unlike [futures::StreamExt::take], the batches are no longer fetched from the driver once `n` rows have been
+    /// returned and the cursor of the driver is dropped right away, without waiting for the stream to be dropped.
+    pub fn take(mut self, n: usize) -> Self {
+        let remaining = self.remaining.map_or(n, |remaining| remaining.min(n));
+        self.remaining = Some(remaining);
+        if remaining == 0 {
+            self.iterator = None;
+        }
+        self
+    }
 }
 
 impl<'i> From<RecordBatchStream<'i>> for RowStream<'i> {
     fn from(iterator: RecordBatchStream<'i>) -> Self {
-        RowStream { last_record_batch: None, iterator, index_in_batch: 0 }
+        RowStream { last_record_batch: None, iterator: Some(iterator), index_in_batch: 0, remaining: None }
     }
 }
 
@@ -100,9 +118,15 @@ impl<'i> Stream for RowStream<'i> {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> std::task::Poll<Option<Self::Item>> {
         let this = self.get_mut();
+        if this.remaining == Some(0) {
+            return std::task::Poll::Ready(None);
+        }
         if this.last_record_batch.is_none() {
+            let Some(iterator) = this.iterator.as_mut() else {
+                return std::task::Poll::Ready(None);
+            };
             // First call or we've exhausted the last batch.
-            this.last_record_batch = match Pin::new(&mut this.iterator).poll_next(cx) {
+            this.last_record_batch = match Pin::new(iterator).poll_next(cx) {
                 std::task::Poll::Ready(Some(Ok(record_batch))) => {
                     this.index_in_batch = 0;
                     Some(Arc::new(record_batch))
@@ -121,6 +145,14 @@ impl<'i> Stream for RowStream<'i> {
                 if this.index_in_batch >= last_record_batch.num_rows() {
                     this.last_record_batch = None;
                 }
+                if let Some(remaining) = this.remaining.as_mut() {
+                    *remaining -= 1;
+                    if *remaining == 0 {
+                        // The cursor of the driver is released as soon as the limit is reached.
+                        this.last_record_batch = None;
+                        this.iterator = None;
+                    }
+                }
                 std::task::Poll::Ready(Some(Ok(row)))
             }
         }
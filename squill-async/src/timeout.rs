@@ -0,0 +1,21 @@
+use squill_core::{Error, Result};
+use std::future::Future;
+use std::time::Duration;
+
+/// Require a future to complete before the given duration has elapsed.
+///
+/// Returns [Error::Timeout] if the future did not complete in time. The future is dropped, so when used on a
+/// [crate::RecordBatchStream] or a [crate::RowStream] the cursor is released once the pending fetch completes.
+///
+/// ```rust,ignore
+/// let rows = squill_async::timeout(Duration::from_secs(1), conn.query_row("SELECT 1", None)).await?;
+/// ```
+pub async fn timeout<F, T>(duration: Duration, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match tokio::time::timeout(duration, future).await {
+        Ok(result) => result,
+        Err(_elapsed) => Err(Error::Timeout),
+    }
+}
@@ -0,0 +1,131 @@
+use crate::connection::{execute_statement, into_error, prepare_statement, Command};
+use crate::{await_on, Statement};
+use futures::future::BoxFuture;
+use squill_core::parameters::Parameters;
+use squill_core::row::Row;
+use squill_core::{debug_clean_statement, Error, Result};
+use tokio::sync::{oneshot, OwnedMutexGuard};
+use tracing::{debug, event, Level};
+
+/// A non-blocking transaction.
+///
+/// A transaction is started by [crate::Connection::begin] and must be ended by calling either [Transaction::commit] or
+/// [Transaction::rollback]. If the transaction is dropped without being committed, it is rolled back.
+///
+/// While the transaction is alive, the connection must be used through the transaction (i.e. `tx.execute(...)`) and
+/// the operations of the clones of the connection wait for the transaction to end.
+///
+/// ```rust,ignore
+/// let mut tx = conn.begin().await?;
+/// tx.execute("INSERT INTO employee (id) VALUES (1)", None).await?;
+/// tx.commit().await?;
+/// ```
+pub struct Transaction<'c> {
+    /// The command sender is used to send commands to the connection thread.
+    command_tx: crossbeam_channel::Sender<Command>,
+
+    /// Set once the transaction has been committed or rolled back.
+    finished: bool,
+
+    /// This field is used to make sure the connection will be mut borrowed until the transaction is dropped.
+    phantom: std::marker::PhantomData<&'c ()>,
+
+    /// The lock of the connection, preventing the clones of the connection from using it until the transaction is
+    /// dropped.
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl<'c> Transaction<'c> {
+    pub(crate) fn new(command_tx: crossbeam_channel::Sender<Command>, guard: OwnedMutexGuard<()>) -> Self {
+        Self { command_tx, finished: false, phantom: std::marker::PhantomData, _guard: guard }
+    }
+
+    /// Commit the transaction.
+    pub fn commit(mut self) -> BoxFuture<'c, Result<()>> {
+        self.finished = true;
+        let (tx, rx) = oneshot::channel();
+        if let Err(e) = self.command_tx.send(Command::Commit { tx }) {
+            return Box::pin(futures::future::err(Error::InternalError { error: e.into() }));
+        }
+        // The transaction (and so the lock of the connection) is released once the commit is done.
+        Box::pin(async move {
+            let result = await_on!(rx).await;
+            drop(self);
+            result
+        })
+    }
+
+    /// Rollback the transaction.
+    pub fn rollback(mut self) -> BoxFuture<'c, Result<()>> {
+        self.finished = true;
+        let (tx, rx) = oneshot::channel();
+        if let Err(e) = self.command_tx.send(Command::Rollback { tx }) {
+            return Box::pin(futures::future::err(Error::InternalError { error: e.into() }));
+        }
+        // The transaction (and so the lock of the connection) is released once the rollback is done.
+        Box::pin(async move {
+            let result = await_on!(rx).await;
+            drop(self);
+            result
+        })
+    }
+
+    /// Prepare a statement in the transaction.
+    ///
+    /// The transaction is no longer usable until the statement is dropped.
+    pub fn prepare<S: Into<String>>(&mut self, statement: S) -> BoxFuture<'_, Result<Statement<'_>>> {
+        let statement = statement.into();
+        event!(Level::DEBUG, message = %{ debug_clean_statement(&statement) });
+        Box::pin(async move { prepare_statement(&self.command_tx, statement, None).await })
+    }
+
+    /// Execute a statement in the transaction.
+    ///
+    /// See [crate::Connection::execute].
+    pub fn execute<S: Into<String>>(
+        &mut self,
+        statement: S,
+        parameters: Option<Parameters>,
+    ) -> BoxFuture<'_, Result<u64>> {
+        let statement = statement.into();
+        event!(Level::DEBUG, message = %{ debug_clean_statement(&statement) });
+        Box::pin(async move { execute_statement(&self.command_tx, statement, parameters).await })
+    }
+
+    /// Execute a query expecting to return at most one row in the transaction.
+    pub fn query_row<S: Into<String>>(
+        &mut self,
+        statement: S,
+        parameters: Option<Parameters>,
+    ) -> BoxFuture<'_, Result<Option<Row>>> {
+        let statement: String = statement.into();
+        Box::pin(async move {
+            let mut statement = self.prepare(statement).await?;
+            statement.query_row(parameters).await
+        })
+    }
+}
+
+impl Drop for Transaction<'_> {
+    /// Drop the transaction.
+    ///
+    /// If the transaction was neither committed nor rolled back, a command to rollback the transaction is sent and we
+    /// wait for its completion so the connection is on the right state to process the next command.
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        // There is no way to report an error from `drop`, the rollback is a best effort.
+        let (tx, rx) = oneshot::channel();
+        match self.command_tx.send(Command::Rollback { tx }) {
+            Ok(()) => match futures::executor::block_on(rx) {
+                Ok(Err(e)) => debug!("Error rolling back the transaction: {}", e),
+                Err(e) => debug!("Error waiting for the transaction rollback: {}", e),
+                Ok(Ok(())) => {}
+            },
+            Err(e) => {
+                debug!("Error rolling back the transaction: {}", e);
+            }
+        }
+    }
+}
@@ -1,9 +1,24 @@
+use crate::interrupt::InterruptHandle;
+use crate::metrics::{record, Metrics, MetricsRef};
+use crate::owned_rows::OwnedRows;
+use crate::slow_query::{SlowQuery, SlowQueryHook, SlowQueryRef};
 use crate::statement::Statement;
-use squill_core::driver::DriverConnection;
+use crate::transaction::Transaction;
+use arrow_array::RecordBatch;
+use arrow_schema::{DataType, SchemaRef};
+use squill_core::decode::Decode;
+use squill_core::driver::{CacheStats, Described, DriverConnection};
 use squill_core::factory::Factory;
 use squill_core::parameters::Parameters;
+use squill_core::placeholders::{self, PlaceholderStyle};
+use squill_core::quote;
 use squill_core::row::Row;
+use squill_core::uri;
+use squill_core::values::Value;
 use squill_core::{Error, Result};
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::time::Duration;
 
 /// A connection to a data source.
 ///
@@ -18,14 +33,61 @@ use squill_core::{Error, Result};
 /// let stmt = conn.prepare("SELECT * FROM employee")?;
 /// let rows = query!(stmt, 1, "Alice");
 /// ```
+///
+/// # Thread safety
+///
+/// A connection is neither `Send` nor `Sync`: the handles of most drivers cannot leave the thread that opened them, so
+/// a connection and its statements must be used on that thread. Use an [InterruptHandle] to interrupt a statement from
+/// another thread, or the connection of `squill_async` that runs the driver on a dedicated thread.
+///
+/// ```rust,compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<squill_blocking::Connection>();
+/// ```
 pub struct Connection {
     inner: Box<dyn DriverConnection>,
+    rewrite_placeholders: bool,
+    savepoint_counter: usize,
+    server_version: Option<String>,
+    metrics: MetricsRef,
+    /// The session variables set by [Connection::set_session_var] along with their previous value when the driver
+    /// cannot reset them to their default (SQLite).
+    session_vars: Vec<(String, Option<String>)>,
+    slow_query: SlowQueryRef,
 }
 
 impl Connection {
     pub fn open(uri: &str) -> Result<Self> {
         let inner = Factory::open(uri)?;
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            rewrite_placeholders: false,
+            savepoint_counter: 0,
+            server_version: None,
+            metrics: None,
+            session_vars: Vec::new(),
+            slow_query: None,
+        })
+    }
+
+    /// Open a connection to a named in-memory database shared by all the connections opened with the same name.
+    ///
+    /// Unlike the connections opened with the in-memory URI of a driver that each get their own database, the
+    /// connections opened with the same `name` see the same data. The URI used depends on the driver:
+    /// - SQLite: `sqlite:<name>?mode=memory&cache=shared`, the database is deleted when its last connection is closed,
+    /// - DuckDB: `duckdb:///:memory:<name>`, the database is kept in memory until the end of the process.
+    ///
+    /// The other drivers don't have in-memory databases and an [Error::InvalidUri] is returned.
+    pub fn open_shared_memory(driver_name: &str, name: &str) -> Result<Self> {
+        let name = uri::encode_component(name);
+        match driver_name {
+            "sqlite" => Self::open(&format!("sqlite:{}?mode=memory&cache=shared", name)),
+            "duckdb" => Self::open(&format!("duckdb:///:memory:{}", name)),
+            _ => Err(Error::InvalidUri {
+                uri: format!("{}:", driver_name),
+                reason: format!("In-memory databases are not supported by {}", driver_name),
+            }),
+        }
     }
 
     /// Get the driver name used by the connection.
@@ -33,17 +95,332 @@ impl Connection {
         self.inner.driver_name()
     }
 
+    /// Get the version of the server the connection is connected to.
+    ///
+    /// For the embedded databases, this is the version of the library. The version is queried using:
+    /// - SQLite: `sqlite_version()` (same as `sqlite3_libversion`),
+    /// - MySQL: `VERSION()`,
+    /// - Other drivers (PostgreSQL, DuckDB): `version()`.
+    ///
+    /// The version is only queried once and then cached for the lifetime of the connection.
+    pub fn server_version(&mut self) -> Result<String> {
+        if let Some(version) = &self.server_version {
+            return Ok(version.clone());
+        }
+        let version_query = match self.driver_name() {
+            "sqlite" => "SELECT sqlite_version()",
+            "mysql" => "SELECT VERSION()",
+            _ => "SELECT version()",
+        };
+        let version = self.query_scalar::<_, String>(version_query, None)?.ok_or(Error::NotFound)?;
+        self.server_version = Some(version.clone());
+        Ok(version)
+    }
+
+    /// Quote an identifier (i.e. the name of a table or a column) in the style expected by the driver.
+    ///
+    /// See [quote::quote_identifier] for more information.
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        quote::quote_identifier(self.driver_name(), identifier)
+    }
+
+    /// Quote a string literal in the style expected by the driver.
+    ///
+    /// Binding a parameter should always be preferred, see [quote::quote_literal] for more information.
+    pub fn quote_literal(&self, value: &str) -> String {
+        quote::quote_literal(self.driver_name(), value)
+    }
+
     /// Check if the connection is alive.
     pub fn ping(&mut self) -> Result<()> {
         self.inner.ping().map_err(Error::from)
     }
 
+    /// Check the connection is alive and the expected tables exist.
+    ///
+    /// This is intended to be called at the startup of an application. The tables (and views) are looked up in the
+    /// catalog of the database using:
+    /// - SQLite: `sqlite_master` (and `sqlite_temp_master`),
+    /// - MySQL: `information_schema.tables` of the current database,
+    /// - PostgreSQL: `information_schema.tables` of the schemas in the search path,
+    /// - Other drivers (DuckDB): `information_schema.tables`.
+    ///
+    /// The table names are compared case-insensitively. If some tables are missing, an [Error::MissingTables] listing
+    /// them is returned.
+    pub fn health_check(&mut self, expected_tables: &[&str]) -> Result<()> {
+        self.ping()?;
+        if expected_tables.is_empty() {
+            return Ok(());
+        }
+        let tables_query = match self.driver_name() {
+            "sqlite" => {
+                "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') \
+                 UNION ALL SELECT name FROM sqlite_temp_master WHERE type IN ('table', 'view')"
+            }
+            "mysql" => "SELECT table_name FROM information_schema.tables WHERE table_schema = DATABASE()",
+            "postgres" => {
+                "SELECT table_name::TEXT FROM information_schema.tables WHERE table_schema = ANY(current_schemas(true))"
+            }
+            _ => "SELECT table_name FROM information_schema.tables",
+        };
+        let tables = self.query_map_rows(tables_query, None, |row| Ok(row.try_get_nullable::<_, String>(0)?))?;
+        let missing_tables: Vec<String> = expected_tables
+            .iter()
+            .filter(|expected| !tables.iter().flatten().any(|table| table.eq_ignore_ascii_case(expected)))
+            .map(|expected| expected.to_string())
+            .collect();
+        match missing_tables.is_empty() {
+            true => Ok(()),
+            false => Err(Error::MissingTables { tables: missing_tables }),
+        }
+    }
+
+    /// Set a variable for the session of the connection.
+    ///
+    /// The statement issued depends on the driver:
+    /// - SQLite: `PRAGMA name = 'value'`,
+    /// - Other drivers (PostgreSQL, MySQL, DuckDB): `SET SESSION name = 'value'`.
+    ///
+    /// With PostgreSQL, this can be used to change the role of the session (i.e. `set_session_var("role", "tenant")`)
+    /// or to set a custom variable (i.e. `app.tenant_id`). The value is quoted as a literal but the name cannot be
+    /// quoted, so only names made of ASCII letters, digits and underscores (optionally separated by dots) are accepted,
+    /// otherwise an [Error::InputError] is returned.
+    pub fn set_session_var(&mut self, name: &str, value: &str) -> Result<()> {
+        validate_session_var_name(name)?;
+        let previous_value = match self.driver_name() {
+            "sqlite" => self.query_row(format!("PRAGMA {}", name), None)?.map(|row| row.get::<_, Value>(0).to_string()),
+            _ => None,
+        };
+        let statement = match self.driver_name() {
+            "sqlite" => format!("PRAGMA {} = {}", name, self.quote_literal(value)),
+            _ => format!("SET SESSION {} = {}", name, self.quote_literal(value)),
+        };
+        self.execute(statement, None)?;
+        self.session_vars.push((name.to_string(), previous_value));
+        Ok(())
+    }
+
+    /// Reset the variables set by [Connection::set_session_var].
+    ///
+    /// The variables are reset to their default value using `RESET name` (PostgreSQL, DuckDB) or
+    /// `SET SESSION name = DEFAULT` (MySQL). With SQLite, the pragmas are restored to the value they had before being
+    /// set.
+    pub fn reset_session(&mut self) -> Result<()> {
+        while let Some((name, previous_value)) = self.session_vars.pop() {
+            let statement = match self.driver_name() {
+                "sqlite" => match previous_value {
+                    Some(previous_value) => format!("PRAGMA {} = {}", name, self.quote_literal(&previous_value)),
+                    // The pragma has no value that can be read back (i.e. a write-only pragma).
+                    None => continue,
+                },
+                "mysql" => format!("SET SESSION {} = DEFAULT", name),
+                _ => format!("RESET {}", name),
+            };
+            self.execute(statement, None)?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the database to reclaim the unused space.
+    ///
+    /// Issues `VACUUM` with SQLite, DuckDB and PostgreSQL, MySQL is not supported. With PostgreSQL, this cannot be
+    /// called from a transaction.
+    pub fn vacuum(&mut self) -> Result<()> {
+        match self.driver_name() {
+            "sqlite" | "duckdb" | "postgres" => self.execute("VACUUM", None).map(|_| ()),
+            _ => Err(self.unsupported_maintenance("VACUUM")),
+        }
+    }
+
+    /// Collect the statistics used by the query planner, either for a table (i.e. `main.employee`) or for the whole
+    /// database.
+    ///
+    /// Issues `ANALYZE` (or `ANALYZE TABLE` with MySQL which requires a table).
+    pub fn analyze(&mut self, table: Option<&str>) -> Result<()> {
+        let table = table.map(|table| table.split('.').map(|part| self.quote_identifier(part)).collect::<Vec<_>>());
+        let statement = match (self.driver_name(), table) {
+            ("mysql", Some(table)) => format!("ANALYZE TABLE {}", table.join(".")),
+            ("mysql", None) => return Err(self.unsupported_maintenance("ANALYZE without a table")),
+            (_, Some(table)) => format!("ANALYZE {}", table.join(".")),
+            (_, None) => "ANALYZE".to_string(),
+        };
+        self.execute(statement, None).map(|_| ())
+    }
+
+    /// Run the maintenance recommended by the driver to keep the database efficient.
+    ///
+    /// The statement issued depends on the driver:
+    /// - SQLite: `PRAGMA optimize`,
+    /// - DuckDB: `CHECKPOINT`,
+    /// - PostgreSQL: `VACUUM ANALYZE` (cannot be called from a transaction).
+    ///
+    /// MySQL is not supported.
+    pub fn optimize(&mut self) -> Result<()> {
+        let statement = match self.driver_name() {
+            "sqlite" => "PRAGMA optimize",
+            "duckdb" => "CHECKPOINT",
+            "postgres" => "VACUUM ANALYZE",
+            _ => return Err(self.unsupported_maintenance("optimize")),
+        };
+        self.execute(statement, None).map(|_| ())
+    }
+
+    fn unsupported_maintenance(&self, operation: &str) -> Error {
+        Error::DriverError { error: format!("{} is not supported by {}", operation, self.driver_name()).into() }
+    }
+
+    /// Get a handle to interrupt the statement running on the connection from another thread.
+    ///
+    /// For the drivers not supporting it (only SQLite does), the handle returned does nothing when interrupting.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle::new(self.inner.interrupt_handle())
+    }
+
+    /// Get the underlying driver connection as its concrete type.
+    ///
+    /// This gives access to the features that are specific to a driver. Returns `None` if the connection is not of the
+    /// given type.
+    pub fn downcast_mut<T: DriverConnection>(&mut self) -> Option<&mut T> {
+        self.inner.downcast_mut::<T>()
+    }
+
+    /// Begin a transaction.
+    ///
+    /// The transaction is rolled back when dropped unless [Transaction::commit] is called.
+    pub fn begin(&mut self) -> Result<Transaction<'_>> {
+        Transaction::new(self)
+    }
+
+    /// Get the next identifier used to name a savepoint.
+    pub(crate) fn next_savepoint_id(&mut self) -> usize {
+        self.savepoint_counter += 1;
+        self.savepoint_counter
+    }
+
+    /// Enable or disable the rewriting of the `?` placeholders into the style expected by the driver.
+    ///
+    /// When enabled, statements can be written using `?` placeholders regardless of the driver (e.g. `$1`, `$2` for
+    /// PostgreSQL). The rewriting is disabled by default.
+    pub fn set_rewrite_placeholders(&mut self, enabled: bool) {
+        self.rewrite_placeholders = enabled;
+    }
+
+    /// Enable or disable the metrics of the connection.
+    ///
+    /// The metrics are disabled by default. Enabling them resets the counters, disabling them discards the counters.
+    /// See [Metrics] for more information.
+    pub fn set_metrics_enabled(&mut self, enabled: bool) {
+        self.metrics = enabled.then(Default::default);
+    }
+
+    /// Get a snapshot of the metrics of the connection.
+    ///
+    /// Returns `None` if the metrics are not enabled (see [Connection::set_metrics_enabled]).
+    pub fn metrics(&self) -> Option<Metrics> {
+        self.metrics.as_ref().map(|metrics| metrics.borrow().clone())
+    }
+
+    /// Register a callback invoked when a statement takes longer than `threshold` to run.
+    ///
+    /// The executions and the queries of the statements prepared after the registration are timed, the time of a query
+    /// includes the fetching of its rows and is checked once its rows are dropped. The callback is given the statement
+    /// cleaned for logging (see [squill_core::debug_clean_statement]) and the time it took. Registering a new callback
+    /// replaces the previous one, and no statement is timed until a callback is registered.
+    ///
+    /// ```rust,ignore
+    /// conn.on_slow_query(Duration::from_millis(500), |statement, elapsed| {
+    ///     warn!("Slow query ({:?}): {}", elapsed, statement);
+    /// });
+    /// ```
+    pub fn on_slow_query<F>(&mut self, threshold: Duration, callback: F)
+    where
+        F: Fn(&str, Duration) + 'static,
+    {
+        self.slow_query = Some(Rc::new(SlowQueryHook { threshold, callback: Box::new(callback) }));
+    }
+
+    /// Get the statistics of the cache of prepared statements of the connection.
+    ///
+    /// Only SQLite caches the prepared statements, the statistics of the other drivers are always zero.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.inner.cache_stats().unwrap_or_default()
+    }
+
+    /// Remove all the prepared statements from the cache of the connection.
+    ///
+    /// The hits and misses are not reset, only the size of the cache is.
+    pub fn clear_prepared_cache(&mut self) {
+        self.inner.clear_prepared_cache()
+    }
+
     /// Prepare a statement.
     ///
     /// Return a [Statement] that can be later used to by `query` or `execute` functions. A prepared statement can be
     /// used multiple times with different parameters.    
     pub fn prepare<S: AsRef<str>>(&mut self, statement: S) -> Result<Statement<'_>> {
-        Ok(Statement { inner: self.inner.prepare(statement.as_ref())? })
+        let text = statement.as_ref().to_string();
+        let statement = self.rewrite(statement.as_ref());
+        let inner = &mut self.inner;
+        let inner = record(&self.metrics, || inner.prepare(&statement), |metrics, _| metrics.statements_prepared += 1)?;
+        Ok(Statement {
+            inner,
+            text,
+            metrics: self.metrics.clone(),
+            max_batch_rows: None,
+            slow_query: SlowQuery::new(&self.slow_query, &statement),
+        })
+    }
+
+    /// Prepare a statement giving the types of its first parameters.
+    ///
+    /// The types are hints for the parameters whose type cannot be inferred by the driver, i.e. `SELECT ? + ?` which
+    /// cannot be prepared by PostgreSQL or DuckDB without knowing the types of the parameters. The drivers not needing
+    /// the types (i.e. SQLite) ignore them.
+    ///
+    /// ```rust,ignore
+    /// let mut stmt = conn.prepare_typed("SELECT ? + ?", &[DataType::Int64, DataType::Int64])?;
+    /// assert_eq!(stmt.query_scalar::<i64>(params!(1, 2))?, Some(3));
+    /// ```
+    pub fn prepare_typed<S: AsRef<str>>(&mut self, statement: S, types: &[DataType]) -> Result<Statement<'_>> {
+        let text = statement.as_ref().to_string();
+        let statement = self.rewrite(statement.as_ref());
+        let inner = &mut self.inner;
+        let inner = record(
+            &self.metrics,
+            || inner.prepare_typed(&statement, types),
+            |metrics, _| metrics.statements_prepared += 1,
+        )?;
+        Ok(Statement {
+            inner,
+            text,
+            metrics: self.metrics.clone(),
+            max_batch_rows: None,
+            slow_query: SlowQuery::new(&self.slow_query, &statement),
+        })
+    }
+
+    /// Describe a statement without executing it.
+    ///
+    /// Returns the schema of the rows the statement would return along with its number of parameters, this is useful
+    /// to validate a statement (i.e. a dry-run). With DuckDB, only queries can be described.
+    ///
+    /// ```rust,ignore
+    /// let described = conn.describe("SELECT id, name FROM employee WHERE id = ?")?;
+    /// assert_eq!(described.schema.fields().len(), 2);
+    /// assert_eq!(described.param_count, 1);
+    /// ```
+    pub fn describe<S: AsRef<str>>(&mut self, statement: S) -> Result<Described> {
+        let statement = self.rewrite(statement.as_ref());
+        let inner = &mut self.inner;
+        Ok(record(&self.metrics, || inner.describe(&statement), |metrics, _| metrics.statements_described += 1)?)
+    }
+
+    /// Rewrite the placeholders of a statement into the style of the driver if enabled.
+    fn rewrite<'s>(&self, statement: &'s str) -> Cow<'s, str> {
+        match self.rewrite_placeholders {
+            true => Cow::Owned(placeholders::rewrite(statement, PlaceholderStyle::for_driver(self.driver_name()))),
+            false => Cow::Borrowed(statement),
+        }
     }
 
     /// Execute a statement.
@@ -56,6 +433,65 @@ impl Connection {
         statement.execute(parameters)
     }
 
+    /// Execute a statement binding lists of values to `IN (?)` clauses.
+    ///
+    /// Each `?` placeholder bound to a [Value::List] is expanded into as many placeholders as there are values in the
+    /// list, the values are still bound as parameters so there is no risk of SQL injection.
+    /// See [placeholders::expand_lists] for more information.
+    ///
+    /// Returns the number of rows affected.
+    ///
+    /// ```rust,ignore
+    /// conn.execute_in("DELETE FROM employee WHERE id IN (?)", params!(vec![1, 2, 3]))?;
+    /// ```
+    pub fn execute_in<S: AsRef<str>>(&mut self, statement: S, parameters: Option<Parameters>) -> Result<u64> {
+        let (statement, parameters) = placeholders::expand_lists(statement.as_ref(), parameters);
+        self.execute(statement, parameters)
+    }
+
+    /// Execute an `INSERT` statement and return the key generated for the inserted row.
+    ///
+    /// The generated key is retrieved using the best mechanism available for the driver:
+    /// - SQLite: `last_insert_rowid()`,
+    /// - MySQL: `LAST_INSERT_ID()`,
+    /// - Other drivers (PostgreSQL, DuckDB): the statement is executed with a `RETURNING id` clause appended to it
+    ///   unless the statement already has a `RETURNING` clause. This requires the table to have a column named `id`,
+    ///   or the `RETURNING` clause to return the key as the first column.
+    pub fn insert_returning_id<S: AsRef<str>>(&mut self, statement: S, parameters: Option<Parameters>) -> Result<i64> {
+        let row = match self.driver_name() {
+            "sqlite" | "mysql" => {
+                self.execute(statement, parameters)?;
+                let last_id_query = if self.driver_name() == "sqlite" {
+                    "SELECT last_insert_rowid()"
+                } else {
+                    "SELECT LAST_INSERT_ID()"
+                };
+                self.query_row(last_id_query, None)?
+            }
+            _ => {
+                let statement = statement.as_ref().trim_end().trim_end_matches(';');
+                if has_returning(statement) {
+                    self.query_row(statement, parameters)?
+                } else {
+                    self.query_row(format!("{} RETURNING id", statement), parameters)?
+                }
+            }
+        };
+        match row.map(|row| row.try_get::<_, Value>(0)).transpose()? {
+            Some(Value::Int8(id)) => Ok(id as i64),
+            Some(Value::Int16(id)) => Ok(id as i64),
+            Some(Value::Int32(id)) => Ok(id as i64),
+            Some(Value::Int64(id)) => Ok(id),
+            Some(Value::UInt8(id)) => Ok(id as i64),
+            Some(Value::UInt16(id)) => Ok(id as i64),
+            Some(Value::UInt32(id)) => Ok(id as i64),
+            Some(Value::UInt64(id)) => i64::try_from(id)
+                .map_err(|_| Error::InvalidType { expected: "i64".to_string(), actual: id.to_string() }),
+            Some(value) => Err(Error::InvalidType { expected: "integer".to_string(), actual: format!("{:?}", value) }),
+            None => Err(Error::NotFound),
+        }
+    }
+
     /// Query a statement that is expected to return a single [Row].
     ///
     /// Returns `Ok(None)` if the query returned no rows.
@@ -65,6 +501,46 @@ impl Connection {
         statement.query_row(parameters)
     }
 
+    /// Query a statement and return its schema along with all the record batches.
+    ///
+    /// Unlike [Statement::query], the record batches are all fetched before returning, this is only intended for
+    /// queries returning a small number of rows. The schema is returned even if the query doesn't return any row.
+    pub fn fetch_all<S: AsRef<str>>(
+        &mut self,
+        statement: S,
+        parameters: Option<Parameters>,
+    ) -> Result<(SchemaRef, Vec<RecordBatch>)> {
+        let mut statement = self.prepare(statement)?;
+        let batches = statement.query(parameters)?.collect::<Result<Vec<_>>>()?;
+        Ok((statement.schema(), batches))
+    }
+
+    /// Query a statement and return an iterator of [Row] owning the prepared statement.
+    ///
+    /// Unlike [Statement::query_rows], the returned [OwnedRows] doesn't borrow a statement so it can be returned from a
+    /// function.
+    pub fn into_query_rows<S: AsRef<str>>(
+        &mut self,
+        statement: S,
+        parameters: Option<Parameters>,
+    ) -> Result<OwnedRows<'_>> {
+        self.prepare(statement)?.into_query_rows(parameters)
+    }
+
+    /// Query a statement that is expected to return a single value.
+    ///
+    /// Returns `Ok(None)` if the query returned no rows or if the value is null.
+    ///
+    /// See [Statement::query_scalar] for more information.
+    pub fn query_scalar<S: AsRef<str>, T: Decode>(
+        &mut self,
+        statement: S,
+        parameters: Option<Parameters>,
+    ) -> Result<Option<T>> {
+        let mut statement = self.prepare(statement)?;
+        statement.query_scalar(parameters)
+    }
+
     /// Query a statement that is expected to return a single row and map it to a value.
     ///
     /// Returns `Ok(None)` if the query returned no rows.
@@ -120,11 +596,62 @@ impl Connection {
     }
 }
 
+/// Check if a statement has a `RETURNING` clause, the quoted literals and the comments are ignored.
+fn has_returning(statement: &str) -> bool {
+    let bytes = statement.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = placeholders::skip_literal_or_comment(bytes, i) {
+            i = end + 1;
+            continue;
+        }
+        let end = (i..bytes.len()).find(|&end| !(bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_'));
+        let end = end.unwrap_or(bytes.len());
+        if end == i {
+            i += 1;
+        } else if statement[i..end].eq_ignore_ascii_case("RETURNING") {
+            return true;
+        } else {
+            i = end;
+        }
+    }
+    false
+}
+
+/// Check that the name of a session variable can be safely used in a statement without being quoted.
+fn validate_session_var_name(name: &str) -> Result<()> {
+    let mut offset = 0;
+    for part in name.split('.') {
+        let valid = part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(Error::InputError {
+                message: "Invalid session variable name".to_string(),
+                input: name.to_string(),
+                offset,
+                error: "a session variable name must only contain ASCII letters, digits, underscores and dots".into(),
+            });
+        }
+        offset += part.len() + 1;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use squill_core::{assert_ok, params};
 
+    #[test]
+    fn test_has_returning() {
+        assert!(has_returning("INSERT INTO t (name) VALUES ($1) RETURNING id"));
+        assert!(has_returning("insert into t (name) values ($1) returning\tid"));
+        assert!(!has_returning("INSERT INTO t (name) VALUES ($1)"));
+        assert!(!has_returning("INSERT INTO t (name) VALUES ('not returning')"));
+        assert!(!has_returning("INSERT INTO t (name) VALUES ($$returning$$) -- RETURNING id"));
+        assert!(!has_returning("INSERT INTO t (returning_id) VALUES (1)"));
+    }
+
     #[test]
     fn test_prepare() {
         let mut conn = Connection::open("mock://").unwrap();
@@ -138,6 +665,22 @@ mod tests {
         assert_ok!(conn.ping());
     }
 
+    #[test]
+    fn test_downcast_mut() {
+        use squill_core::driver::MockDriverConnection;
+        let mut conn = Connection::open("mock://").unwrap();
+        assert!(conn.downcast_mut::<MockDriverConnection>().is_some());
+    }
+
+    #[test]
+    fn test_query_scalar() {
+        let mut conn = Connection::open("mock://").unwrap();
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i32>("SELECT 2", None)), Some(1));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i32>("SELECT 0", None)), None);
+        assert!(conn.query_scalar::<_, i64>("SELECT 1", None).is_err());
+        assert!(conn.query_scalar::<_, i32>("SELECT -1", None).is_err());
+    }
+
     #[test]
     fn test_query_map_row() {
         struct TestUser {
@@ -178,6 +721,16 @@ mod tests {
                 }
             })
             .is_err());
+
+        // a decoding error in the mapping function is returned instead of panicking
+        assert!(matches!(
+            conn.query_map_rows("SELECT 2", None, |row| Ok(row.try_get::<_, bool>("username")?)),
+            Err(Error::Column { name, index: 1, .. }) if name == "username"
+        ));
+        assert!(matches!(
+            conn.query_map_row("SELECT 1", None, |row| Ok(row.try_get_nullable::<_, i32>("unknown")?)),
+            Err(Error::NotFound)
+        ));
     }
 
     #[test]
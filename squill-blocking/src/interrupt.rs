@@ -0,0 +1,39 @@
+use squill_core::driver::DriverInterruptHandle;
+
+/// A handle to interrupt the statement running on a [crate::Connection] from another thread.
+///
+/// The handle is obtained using [crate::Connection::interrupt_handle] and can be sent to another thread. The statement
+/// interrupted fails with [squill_core::Error::Cancelled] and the connection remains usable.
+///
+/// ```rust,ignore
+/// let handle = conn.interrupt_handle();
+/// std::thread::spawn(move || {
+///     std::thread::sleep(Duration::from_secs(1));
+///     handle.interrupt();
+/// });
+/// assert!(matches!(conn.query_row(slow_query, None), Err(Error::Cancelled)));
+/// ```
+pub struct InterruptHandle {
+    /// The handle of the driver, `None` if the driver doesn't support interrupting a statement.
+    inner: Option<Box<dyn DriverInterruptHandle>>,
+}
+
+impl InterruptHandle {
+    pub(crate) fn new(inner: Option<Box<dyn DriverInterruptHandle>>) -> Self {
+        Self { inner }
+    }
+
+    /// Interrupt the statement running on the connection.
+    ///
+    /// This is a no-op if no statement is running or if the driver doesn't support interrupting a statement.
+    pub fn interrupt(&self) {
+        if let Some(inner) = &self.inner {
+            inner.interrupt();
+        }
+    }
+
+    /// Check if the driver of the connection supports interrupting a statement.
+    pub fn is_supported(&self) -> bool {
+        self.inner.is_some()
+    }
+}
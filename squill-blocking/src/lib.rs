@@ -1,17 +1,33 @@
 #![forbid(unsafe_code)]
 
 pub mod connection;
+pub mod interrupt;
+pub mod metrics;
+pub mod owned_rows;
 pub mod rows;
+mod slow_query;
 pub mod statement;
+pub mod transaction;
 
 pub use connection::Connection;
-pub use rows::Rows;
+pub use interrupt::InterruptHandle;
+pub use metrics::Metrics;
+pub use owned_rows::OwnedRows;
+pub use rows::{PeekableRows, Rows};
 pub use statement::Statement;
+pub use transaction::{Savepoint, Transaction};
 
 #[cfg(test)]
 mod blocking_tests {
     use crate::connection::Connection;
+    use crate::{Metrics, OwnedRows, Rows};
+    use arrow_array::{Int32Array, RecordBatch};
     use squill_core::error::Error;
+    use squill_core::params;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     #[test]
     fn test_query_rows() {
@@ -26,12 +42,257 @@ mod blocking_tests {
         assert!(rows.next().is_none());
     }
 
+    #[test]
+    fn test_rows_peekable() {
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        let mut rows = stmt.query_rows(None).unwrap().peekable();
+
+        // Peeking doesn't consume the row, even when called several times.
+        assert_eq!(rows.peek().unwrap().as_ref().unwrap().get::<_, i32>("id"), 1);
+        assert_eq!(rows.peek().unwrap().as_ref().unwrap().get::<_, i32>("id"), 1);
+        assert_eq!(rows.next().unwrap().unwrap().get::<_, i32>("id"), 1);
+        assert_eq!(rows.peek().unwrap().as_ref().unwrap().get::<_, String>("username"), "user2");
+        assert_eq!(rows.next().unwrap().unwrap().get::<_, i32>("id"), 2);
+        assert!(rows.peek().is_none());
+        assert!(rows.next().is_none());
+        drop(rows);
+
+        // Iterating without peeking returns all the rows.
+        let rows = stmt.query_rows(None).unwrap().peekable();
+        assert_eq!(rows.map(|row| row.unwrap().get::<_, i32>(0)).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_rows_take() {
+        // An endless cursor of batches of 2 rows, recording the number of batches fetched and when it is dropped.
+        struct Cursor {
+            fetched: Rc<Cell<usize>>,
+            dropped: Rc<Cell<bool>>,
+        }
+        impl Iterator for Cursor {
+            type Item = squill_core::Result<RecordBatch>;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.fetched.set(self.fetched.get() + 1);
+                Some(Ok(RecordBatch::try_from_iter(vec![("id", Arc::new(Int32Array::from(vec![1, 2])) as _)]).unwrap()))
+            }
+        }
+        impl Drop for Cursor {
+            fn drop(&mut self) {
+                self.dropped.set(true);
+            }
+        }
+        let fetched = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(false));
+        let cursor = || -> Box<dyn Iterator<Item = squill_core::Result<RecordBatch>>> {
+            Box::new(Cursor { fetched: fetched.clone(), dropped: dropped.clone() })
+        };
+
+        let mut rows = Rows::from(cursor()).take(3);
+        assert_eq!(rows.by_ref().map(|row| row.unwrap().get::<_, i32>(0)).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(fetched.get(), 2);
+        assert!(dropped.get()); // The cursor is dropped before the rows.
+        assert!(rows.next().is_none());
+        assert_eq!(fetched.get(), 2);
+
+        // Limiting the batches of partially consumed rows.
+        fetched.set(0);
+        dropped.set(false);
+        let mut rows = Rows::from(cursor()).take(4);
+        assert_eq!(rows.next().unwrap().unwrap().get::<_, i32>(0), 1);
+        let batches = rows.into_batches().map(|batch| batch.unwrap().num_rows()).collect::<Vec<_>>();
+        assert_eq!(batches, vec![1, 2]);
+        assert_eq!(fetched.get(), 2);
+        assert!(dropped.get());
+
+        // Using a statement.
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 3").unwrap();
+        assert_eq!(stmt.query_rows_limited(None, 2).unwrap().count(), 2);
+        assert_eq!(stmt.query_rows_limited(None, 5).unwrap().count(), 3);
+        assert_eq!(stmt.query_rows_limited(None, 0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut conn = Connection::open("mock://").unwrap();
+        assert_eq!(conn.metrics(), None);
+        assert_eq!(conn.query_map_rows("SELECT 2", None, Ok).unwrap().len(), 2);
+        assert_eq!(conn.metrics(), None);
+
+        conn.set_metrics_enabled(true);
+        assert_eq!(conn.metrics(), Some(Metrics::default()));
+        assert_eq!(conn.execute("INSERT 1", None).unwrap(), 1);
+        let mut stmt = conn.prepare("SELECT 3").unwrap();
+        assert_eq!(stmt.query_rows(None).unwrap().count(), 3);
+        assert_eq!(stmt.query_rows(None).unwrap().take(1).count(), 1);
+        drop(stmt);
+        let mut stmt = conn.prepare("INSERT ?").unwrap();
+        stmt.execute_many((1..=4).map(|id| params!(id).unwrap())).unwrap();
+        drop(stmt);
+        conn.describe("SELECT 1").unwrap();
+
+        let metrics = conn.metrics().unwrap();
+        assert_eq!(metrics.statements_prepared, 3);
+        assert_eq!(metrics.statements_described, 1);
+        assert_eq!(metrics.statements_executed, 7);
+        assert_eq!(metrics.rows_fetched, 6);
+        assert!(metrics.elapsed > Duration::ZERO);
+
+        // Disabling the metrics discards the counters.
+        conn.set_metrics_enabled(false);
+        assert_eq!(conn.metrics(), None);
+    }
+
+    #[test]
+    fn test_into_query_rows() {
+        fn query_users(conn: &mut Connection, count: usize) -> squill_core::Result<OwnedRows<'_>> {
+            conn.into_query_rows(format!("SELECT {}", count), None)
+        }
+
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut rows = query_users(&mut conn, 3).unwrap();
+        assert!(rows.current_batch().is_none());
+        assert_eq!(rows.next().unwrap().unwrap().get::<_, i32>(0), 1);
+        assert_eq!(rows.current_batch().unwrap().num_rows(), 3);
+        let usernames: Vec<String> = rows.map(|row| row.unwrap().get::<_, String>("username")).collect();
+        assert_eq!(usernames, vec!["user2", "user3"]);
+
+        // The statement is owned by the rows.
+        let stmt = conn.prepare("SELECT 2").unwrap();
+        assert_eq!(stmt.into_query_rows(None).unwrap().count(), 2);
+
+        assert!(query_users(&mut conn, 0).unwrap().next().is_none());
+        assert!(conn.into_query_rows("SELECT -1", None).unwrap().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_interrupt_handle() {
+        // The mock driver doesn't support interrupting a statement, the handle does nothing.
+        let mut conn = Connection::open("mock://").unwrap();
+        let handle = conn.interrupt_handle();
+        assert!(!handle.is_supported());
+        std::thread::spawn(move || handle.interrupt()).join().unwrap();
+        assert_eq!(conn.query_map_rows("SELECT 2", None, Ok).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_estimated_row_count() {
+        // The mock driver doesn't provide an estimate.
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        assert_eq!(stmt.query_rows(None).unwrap().count(), 2);
+        assert_eq!(stmt.estimated_row_count(), None);
+    }
+
+    #[test]
+    fn test_record_batch_access() {
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 3").unwrap();
+        let mut rows = stmt.query_rows(None).unwrap();
+        assert!(rows.current_batch().is_none());
+
+        let row = rows.next().unwrap().unwrap();
+        assert_eq!(row.row_index(), 0);
+        let ids = row.record_batch().column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(ids.value(row.row_index()), row.get::<_, i32>(0));
+        assert_eq!(rows.current_batch().unwrap().num_rows(), 3);
+
+        // The remaining rows of the current batch are returned first.
+        let row = rows.next().unwrap().unwrap();
+        assert_eq!(row.row_index(), 1);
+        assert_eq!(row.get::<_, String>(1), "user2");
+        let batches = rows.into_batches().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 1);
+        assert_eq!(batches[0].column(0).as_any().downcast_ref::<Int32Array>().unwrap().value(0), 3);
+
+        // All the rows consumed.
+        let mut rows = stmt.query_rows(None).unwrap();
+        assert_eq!(rows.by_ref().count(), 3);
+        assert!(rows.current_batch().is_none());
+        assert_eq!(rows.into_batches().count(), 0);
+    }
+
     #[test]
     fn test_try_get() {
         let mut conn = Connection::open("mock://").unwrap();
         let row = conn.query_row("SELECT 1", None).unwrap().unwrap();
         assert_eq!(row.try_get::<_, i32>(0).unwrap(), 1);
-        assert!(matches!(row.try_get::<_, i64>(0), Err(Error::InvalidType { expected: _, actual: _ })));
+        assert!(matches!(
+            row.try_get::<_, i64>(0),
+            Err(Error::Column { index: 0, source, .. }) if matches!(*source, Error::InvalidType { .. })
+        ));
         assert!(matches!(row.try_get::<_, i32>(7), Err(Error::OutOfBounds { index: _ })));
     }
+
+    #[test]
+    fn test_on_slow_query() {
+        let mut conn = Connection::open("mock://?delay_ms=20").unwrap();
+        let reported: Rc<std::cell::RefCell<Vec<(String, Duration)>>> = Rc::default();
+        let reported_clone = reported.clone();
+        conn.on_slow_query(Duration::from_millis(10), move |statement, elapsed| {
+            reported_clone.borrow_mut().push((statement.to_string(), elapsed));
+        });
+
+        // The query is reported once its rows are dropped, the time includes the fetching of the batches.
+        let mut stmt = conn.prepare("SELECT\n  2").unwrap();
+        let rows = stmt.query_rows(None).unwrap();
+        assert!(reported.borrow().is_empty());
+        assert_eq!(rows.count(), 2);
+        assert_eq!(reported.borrow().len(), 1);
+        assert_eq!(reported.borrow()[0].0, "SELECT  2");
+        assert!(reported.borrow()[0].1 >= Duration::from_millis(20));
+        drop(stmt);
+
+        // Below the threshold, the callback is not invoked.
+        conn.on_slow_query(Duration::from_secs(3600), |_, _| panic!("unexpected slow query"));
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        assert_eq!(stmt.query_rows(None).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_query_with_schema() {
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        let (schema, batches) = stmt.query_with_schema(None).unwrap();
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(schema.field(1).name(), "username");
+        assert_eq!(batches.map(|batch| batch.unwrap().num_rows()).sum::<usize>(), 2);
+        drop(stmt);
+
+        // The schema is available even if the query returns no rows.
+        let mut stmt = conn.prepare("SELECT 0").unwrap();
+        let (schema, mut batches) = stmt.query_with_schema(None).unwrap();
+        assert_eq!(schema.fields().len(), 2);
+        assert!(batches.next().is_none());
+    }
+
+    #[test]
+    fn test_query_projected() {
+        let mut conn = Connection::open("mock://").unwrap();
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        let batches = stmt.query_projected(None, &["username"]).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_columns(), 1);
+        assert_eq!(batches[0].schema().field(0).name(), "username");
+        assert_eq!(batches[0].num_rows(), 2);
+
+        // The columns are in the order they are requested.
+        let mut batches = stmt.query_projected(None, &["username", "id"]).unwrap();
+        let batch = batches.next().unwrap().unwrap();
+        assert_eq!(batch.schema().field(0).name(), "username");
+        assert_eq!(batch.schema().field(1).name(), "id");
+        drop(batches);
+
+        assert!(matches!(stmt.query_projected(None, &["unknown"]), Err(Error::NotFound)));
+    }
+
+    // The driver handles are not `Send`, see the thread safety section of `Connection`.
+    static_assertions::assert_not_impl_any!(Connection: Send, Sync);
+    static_assertions::assert_not_impl_any!(crate::Statement<'static>: Send, Sync);
+    static_assertions::assert_not_impl_any!(crate::Transaction<'static>: Send, Sync);
+    static_assertions::assert_not_impl_any!(Rows<'static>: Send, Sync);
+    static_assertions::assert_not_impl_any!(OwnedRows<'static>: Send, Sync);
+    static_assertions::assert_impl_all!(crate::InterruptHandle: Send, Sync);
 }
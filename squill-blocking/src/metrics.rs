@@ -0,0 +1,76 @@
+use arrow_array::RecordBatch;
+use squill_core::Result;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// The counters of the activity of a [crate::Connection].
+///
+/// The metrics are disabled by default, once enabled by [crate::Connection::set_metrics_enabled] they can be read
+/// using [crate::Connection::metrics].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    /// The number of statements prepared.
+    pub statements_prepared: u64,
+
+    /// The number of statements described without being executed (see [crate::Connection::describe]).
+    pub statements_described: u64,
+
+    /// The number of executions of statements, including the queries (an `execute_many` counts for each set of
+    /// parameters).
+    pub statements_executed: u64,
+
+    /// The number of rows fetched from the driver by the queries.
+    pub rows_fetched: u64,
+
+    /// The cumulative time spent by the driver to prepare, execute and fetch the rows of the statements.
+    pub elapsed: Duration,
+}
+
+/// The metrics shared by a connection and its statements (`None` if the metrics are disabled).
+pub(crate) type MetricsRef = Option<Rc<RefCell<Metrics>>>;
+
+/// Run an operation and record it in the metrics if they are enabled.
+///
+/// The time spent by the operation is added to the metrics and `update` is called to increment the counters. When the
+/// metrics are disabled, the operation is run without any overhead.
+pub(crate) fn record<T, F, U>(metrics: &MetricsRef, operation: F, update: U) -> T
+where
+    F: FnOnce() -> T,
+    U: FnOnce(&mut Metrics, &T),
+{
+    match metrics {
+        None => operation(),
+        Some(metrics) => {
+            let start = Instant::now();
+            let result = operation();
+            let mut metrics = metrics.borrow_mut();
+            metrics.elapsed += start.elapsed();
+            update(&mut metrics, &result);
+            result
+        }
+    }
+}
+
+/// An iterator over the record batches of a query recording the rows fetched in the metrics.
+pub(crate) struct RecordedBatches<'i> {
+    pub(crate) inner: Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>,
+    pub(crate) metrics: MetricsRef,
+}
+
+impl Iterator for RecordedBatches<'_> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Result<RecordBatch>> {
+        let inner = &mut self.inner;
+        record(
+            &self.metrics,
+            || inner.next(),
+            |metrics, batch| {
+                if let Some(Ok(batch)) = batch {
+                    metrics.rows_fetched += batch.num_rows() as u64;
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use crate::rows::Rows;
+use crate::statement::Statement;
+use arrow_array::RecordBatch;
+use ouroboros::self_referencing;
+use squill_core::parameters::Parameters;
+use squill_core::row::Row;
+use squill_core::Result;
+
+/// An iterator over the rows returned by a query that owns the statement it was queried from.
+///
+/// Unlike [Rows] which borrows its [Statement], `OwnedRows` only borrows the connection, so it can be returned from a
+/// function and consumed by the caller. The statement is dropped along with the rows.
+///
+/// ```rust
+/// use squill_blocking::{Connection, OwnedRows};
+///
+/// fn employees(conn: &mut Connection) -> squill_core::Result<OwnedRows<'_>> {
+///     conn.into_query_rows("SELECT 2", None)
+/// }
+///
+/// let mut conn = Connection::open("mock://").unwrap();
+/// assert_eq!(employees(&mut conn).unwrap().count(), 2);
+/// ```
+pub struct OwnedRows<'c> {
+    inner: OwnedRowsInner<'c>,
+}
+
+#[self_referencing]
+struct OwnedRowsInner<'c> {
+    statement: Statement<'c>,
+
+    #[borrows(mut statement)]
+    #[not_covariant]
+    rows: Rows<'this>,
+}
+
+impl<'c> OwnedRows<'c> {
+    pub(crate) fn new(statement: Statement<'c>, parameters: Option<Parameters>) -> Result<Self> {
+        let inner = OwnedRowsInner::try_new(statement, |statement| statement.query_rows(parameters))?;
+        Ok(Self { inner })
+    }
+
+    /// Get the record batch of the last row returned by the iterator.
+    ///
+    /// See [Rows::current_batch] for more information.
+    pub fn current_batch(&self) -> Option<&RecordBatch> {
+        self.inner.with_rows(|rows| rows.current_batch())
+    }
+}
+
+impl Iterator for OwnedRows<'_> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Result<Row>> {
+        self.inner.with_rows_mut(|rows| rows.next())
+    }
+}
@@ -8,11 +8,91 @@ pub struct Rows<'i> {
     last_record_batch: Option<Arc<RecordBatch>>,
     iterator: Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>,
     index_in_batch: usize,
+    /// The number of rows that can still be returned when the rows are limited by [Rows::take].
+    remaining: Option<usize>,
+}
+
+impl<'i> Rows<'i> {
+    /// Get the record batch of the last row returned by the iterator.
+    ///
+    /// Returns `None` if no row has been returned yet or if all the rows have been consumed.
+    pub fn current_batch(&self) -> Option<&RecordBatch> {
+        self.last_record_batch.as_deref()
+    }
+
+    /// Limit the rows to the first `n` rows not returned yet.
+    ///
+    /// Unlike [Iterator::take], the batches are no longer fetched from the driver once `n` rows have been returned and
+    /// the cursor of the driver is dropped right away, without waiting for the rows to be dropped.
+    pub fn take(mut self, n: usize) -> Self {
+        let remaining = self.remaining.map_or(n, |remaining| remaining.min(n));
+        self.remaining = Some(remaining);
+        if remaining == 0 {
+            self.release();
+        }
+        self
+    }
+
+    /// Convert the rows into an iterator over the record batches.
+    ///
+    /// If the current batch has been partially consumed, the first batch returned is a zero-copy slice of the rows
+    /// not returned yet.
+    pub fn into_batches(self) -> Box<dyn Iterator<Item = Result<RecordBatch>> + 'i> {
+        let batches = match self.last_record_batch {
+            Some(record_batch) if self.index_in_batch < record_batch.num_rows() => {
+                let remaining = record_batch.slice(self.index_in_batch, record_batch.num_rows() - self.index_in_batch);
+                Box::new(std::iter::once(Ok(remaining)).chain(self.iterator))
+            }
+            _ => self.iterator,
+        };
+        match self.remaining {
+            None => batches,
+            Some(remaining) => Box::new(LimitedBatches { batches: Some(batches), remaining }),
+        }
+    }
+
+    /// Convert the rows into [PeekableRows], allowing to look at the next row without consuming it.
+    pub fn peekable(self) -> PeekableRows<'i> {
+        PeekableRows { rows: self, peeked: None }
+    }
+
+    /// Drop the iterator over the batches, releasing the cursor of the driver.
+    fn release(&mut self) {
+        self.iterator = Box::new(std::iter::empty());
+    }
+}
+
+/// An iterator over the record batches limited to a number of rows.
+struct LimitedBatches<'i> {
+    batches: Option<Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>>,
+    remaining: usize,
+}
+
+impl Iterator for LimitedBatches<'_> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Result<RecordBatch>> {
+        if self.remaining == 0 {
+            self.batches = None;
+            return None;
+        }
+        let batch = match self.batches.as_mut()?.next()? {
+            Ok(batch) if batch.num_rows() > self.remaining => batch.slice(0, self.remaining),
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(e)),
+        };
+        self.remaining -= batch.num_rows();
+        if self.remaining == 0 {
+            // The cursor of the driver is released as soon as the limit is reached.
+            self.batches = None;
+        }
+        Some(Ok(batch))
+    }
 }
 
 impl<'i> From<Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>> for Rows<'i> {
     fn from(iterator: Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>) -> Self {
-        Rows { last_record_batch: None, iterator, index_in_batch: 0 }
+        Rows { last_record_batch: None, iterator, index_in_batch: 0, remaining: None }
     }
 }
 
@@ -21,7 +101,10 @@ impl<'i> Iterator for Rows<'i> {
     type Item = Result<Row>;
 
     fn next(&mut self) -> Option<Result<Row>> {
-        if self.last_record_batch.is_none() {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        while !matches!(&self.last_record_batch, Some(batch) if self.index_in_batch < batch.num_rows()) {
             // First call or we've exhausted the last batch.
             self.last_record_batch = match self.iterator.next() {
                 Some(Ok(record_batch)) => {
@@ -31,23 +114,65 @@ impl<'i> Iterator for Rows<'i> {
                 }
                 Some(Err(e)) => {
                     // AN error occurred while fetching the next batch.
+                    self.last_record_batch = None;
                     return Some(Err(e));
                 }
                 // No more batches available.
-                None => None,
-            };
-        }
-        match &self.last_record_batch {
-            None => None,
-            Some(last_record_batch) => {
-                let row = Row::new(last_record_batch.clone(), self.index_in_batch);
-                self.index_in_batch += 1;
-                if self.index_in_batch >= last_record_batch.num_rows() {
-                    // we've exhausted the current batch
+                None => {
                     self.last_record_batch = None;
+                    return None;
                 }
-                Some(Ok(row))
+            };
+        }
+        let row = Row::new(self.last_record_batch.clone()?, self.index_in_batch);
+        self.index_in_batch += 1;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+            if *remaining == 0 {
+                // The cursor of the driver is released as soon as the limit is reached.
+                self.release();
             }
         }
+        Some(Ok(row))
+    }
+}
+
+/// An iterator over the rows returned by a query that can look at the next row without consuming it.
+///
+/// Created by [Rows::peekable]. A [Row] only holds a reference to its record batch so buffering the next row is cheap.
+///
+/// ```rust,ignore
+/// let mut rows = stmt.query_rows(None)?.peekable();
+/// if rows.peek().is_none() {
+///     println!("No rows");
+/// }
+/// for row in rows {
+///     println!("{}", row?.get::<_, String>("name"));
+/// }
+/// ```
+pub struct PeekableRows<'i> {
+    rows: Rows<'i>,
+    /// The next row if it has already been fetched by [PeekableRows::peek] (`Some(None)` if there is no more rows).
+    peeked: Option<Option<Result<Row>>>,
+}
+
+impl PeekableRows<'_> {
+    /// Get a reference to the next row without consuming it.
+    ///
+    /// Returns `None` if there is no more rows.
+    pub fn peek(&mut self) -> Option<&Result<Row>> {
+        let rows = &mut self.rows;
+        self.peeked.get_or_insert_with(|| rows.next()).as_ref()
+    }
+}
+
+impl Iterator for PeekableRows<'_> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Result<Row>> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.rows.next(),
+        }
     }
 }
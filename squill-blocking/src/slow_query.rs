@@ -0,0 +1,81 @@
+use arrow_array::RecordBatch;
+use squill_core::Result;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A callback invoked with a slow statement and the time it took.
+pub(crate) type SlowQueryCallback = Box<dyn Fn(&str, Duration)>;
+
+/// The callback registered by [crate::Connection::on_slow_query] along with its threshold.
+pub(crate) struct SlowQueryHook {
+    pub(crate) threshold: Duration,
+    pub(crate) callback: SlowQueryCallback,
+}
+
+/// The slow query hook shared by a connection and its statements (`None` if no callback is registered).
+pub(crate) type SlowQueryRef = Option<Rc<SlowQueryHook>>;
+
+/// The slow query hook of a statement along with the statement it was prepared from.
+pub(crate) struct SlowQuery {
+    hook: Rc<SlowQueryHook>,
+    statement: String,
+}
+
+impl SlowQuery {
+    /// Get the slow query hook of a statement, the statement is only copied if a callback is registered.
+    pub(crate) fn new(hook: &SlowQueryRef, statement: &str) -> Option<Self> {
+        hook.as_ref().map(|hook| Self { hook: hook.clone(), statement: statement.to_string() })
+    }
+
+    /// Invoke the callback with the cleaned statement if `elapsed` exceeds the threshold.
+    pub(crate) fn check(&self, elapsed: Duration) {
+        if elapsed > self.hook.threshold {
+            (self.hook.callback)(&squill_core::debug_clean_statement(&self.statement), elapsed);
+        }
+    }
+}
+
+/// Run an operation and invoke the slow query callback if it took longer than the threshold.
+///
+/// When no callback is registered, the operation is run without any overhead.
+pub(crate) fn timed<T, F>(slow_query: &Option<SlowQuery>, operation: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    match slow_query {
+        None => operation(),
+        Some(slow_query) => {
+            let start = Instant::now();
+            let result = operation();
+            slow_query.check(start.elapsed());
+            result
+        }
+    }
+}
+
+/// An iterator over the record batches of a query measuring the time spent to fetch them.
+///
+/// The time spent to execute the query and to fetch all the batches is checked against the threshold when the iterator
+/// is dropped, so a query is reported once whether its batches have been fully consumed or not.
+pub(crate) struct TimedBatches<'i> {
+    pub(crate) inner: Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>,
+    pub(crate) slow_query: &'i SlowQuery,
+    pub(crate) elapsed: Duration,
+}
+
+impl Iterator for TimedBatches<'_> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Result<RecordBatch>> {
+        let start = Instant::now();
+        let batch = self.inner.next();
+        self.elapsed += start.elapsed();
+        batch
+    }
+}
+
+impl Drop for TimedBatches<'_> {
+    fn drop(&mut self) {
+        self.slow_query.check(self.elapsed);
+    }
+}
@@ -1,56 +1,227 @@
+use crate::metrics::{record, MetricsRef, RecordedBatches};
+use crate::owned_rows::OwnedRows;
 use crate::rows::Rows;
+use crate::slow_query::{timed, SlowQuery, TimedBatches};
 use arrow_array::RecordBatch;
-use arrow_schema::SchemaRef;
-use squill_core::driver::DriverStatement;
+use arrow_schema::{DataType, SchemaRef};
+use squill_core::decode::Decode;
+use squill_core::driver::{DriverStatement, ExecuteOutcome, Result as DriverResult};
 use squill_core::parameters::Parameters;
+use squill_core::placeholders::{self, Placeholder};
 use squill_core::row::Row;
 use squill_core::{Error, Result};
+use std::time::Instant;
+
+/// The record batches returned by a query.
+type Batches<'i> = Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>;
+
+/// The record batches returned by the driver for a query.
+type DriverBatches<'s> = Box<dyn Iterator<Item = DriverResult<RecordBatch>> + 's>;
 
 /// A prepared statement.
 ///
 /// A statement is a query that has been prepared for execution. It can be bound with parameters and executed.
 pub struct Statement<'c> {
     pub(crate) inner: Box<dyn DriverStatement + 'c>,
+
+    /// The text of the statement as given to [crate::Connection::prepare], before its placeholders are rewritten.
+    pub(crate) text: String,
+
+    pub(crate) metrics: MetricsRef,
+
+    /// The maximum number of rows of the record batches set by [Statement::set_max_batch_rows].
+    pub(crate) max_batch_rows: Option<usize>,
+
+    /// The slow query callback registered by [crate::Connection::on_slow_query] (`None` if not registered).
+    pub(crate) slow_query: Option<SlowQuery>,
 }
 
-impl Statement<'_> {
+impl<'c> Statement<'c> {
     pub fn execute(&mut self, parameters: Option<Parameters>) -> Result<u64> {
-        self.inner.execute(parameters).map_err(Error::from)
+        let inner = &mut self.inner;
+        timed(&self.slow_query, || {
+            record(&self.metrics, || inner.execute(parameters), |metrics, _| metrics.statements_executed += 1)
+        })
+        .map_err(Error::from)
+    }
+
+    /// Execute the statement and return the details of the execution.
+    ///
+    /// Besides the number of rows affected, the outcome may contain the id of the inserted row and the warnings raised
+    /// by the execution depending on the driver (see [ExecuteOutcome]).
+    pub fn execute_detailed(&mut self, parameters: Option<Parameters>) -> Result<ExecuteOutcome> {
+        let inner = &mut self.inner;
+        timed(&self.slow_query, || {
+            record(&self.metrics, || inner.execute_detailed(parameters), |metrics, _| metrics.statements_executed += 1)
+        })
+        .map_err(Error::from)
+    }
+
+    /// Execute the statement once for each set of parameters.
+    ///
+    /// This is more efficient than calling [Statement::execute] in a loop when the driver supports it (e.g. SQLite
+    /// executes all of them in a single transaction).
+    ///
+    /// Returns the total number of rows affected.
+    pub fn execute_many<I: IntoIterator<Item = Parameters>>(&mut self, parameters: I) -> Result<u64> {
+        let parameters: Vec<Parameters> = parameters.into_iter().collect();
+        let executions = parameters.len() as u64;
+        let inner = &mut self.inner;
+        timed(&self.slow_query, || {
+            record(
+                &self.metrics,
+                || inner.execute_many(parameters),
+                |metrics, _| metrics.statements_executed += executions,
+            )
+        })
+        .map_err(Error::from)
     }
 
     pub fn query<'s: 'i, 'i>(
         &'s mut self,
         parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 'i>> {
-        match self.inner.query(parameters) {
-            Ok(iterator) => {
-                let iterator = iterator.map(|result| result.map_err(Error::from));
-                Ok(Box::new(iterator))
+        let ((), iterator) = self.run_query(|inner| Ok(((), inner.query(parameters)?)))?;
+        Ok(iterator)
+    }
+
+    /// Query a statement and return the schema of its rows along with an iterator over its record batches.
+    ///
+    /// Unlike [Statement::schema] that can only be called once the iterator returned by [Statement::query] has been
+    /// consumed and dropped, the schema is captured when the query is executed and is available even if the query
+    /// returns no rows.
+    ///
+    /// ```rust,ignore
+    /// let (schema, batches) = stmt.query_with_schema(None)?;
+    /// println!("{:?}", schema.fields());
+    /// ```
+    pub fn query_with_schema<'s: 'i, 'i>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+    ) -> Result<(SchemaRef, Batches<'i>)> {
+        self.run_query(|inner| inner.query_with_schema(parameters))
+    }
+
+    /// Query a statement and return an iterator over its record batches projected to the given columns.
+    ///
+    /// The batches only contain the columns named in `columns`, in the given order, which saves decoding the other
+    /// columns of a wide result. The statement itself is not modified, so all the columns are still fetched by the
+    /// driver. An [Error::NotFound] is returned if a column is not in the result of the query.
+    ///
+    /// ```rust,ignore
+    /// let batches = stmt.query_projected(None, &["id", "name"])?;
+    /// ```
+    pub fn query_projected<'s: 'i, 'i>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+        columns: &[&str],
+    ) -> Result<Batches<'i>> {
+        let (schema, batches) = self.query_with_schema(parameters)?;
+        let indices = columns
+            .iter()
+            .map(|column| schema.index_of(column).map_err(|_| Error::NotFound))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Box::new(batches.map(move |batch| Ok(batch?.project(&indices)?))))
+    }
+
+    /// Run a query, recording it in the metrics and timing it for the slow query callback when they are enabled.
+    fn run_query<'s: 'i, 'i, T, F>(&'s mut self, query: F) -> Result<(T, Batches<'i>)>
+    where
+        F: FnOnce(&'s mut Box<dyn DriverStatement + 'c>) -> DriverResult<(T, DriverBatches<'s>)>,
+    {
+        let inner = &mut self.inner;
+        let start = self.slow_query.as_ref().map(|_| Instant::now());
+        let result = record(&self.metrics, || query(inner), |metrics, _| metrics.statements_executed += 1);
+        match result {
+            Ok((value, iterator)) => {
+                let iterator: Batches<'i> = Box::new(iterator.map(|result| result.map_err(Error::from)));
+                let iterator = match &self.metrics {
+                    None => iterator,
+                    Some(_) => Box::new(RecordedBatches { inner: iterator, metrics: self.metrics.clone() }),
+                };
+                match (&self.slow_query, start) {
+                    (Some(slow_query), Some(start)) => {
+                        Ok((value, Box::new(TimedBatches { inner: iterator, slow_query, elapsed: start.elapsed() })))
+                    }
+                    _ => Ok((value, iterator)),
+                }
+            }
+            Err(e) => {
+                if let (Some(slow_query), Some(start)) = (&self.slow_query, start) {
+                    slow_query.check(start.elapsed());
+                }
+                Err(Error::from(e))
             }
-            Err(e) => Err(Error::from(e)),
         }
     }
 
     /// Query a statement and return an iterator of [Row].
-    pub fn query_rows<'s: 'i, 'i>(
-        &'s mut self,
-        parameters: Option<Parameters>,
-    ) -> Result<Box<dyn Iterator<Item = Result<Row>> + 'i>> {
+    pub fn query_rows<'s: 'i, 'i>(&'s mut self, parameters: Option<Parameters>) -> Result<Rows<'i>> {
         match self.query(parameters) {
-            Ok(iterator) => Ok(Box::new(Rows::from(iterator))),
+            Ok(iterator) => Ok(Rows::from(iterator)),
             Err(e) => Err(e),
         }
     }
 
+    /// Query a statement and return an iterator over its first `limit` rows.
+    ///
+    /// The batches are no longer fetched from the driver once the limit is reached, see [Rows::take].
+    pub fn query_rows_limited<'s: 'i, 'i>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+        limit: usize,
+    ) -> Result<Rows<'i>> {
+        Ok(self.query_rows(parameters)?.take(limit))
+    }
+
+    /// Query a statement and return an iterator of [Row] that takes ownership of the statement.
+    ///
+    /// See [OwnedRows] for more information.
+    pub fn into_query_rows(self, parameters: Option<Parameters>) -> Result<OwnedRows<'c>> {
+        OwnedRows::new(self, parameters)
+    }
+
     /// Query a statement that is expected to return a single [Row].
     ///
     /// Returns `Ok(None)` if the query returned no rows.
     /// If the query returns more than one row, the function will return an the first row and ignore the rest.
+    ///
+    /// Only the first row being needed, the driver is asked to fetch a record batch of a single row instead of a full
+    /// batch (see [Statement::set_max_batch_rows]).
     pub fn query_row(&mut self, parameters: Option<Parameters>) -> Result<Option<Row>> {
-        let mut rows = self.query_rows(parameters)?;
-        match rows.next() {
-            Some(Ok(row)) => Ok(Some(row)),
-            Some(Err(e)) => Err(e),
+        self.inner.set_max_batch_rows(Some(1));
+        let row = self.query_rows(parameters).and_then(|mut rows| rows.next().transpose());
+        self.inner.set_max_batch_rows(self.max_batch_rows);
+        row
+    }
+
+    /// Set the maximum number of rows of the record batches fetched by the queries of the statement, overriding the
+    /// `max_batch_rows` option of the connection (`None` to restore it).
+    ///
+    /// Smaller batches reduce the rows fetched ahead of the ones consumed, larger batches reduce the overhead per row.
+    /// This is only a hint ignored by the drivers not building the record batches themselves (i.e. DuckDB).
+    pub fn set_max_batch_rows(&mut self, max_batch_rows: Option<usize>) {
+        self.max_batch_rows = max_batch_rows;
+        self.inner.set_max_batch_rows(max_batch_rows);
+    }
+
+    /// Query a statement that is expected to return a single value.
+    ///
+    /// The value is decoded from the first column of the first row.
+    /// Returns `Ok(None)` if the query returned no rows or if the value is null.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use squill_blocking::Connection;
+    ///
+    /// let mut conn = Connection::open("mock://").unwrap();
+    /// let mut stmt = conn.prepare("SELECT 3").unwrap();
+    /// assert_eq!(stmt.query_scalar::<i32>(None).unwrap(), Some(1));
+    /// ```
+    pub fn query_scalar<T: Decode>(&mut self, parameters: Option<Parameters>) -> Result<Option<T>> {
+        match self.query_row(parameters)? {
+            Some(row) => row.try_get_nullable::<_, T>(0),
             None => Ok(None),
         }
     }
@@ -60,6 +231,9 @@ impl Statement<'_> {
     /// Returns `Ok(None)` if the query returned no rows.
     /// If the query returns more than one row, the function will return an the first row and ignore the rest.
     ///
+    /// The errors of [Row::try_get] and [Row::try_get_nullable] can be propagated from the mapping function using `?`,
+    /// they are returned as is (i.e. [Error::Column] if a column cannot be decoded).
+    ///
     /// # Example
     ///
     /// ```rust
@@ -73,9 +247,7 @@ impl Statement<'_> {
     ///
     /// // some rows
     /// let user = conn
-    ///     .query_map_row("SELECT 1", None, |row| {
-    ///         Ok(User { id: row.get::<_, _>(0), username: row.get::<_, _>(1) })
-    ///     })
+    ///     .query_map_row("SELECT 1", None, |row| Ok(User { id: row.try_get(0)?, username: row.try_get(1)? }))
     ///     .unwrap()
     ///     .unwrap();
     /// assert_eq!(user.id, 1);
@@ -95,7 +267,8 @@ impl Statement<'_> {
     ///
     /// Returns a vector of the mapped values.
     /// Each rows is mapped to a value using the provided mapping function, if the mapping function returns an error,
-    /// the query is aborted and the error is returned.
+    /// the query is aborted and the error is returned. As with [Statement::query_map_row], the errors of
+    /// [Row::try_get] and [Row::try_get_nullable] can be propagated using `?` instead of using [Row::get] which panics.
     ///
     /// # Example
     /// ```rust
@@ -109,7 +282,7 @@ impl Statement<'_> {
     /// let mut conn = Connection::open("mock://").unwrap();
     ///
     /// let users = conn
-    ///    .query_map_rows("SELECT 2", None, |row| Ok(User { id: row.get::<_, _>(0), username: row.get::<_, _>(1) }))
+    ///    .query_map_rows("SELECT 2", None, |row| Ok(User { id: row.try_get(0)?, username: row.try_get(1)? }))
     ///    .unwrap();
     ///
     /// assert_eq!(users.len(), 2);
@@ -136,7 +309,67 @@ impl Statement<'_> {
         Ok(results)
     }
 
+    /// Query a statement and return the rows as JSON objects using the names of the columns as keys.
+    ///
+    /// This requires the `json` feature. See [squill_core::values::Value::to_json] for the representation of the values
+    /// and [squill_core::arrow::schema_to_json] to describe the columns given by [Statement::schema].
+    ///
+    /// ```rust,ignore
+    /// let users = conn.prepare("SELECT id, username FROM users")?.query_json(None)?;
+    /// assert_eq!(users[0], serde_json::json!({ "id": 1, "username": "user1" }));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn query_json(&mut self, parameters: Option<Parameters>) -> Result<Vec<serde_json::Value>> {
+        self.query_map_rows(parameters, |row| Ok(row.to_json()?))
+    }
+
     pub fn schema(&self) -> SchemaRef {
         self.inner.schema()
     }
+
+    /// Get the number of parameters (placeholders) of the statement.
+    pub fn parameter_count(&self) -> usize {
+        self.inner.parameter_count()
+    }
+
+    /// Get the placeholders of the statement along with their position in the text given to
+    /// [crate::Connection::prepare].
+    ///
+    /// This is a text analysis of the statement that doesn't involve the driver, see
+    /// [squill_core::placeholders::placeholders].
+    ///
+    /// ```rust
+    /// use squill_blocking::Connection;
+    ///
+    /// let mut conn = Connection::open("mock://").unwrap();
+    /// let stmt = conn.prepare("SELECT * FROM users WHERE id = ? AND name <> '?'").unwrap();
+    /// assert_eq!(stmt.placeholders()[0].byte_offset, 31);
+    /// ```
+    pub fn placeholders(&self) -> Vec<Placeholder> {
+        placeholders::placeholders(&self.text)
+    }
+
+    /// Get the types expected for the parameters of the statement.
+    ///
+    /// Returns `None` if the driver doesn't know them, only PostgreSQL infers the types of the parameters when
+    /// preparing a statement.
+    pub fn parameter_types(&self) -> Option<Vec<DataType>> {
+        self.inner.parameter_types()
+    }
+
+    /// Get an estimate of the number of rows returned by the last query of the statement.
+    ///
+    /// Returns `None` if the driver doesn't know it. The estimate may only be available once all the rows of the query
+    /// have been consumed, see [DriverStatement::estimated_row_count] for more information.
+    pub fn estimated_row_count(&self) -> Option<u64> {
+        self.inner.estimated_row_count()
+    }
+
+    /// Reset the statement, clearing the bound parameters and the state of the last query.
+    ///
+    /// This is useful to reuse a statement after partially consuming the rows of a query: the parameters of the next
+    /// execution must be given again.
+    pub fn reset(&mut self) -> Result<()> {
+        self.inner.reset().map_err(Error::from)
+    }
 }
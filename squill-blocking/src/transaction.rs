@@ -0,0 +1,150 @@
+use crate::connection::Connection;
+use squill_core::Result;
+use std::ops::{Deref, DerefMut};
+
+/// A transaction.
+///
+/// A transaction is started by [Connection::begin] and must be ended by calling either [Transaction::commit] or
+/// [Transaction::rollback]. If the transaction is dropped without being committed, it is rolled back.
+///
+/// While the transaction is alive, the connection can be used through the transaction (i.e. `tx.execute(...)`).
+///
+/// ```rust
+/// use squill_blocking::Connection;
+///
+/// let mut conn = Connection::open("mock://").unwrap();
+/// let mut tx = conn.begin().unwrap();
+/// tx.execute("INSERT INTO employee (id) VALUES (1)", None).unwrap();
+/// tx.commit().unwrap();
+/// ```
+pub struct Transaction<'c> {
+    conn: &'c mut Connection,
+    finished: bool,
+}
+
+impl<'c> Transaction<'c> {
+    pub(crate) fn new(conn: &'c mut Connection) -> Result<Self> {
+        conn.execute("BEGIN", None)?;
+        Ok(Self { conn, finished: false })
+    }
+
+    /// Commit the transaction.
+    pub fn commit(mut self) -> Result<()> {
+        self.finished = true;
+        self.conn.execute("COMMIT", None)?;
+        Ok(())
+    }
+
+    /// Rollback the transaction.
+    pub fn rollback(mut self) -> Result<()> {
+        self.finished = true;
+        self.conn.execute("ROLLBACK", None)?;
+        Ok(())
+    }
+
+    /// Create a savepoint in the transaction.
+    ///
+    /// See [Savepoint] for more information.
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        Savepoint::new(self.conn)
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl DerefMut for Transaction<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // There is no way to report an error from `drop`, the rollback is a best effort.
+            let _ = self.conn.execute("ROLLBACK", None);
+        }
+    }
+}
+
+/// A savepoint in a transaction.
+///
+/// A savepoint marks a point in a transaction that can be rolled back to without affecting the changes made before it.
+/// Savepoints can be nested by calling [Savepoint::savepoint] and are given unique names (`sp_1`, `sp_2`, ...).
+///
+/// A savepoint must be ended by calling either [Savepoint::release] to keep its changes in the transaction or
+/// [Savepoint::rollback_to] to discard them. If the savepoint is dropped without being released, it is rolled back.
+///
+/// Savepoints are created using `SAVEPOINT` statements which are not supported by all the drivers (e.g. DuckDB).
+pub struct Savepoint<'c> {
+    conn: &'c mut Connection,
+    name: String,
+    finished: bool,
+}
+
+impl<'c> Savepoint<'c> {
+    fn new(conn: &'c mut Connection) -> Result<Self> {
+        let name = format!("sp_{}", conn.next_savepoint_id());
+        conn.execute(format!("SAVEPOINT {}", name), None)?;
+        Ok(Self { conn, name, finished: false })
+    }
+
+    /// Get the name of the savepoint.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Release the savepoint, keeping its changes in the transaction.
+    pub fn release(mut self) -> Result<()> {
+        self.finished = true;
+        self.conn.execute(format!("RELEASE SAVEPOINT {}", self.name), None)?;
+        Ok(())
+    }
+
+    /// Rollback the changes made since the savepoint was created.
+    pub fn rollback_to(mut self) -> Result<()> {
+        self.finished = true;
+        self.rollback_and_release()
+    }
+
+    /// Create a nested savepoint.
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        Savepoint::new(self.conn)
+    }
+
+    fn rollback_and_release(&mut self) -> Result<()> {
+        // Rolling back to a savepoint doesn't remove it from the transaction.
+        self.conn.execute(format!("ROLLBACK TO SAVEPOINT {}", self.name), None)?;
+        self.conn.execute(format!("RELEASE SAVEPOINT {}", self.name), None)?;
+        Ok(())
+    }
+}
+
+impl Deref for Savepoint<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl DerefMut for Savepoint<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // There is no way to report an error from `drop`, the rollback is a best effort.
+            let _ = self.rollback_and_release();
+        }
+    }
+}
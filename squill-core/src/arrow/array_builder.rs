@@ -1,5 +1,6 @@
 use arrow_array::builder::{
-    ArrayBuilder, Date32Builder, IntervalMonthDayNanoBuilder, Time64MicrosecondBuilder, TimestampMicrosecondBuilder,
+    ArrayBuilder, Date32Builder, Decimal128Builder, IntervalMonthDayNanoBuilder, Time64MicrosecondBuilder,
+    TimestampMicrosecondBuilder,
 };
 use arrow_array::builder::{
     BinaryBuilder, BooleanBuilder, Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder,
@@ -7,6 +8,10 @@ use arrow_array::builder::{
 };
 use arrow_array::types::IntervalMonthDayNano;
 
+/// Append a value to an [ArrayBuilder] without knowing its concrete type.
+///
+/// The value is appended to the first builder type accepting it (e.g. an `i32` can be appended to an `Int32Builder`
+/// or a `Date32Builder`). Appending a value to a builder of an incompatible type is a programming error and panics.
 pub trait ArrayBuilderAppender<T> {
     fn append_value(&mut self, value: Option<T>);
 }
@@ -24,7 +29,7 @@ macro_rules! impl_array_builder_appender {
                         return;
                     }
                 )+
-                panic!("Failed to downcast ArrayBuilder to any of the provided types");
+                panic!("Failed to downcast ArrayBuilder to any of the builders accepting {}", stringify!($data_type));
             }
         }
     };
@@ -36,8 +41,62 @@ impl_array_builder_appender!(i16, Int16Builder);
 impl_array_builder_appender!(i32, Int32Builder, Date32Builder);
 impl_array_builder_appender!(u32, UInt32Builder);
 impl_array_builder_appender!(i64, Int64Builder, TimestampMicrosecondBuilder, Time64MicrosecondBuilder);
+impl_array_builder_appender!(i128, Decimal128Builder);
 impl_array_builder_appender!(f32, Float32Builder);
 impl_array_builder_appender!(f64, Float64Builder);
 impl_array_builder_appender!(String, StringBuilder);
 impl_array_builder_appender!(Vec<u8>, BinaryBuilder);
 impl_array_builder_appender!(IntervalMonthDayNano, IntervalMonthDayNanoBuilder);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, Date32Array, Decimal128Array, Int32Array, IntervalMonthDayNanoArray};
+
+    #[test]
+    fn test_append_value() {
+        let mut builder: Box<dyn ArrayBuilder> = Box::new(Int32Builder::new());
+        builder.append_value(Some(1_i32));
+        builder.append_value(None::<i32>);
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array.value(0), 1);
+        assert!(array.is_null(1));
+
+        // Date
+        let mut builder: Box<dyn ArrayBuilder> = Box::new(Date32Builder::new());
+        builder.append_value(Some(19_000_i32));
+        builder.append_value(None::<i32>);
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(array.value(0), 19_000);
+        assert!(array.is_null(1));
+
+        // Decimal
+        let mut builder: Box<dyn ArrayBuilder> =
+            Box::new(Decimal128Builder::new().with_precision_and_scale(10, 2).unwrap());
+        builder.append_value(Some(12345_i128));
+        builder.append_value(None::<i128>);
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!(array.value_as_string(0), "123.45");
+        assert!(array.is_null(1));
+
+        // Interval
+        let interval = IntervalMonthDayNano::new(1, 2, 3_000_000_000);
+        let mut builder: Box<dyn ArrayBuilder> = Box::new(IntervalMonthDayNanoBuilder::new());
+        builder.append_value(Some(interval));
+        builder.append_value(None::<IntervalMonthDayNano>);
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<IntervalMonthDayNanoArray>().unwrap();
+        assert_eq!(array.value(0), interval);
+        assert!(array.is_null(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to downcast ArrayBuilder")]
+    fn test_append_value_type_mismatch() {
+        let mut builder: Box<dyn ArrayBuilder> = Box::new(StringBuilder::new());
+        builder.append_value(Some(1_i128));
+    }
+}
@@ -1 +1,8 @@
 pub mod array_builder;
+pub mod stats;
+pub mod type_name;
+pub mod unify;
+
+pub use stats::{column_null_count, BatchStats};
+pub use type_name::{field_type_name, friendly_type_name, schema_to_json, DATASOURCE_TYPE_METADATA};
+pub use unify::{cast_to_schema, unify_batches, unify_schemas, UnifiedBatches};
@@ -0,0 +1,53 @@
+use arrow_array::{Array, RecordBatch};
+
+/// Basic statistics of a record batch, useful for a quick profiling of the data returned by a query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchStats {
+    /// The number of rows of the batch.
+    pub rows: usize,
+
+    /// The number of nulls of each column, in the order of the columns of the batch.
+    pub null_counts: Vec<usize>,
+}
+
+impl From<&RecordBatch> for BatchStats {
+    fn from(batch: &RecordBatch) -> Self {
+        BatchStats {
+            rows: batch.num_rows(),
+            null_counts: batch.columns().iter().map(|column| column.null_count()).collect(),
+        }
+    }
+}
+
+/// Get the number of nulls of a column of a record batch.
+///
+/// The count is maintained by Arrow so this is a constant time operation.
+///
+/// # Panics
+///
+/// Panics if the index is out of bounds.
+pub fn column_null_count(batch: &RecordBatch, index: usize) -> usize {
+    batch.column(index).null_count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_batch_stats() {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2, 3])) as _),
+            ("name", Arc::new(StringArray::from(vec![Some("Alice"), None, None])) as _),
+        ])
+        .unwrap();
+        assert_eq!(column_null_count(&batch, 0), 0);
+        assert_eq!(column_null_count(&batch, 1), 2);
+        assert_eq!(BatchStats::from(&batch), BatchStats { rows: 3, null_counts: vec![0, 2] });
+
+        let empty = batch.slice(0, 0);
+        assert_eq!(BatchStats::from(&empty), BatchStats { rows: 0, null_counts: vec![0, 0] });
+    }
+}
@@ -0,0 +1,154 @@
+use arrow_schema::{DataType, Field, Schema};
+
+/// The key of the metadata of a field giving the name of the type of the column in the data source (i.e. `int4` for
+/// PostgreSQL or `long` for MySQL).
+pub const DATASOURCE_TYPE_METADATA: &str = "datasource_type";
+
+/// Get a human-friendly name of an Arrow data type (i.e. `integer` or `varchar`) to be displayed to the users.
+///
+/// The names are the ones commonly used in SQL, the nested types only give the name of their kind (i.e. `list`) and
+/// the types no driver produces are all named `unknown`.
+///
+/// ```rust
+/// use arrow_schema::DataType;
+/// use squill_core::arrow::friendly_type_name;
+///
+/// assert_eq!(friendly_type_name(&DataType::Int32), "integer");
+/// assert_eq!(friendly_type_name(&DataType::Utf8), "varchar");
+/// ```
+pub fn friendly_type_name(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Null => "null",
+        DataType::Boolean => "boolean",
+        DataType::Int8 => "tinyint",
+        DataType::Int16 => "smallint",
+        DataType::Int32 => "integer",
+        DataType::Int64 => "bigint",
+        DataType::UInt8 => "utinyint",
+        DataType::UInt16 => "usmallint",
+        DataType::UInt32 => "uinteger",
+        DataType::UInt64 => "ubigint",
+        DataType::Float16 => "half",
+        DataType::Float32 => "real",
+        DataType::Float64 => "double",
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => "decimal",
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "varchar",
+        DataType::FixedSizeBinary(16) => "uuid",
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView | DataType::FixedSizeBinary(_) => "blob",
+        DataType::Date32 | DataType::Date64 => "date",
+        DataType::Time32(_) | DataType::Time64(_) => "time",
+        DataType::Timestamp(_, None) => "timestamp",
+        DataType::Timestamp(_, Some(_)) => "timestamptz",
+        DataType::Duration(_) | DataType::Interval(_) => "interval",
+        DataType::List(_) | DataType::LargeList(_) | DataType::ListView(_) | DataType::FixedSizeList(_, _) => "list",
+        DataType::Struct(_) => "struct",
+        DataType::Map(_, _) => "map",
+        DataType::Union(_, _) => "union",
+        DataType::Dictionary(_, value_type) => friendly_type_name(value_type),
+        _ => "unknown",
+    }
+}
+
+/// Get the name of the type of a column to be displayed to the users.
+///
+/// The name of the type in the data source is used when the driver provides it (see [DATASOURCE_TYPE_METADATA]),
+/// otherwise the name is derived from the Arrow data type of the field (see [friendly_type_name]).
+pub fn field_type_name(field: &Field) -> &str {
+    match field.metadata().get(DATASOURCE_TYPE_METADATA) {
+        Some(datasource_type) => datasource_type,
+        None => friendly_type_name(field.data_type()),
+    }
+}
+
+/// Describe the columns of a schema as a JSON array, i.e. to send it along with the rows of a query exported as JSON.
+///
+/// Each column is described by an object giving its `name`, the name of its `type` (see [field_type_name]) and
+/// whether it is `nullable`.
+///
+/// ```rust
+/// use arrow_schema::{DataType, Field, Schema};
+/// use squill_core::arrow::schema_to_json;
+///
+/// let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+/// assert_eq!(schema_to_json(&schema), serde_json::json!([{ "name": "id", "type": "bigint", "nullable": false }]));
+/// ```
+pub fn schema_to_json(schema: &Schema) -> serde_json::Value {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            serde_json::json!({
+                "name": field.name(),
+                "type": field_type_name(field),
+                "nullable": field.is_nullable(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_schema::TimeUnit;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_friendly_type_name() {
+        assert_eq!(friendly_type_name(&DataType::Null), "null");
+        assert_eq!(friendly_type_name(&DataType::Boolean), "boolean");
+        assert_eq!(friendly_type_name(&DataType::Int16), "smallint");
+        assert_eq!(friendly_type_name(&DataType::Int32), "integer");
+        assert_eq!(friendly_type_name(&DataType::Int64), "bigint");
+        assert_eq!(friendly_type_name(&DataType::UInt64), "ubigint");
+        assert_eq!(friendly_type_name(&DataType::Float32), "real");
+        assert_eq!(friendly_type_name(&DataType::Float64), "double");
+        assert_eq!(friendly_type_name(&DataType::Decimal128(10, 2)), "decimal");
+        assert_eq!(friendly_type_name(&DataType::Utf8), "varchar");
+        assert_eq!(friendly_type_name(&DataType::LargeUtf8), "varchar");
+        assert_eq!(friendly_type_name(&DataType::Binary), "blob");
+        assert_eq!(friendly_type_name(&DataType::FixedSizeBinary(16)), "uuid");
+        assert_eq!(friendly_type_name(&DataType::FixedSizeBinary(32)), "blob");
+        assert_eq!(friendly_type_name(&DataType::Date32), "date");
+        assert_eq!(friendly_type_name(&DataType::Time64(TimeUnit::Microsecond)), "time");
+        assert_eq!(friendly_type_name(&DataType::Timestamp(TimeUnit::Microsecond, None)), "timestamp");
+        assert_eq!(friendly_type_name(&DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))), "timestamptz");
+        assert_eq!(friendly_type_name(&DataType::List(Arc::new(Field::new_list_field(DataType::Int32, true)))), "list");
+        assert_eq!(
+            friendly_type_name(&DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))),
+            "varchar"
+        );
+        assert_eq!(
+            friendly_type_name(&DataType::RunEndEncoded(
+                Arc::new(Field::new("run_ends", DataType::Int32, false)),
+                Arc::new(Field::new("values", DataType::Utf8, true))
+            )),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_field_type_name() {
+        let field = Field::new("id", DataType::Int32, true);
+        assert_eq!(field_type_name(&field), "integer");
+        let field = field.with_metadata(HashMap::from([(DATASOURCE_TYPE_METADATA.to_string(), "int4".to_string())]));
+        assert_eq!(field_type_name(&field), "int4");
+    }
+
+    #[test]
+    fn test_schema_to_json() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false)
+                .with_metadata(HashMap::from([(DATASOURCE_TYPE_METADATA.to_string(), "int4".to_string())])),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        assert_eq!(
+            schema_to_json(&schema),
+            serde_json::json!([
+                { "name": "id", "type": "int4", "nullable": false },
+                { "name": "name", "type": "varchar", "nullable": true },
+            ])
+        );
+        assert_eq!(schema_to_json(&Schema::empty()), serde_json::json!([]));
+    }
+}
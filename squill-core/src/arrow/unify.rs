@@ -0,0 +1,385 @@
+use crate::{Error, Result};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Decimal128Type;
+use arrow_array::{new_null_array, Array, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Get the type that two types of the same column are promoted to, `None` if they cannot be unified.
+///
+/// A column of type `Null` takes the other type, the integers are promoted to `Int64` (except `UInt64` that doesn't
+/// fit) and a mix of integers and floats is promoted to `Float64` (i.e. `CASE WHEN ... THEN 1 ELSE 1.5 END`). The
+/// decimals are not promoted since a `Float64` would lose their precision.
+fn promote(data_type: &DataType, other: &DataType) -> Option<DataType> {
+    let is_integer_or_float = |data_type: &DataType| data_type.is_integer() || data_type.is_floating();
+    match (data_type, other) {
+        (DataType::Null, data_type) | (data_type, DataType::Null) => Some(data_type.clone()),
+        (data_type, other) if data_type == other => Some(data_type.clone()),
+        (DataType::UInt64, _) | (_, DataType::UInt64) if data_type.is_integer() && other.is_integer() => None,
+        (data_type, other) if data_type.is_integer() && other.is_integer() => Some(DataType::Int64),
+        (data_type, other) if is_integer_or_float(data_type) && is_integer_or_float(other) => Some(DataType::Float64),
+        _ => None,
+    }
+}
+
+/// Check that the integers of a column are represented exactly once cast to `Float64` (i.e. up to 2^53).
+fn is_exact_in_float64(column: &dyn Array) -> Result<bool> {
+    const MAX_EXACT_INTEGER: u128 = 1 << f64::MANTISSA_DIGITS;
+    // `Decimal128` holds the values of every integer type, including `UInt64`.
+    let values = arrow_cast::cast(column, &DataType::Decimal128(38, 0))?;
+    Ok(values.as_primitive::<Decimal128Type>().iter().flatten().all(|value| value.unsigned_abs() <= MAX_EXACT_INTEGER))
+}
+
+/// Get the schema unifying the schemas of record batches returned by the same query.
+///
+/// Some drivers infer the type of a column from its values (i.e. SQLite for the expressions), so a column only having
+/// null values in the first record batches is of type `Null` until a value is found. The columns are matched by
+/// position and a column of type `Null` takes the type it has in the other schemas. The numeric types are promoted:
+/// the integers to `Int64` and a mix of integers and floats to `Float64` (the decimals are not promoted).
+///
+/// An error is returned if the schemas don't have the same number of columns or if a column has two different types
+/// that cannot be promoted (i.e. `Utf8` and `Int64`).
+///
+/// ```rust
+/// use arrow_schema::{DataType, Field, Schema};
+/// use squill_core::arrow::unify_schemas;
+///
+/// let first = Schema::new(vec![Field::new("id", DataType::Null, true)]);
+/// let second = Schema::new(vec![Field::new("id", DataType::Int64, true)]);
+/// let schema = unify_schemas([&first, &second]).unwrap();
+/// assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+/// ```
+pub fn unify_schemas<'s, I: IntoIterator<Item = &'s Schema>>(schemas: I) -> Result<SchemaRef> {
+    let mut schemas = schemas.into_iter();
+    let Some(first) = schemas.next() else {
+        return Ok(Arc::new(Schema::empty()));
+    };
+    let mut fields: Vec<Field> = first.fields().iter().map(|field| field.as_ref().clone()).collect();
+    for schema in schemas {
+        if schema.fields().len() != fields.len() {
+            return Err(ArrowError::SchemaError(format!(
+                "Cannot unify schemas with {} and {} columns",
+                fields.len(),
+                schema.fields().len()
+            ))
+            .into());
+        }
+        for (field, other) in fields.iter_mut().zip(schema.fields().iter()) {
+            let data_type = promote(field.data_type(), other.data_type()).ok_or_else(|| Error::InvalidType {
+                expected: field.data_type().to_string(),
+                actual: other.data_type().to_string(),
+            })?;
+            // A column of type `Null` in one of the schemas has null values.
+            let nullable = field.is_nullable()
+                || other.is_nullable()
+                || field.data_type() == &DataType::Null
+                || other.data_type() == &DataType::Null;
+            *field = field.clone().with_data_type(data_type).with_nullable(nullable);
+        }
+    }
+    Ok(Arc::new(Schema::new_with_metadata(fields, first.metadata().clone())))
+}
+
+/// Cast a record batch to a schema unifying its own (see [unify_schemas]).
+///
+/// The columns of type `Null` are replaced by arrays of null values of the type given by the schema and the numeric
+/// columns are promoted to the type given by the schema. An [Error::InvalidType] is returned if a column cannot be
+/// promoted without losing precision (i.e. a `Float64` column to `Int64`, or an `Int64` column to `Float64` when one of
+/// its values is beyond 2^53).
+pub fn cast_to_schema(batch: &RecordBatch, schema: SchemaRef) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields().iter())
+        .map(|(column, field)| match column.data_type() {
+            data_type if data_type == field.data_type() => Ok(column.clone()),
+            DataType::Null => Ok(new_null_array(field.data_type(), column.len())),
+            data_type if promote(data_type, field.data_type()).as_ref() == Some(field.data_type()) => {
+                if data_type.is_integer() && field.data_type().is_floating() && !is_exact_in_float64(column)? {
+                    return Err(Error::InvalidType {
+                        expected: field.data_type().to_string(),
+                        actual: data_type.to_string(),
+                    });
+                }
+                Ok(arrow_cast::cast(column, field.data_type())?)
+            }
+            data_type => {
+                Err(Error::InvalidType { expected: field.data_type().to_string(), actual: data_type.to_string() })
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// Cast record batches returned by the same query to the schema unifying theirs (see [unify_schemas]).
+///
+/// The record batches returned have the same schema and can be concatenated.
+pub fn unify_batches(batches: &[RecordBatch]) -> Result<Vec<RecordBatch>> {
+    let schema = unify_schemas(batches.iter().map(|batch| batch.schema_ref().as_ref()))?;
+    batches.iter().map(|batch| cast_to_schema(batch, schema.clone())).collect()
+}
+
+/// An iterator giving the same schema to the record batches returned by a query while they are streamed.
+///
+/// Unlike [unify_batches], the record batches don't need to be collected first. The type of a column is the one
+/// declared by the schema given to [UnifiedBatches::new] (i.e. the one of the prepared statement), or the type of its
+/// first non-null value if its declared type is `Null` (i.e. an expression with SQLite). The record batches are
+/// buffered until the type of every column is known, so a column only having null values makes the whole result be
+/// buffered before being returned with the type `Null`.
+///
+/// The next record batches are cast to that schema (see [cast_to_schema]), so a column first found as `Int64` and then
+/// as `Float64` fails with an [Error::InvalidType] since the previous record batches were already returned.
+///
+/// An error returned by the query comes after the record batches buffered before it (a column whose type is still
+/// unknown keeping the type `Null`) and ends the iteration, so the record batches are never reordered.
+///
+/// ```rust,ignore
+/// let (schema, batches) = stmt.query_with_schema(None)?;
+/// for batch in UnifiedBatches::new(schema, batches) {
+///     let batch = batch?;
+/// }
+/// ```
+pub struct UnifiedBatches<I> {
+    inner: I,
+    schema: SchemaRef,
+    pending: VecDeque<RecordBatch>,
+    error: Option<Error>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Result<RecordBatch>>> UnifiedBatches<I> {
+    pub fn new(schema: SchemaRef, batches: I) -> Self {
+        Self { inner: batches, schema, pending: VecDeque::new(), error: None, done: false }
+    }
+
+    /// Get the schema of the record batches as known so far.
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Give the columns of type `Null` the type they have in a record batch.
+    fn infer_types(&mut self, schema: &Schema) -> Result<()> {
+        if schema.fields().len() != self.schema.fields().len() {
+            return Err(ArrowError::SchemaError(format!(
+                "Cannot unify schemas with {} and {} columns",
+                self.schema.fields().len(),
+                schema.fields().len()
+            ))
+            .into());
+        }
+        if !self.has_null_columns() {
+            return Ok(());
+        }
+        let fields: Vec<Field> = self
+            .schema
+            .fields()
+            .iter()
+            .zip(schema.fields().iter())
+            .map(|(field, other)| match (field.data_type(), other.data_type()) {
+                (DataType::Null, DataType::Null) => field.as_ref().clone(),
+                (DataType::Null, data_type) => {
+                    field.as_ref().clone().with_data_type(data_type.clone()).with_nullable(true)
+                }
+                _ => field.as_ref().clone(),
+            })
+            .collect();
+        self.schema = Arc::new(Schema::new_with_metadata(fields, self.schema.metadata().clone()));
+        Ok(())
+    }
+
+    fn has_null_columns(&self) -> bool {
+        self.schema.fields().iter().any(|field| field.data_type() == &DataType::Null)
+    }
+}
+
+impl<I: Iterator<Item = Result<RecordBatch>>> Iterator for UnifiedBatches<I> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Result<RecordBatch>> {
+        loop {
+            if self.done || !self.has_null_columns() {
+                if let Some(batch) = self.pending.pop_front() {
+                    return Some(cast_to_schema(&batch, self.schema.clone()));
+                }
+                if let Some(e) = self.error.take() {
+                    return Some(Err(e));
+                }
+                if self.done {
+                    return None;
+                }
+            }
+            // An error is returned after the record batches received before it, the iteration ending with it.
+            match self.inner.next() {
+                None => self.done = true,
+                Some(Err(e)) => (self.error, self.done) = (Some(e), true),
+                Some(Ok(batch)) => match self.infer_types(batch.schema_ref()) {
+                    Ok(()) => self.pending.push_back(batch),
+                    Err(e) => (self.error, self.done) = (Some(e), true),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Float64Array, Int32Array, Int64Array, NullArray, StringArray, UInt64Array};
+
+    fn batch(columns: Vec<(&str, Arc<dyn Array>)>) -> RecordBatch {
+        RecordBatch::try_from_iter(columns).unwrap()
+    }
+
+    #[test]
+    fn test_unify_schemas() {
+        let first =
+            Schema::new(vec![Field::new("id", DataType::Null, true), Field::new("name", DataType::Utf8, false)]);
+        let second =
+            Schema::new(vec![Field::new("id", DataType::Int64, false), Field::new("name", DataType::Null, true)]);
+        let schema = unify_schemas([&first, &second]).unwrap();
+        assert_eq!(
+            schema.as_ref(),
+            &Schema::new(vec![Field::new("id", DataType::Int64, true), Field::new("name", DataType::Utf8, true)])
+        );
+        assert_eq!(unify_schemas([&first]).unwrap().as_ref(), &first);
+        assert_eq!(unify_schemas(std::iter::empty()).unwrap().fields().len(), 0);
+
+        let other = Schema::new(vec![Field::new("id", DataType::Utf8, true), Field::new("name", DataType::Utf8, true)]);
+        assert!(matches!(unify_schemas([&second, &other]), Err(Error::InvalidType { .. })));
+        let other = Schema::new(vec![Field::new("id", DataType::Int64, true)]);
+        assert!(matches!(unify_schemas([&first, &other]), Err(Error::ArrowError { .. })));
+    }
+
+    #[test]
+    fn test_unify_numeric_types() {
+        let schema = |data_type: DataType| Schema::new(vec![Field::new("value", data_type, false)]);
+        let unified = |a: DataType, b: DataType| unify_schemas([&schema(a), &schema(b)]).map(|s| s.field(0).clone());
+        assert_eq!(unified(DataType::Int32, DataType::Int64).unwrap(), Field::new("value", DataType::Int64, false));
+        assert_eq!(unified(DataType::UInt32, DataType::Int8).unwrap().data_type(), &DataType::Int64);
+        assert_eq!(unified(DataType::Int64, DataType::Float64).unwrap().data_type(), &DataType::Float64);
+        assert_eq!(unified(DataType::Float32, DataType::Float64).unwrap().data_type(), &DataType::Float64);
+        assert!(matches!(unified(DataType::UInt64, DataType::Int64), Err(Error::InvalidType { .. })));
+        assert!(matches!(unified(DataType::Int64, DataType::Boolean), Err(Error::InvalidType { .. })));
+        assert!(matches!(unified(DataType::Decimal128(10, 2), DataType::Int64), Err(Error::InvalidType { .. })));
+        assert!(matches!(unified(DataType::Float64, DataType::Decimal128(10, 2)), Err(Error::InvalidType { .. })));
+
+        // i.e. `CASE WHEN ... THEN 1 ELSE 1.5 END` giving integers in the first batch and reals in the next one.
+        let batches = unify_batches(&[
+            batch(vec![("value", Arc::new(Int64Array::from(vec![1, 2])))]),
+            batch(vec![("value", Arc::new(Float64Array::from(vec![1.5])))]),
+        ])
+        .unwrap();
+        assert_eq!(batches[0].column(0).as_ref(), &Float64Array::from(vec![1.0, 2.0]) as &dyn Array);
+        assert_eq!(batches[1].column(0).as_ref(), &Float64Array::from(vec![1.5]) as &dyn Array);
+
+        // A column cannot be cast to a type losing precision.
+        let float_batch = batch(vec![("value", Arc::new(Float64Array::from(vec![1.5])))]);
+        assert!(matches!(
+            cast_to_schema(&float_batch, Arc::new(schema(DataType::Int64))),
+            Err(Error::InvalidType { .. })
+        ));
+        let unsigned_batch = batch(vec![("value", Arc::new(UInt64Array::from(vec![u64::MAX])))]);
+        assert!(matches!(
+            cast_to_schema(&unsigned_batch, Arc::new(schema(DataType::Int64))),
+            Err(Error::InvalidType { .. })
+        ));
+        assert!(matches!(
+            cast_to_schema(&unsigned_batch, Arc::new(schema(DataType::Float64))),
+            Err(Error::InvalidType { .. })
+        ));
+
+        // An integer beyond 2^53 cannot be cast to a float without being rounded.
+        let exact = batch(vec![("value", Arc::new(Int64Array::from(vec![Some(-(1 << 53)), None, Some(1 << 53)])))]);
+        let cast = cast_to_schema(&exact, Arc::new(Schema::new(vec![Field::new("value", DataType::Float64, true)])));
+        assert_eq!(
+            cast.unwrap().column(0).as_ref(),
+            &Float64Array::from(vec![Some(-9007199254740992.0), None, Some(9007199254740992.0)]) as &dyn Array
+        );
+        let rounded = batch(vec![("value", Arc::new(Int64Array::from(vec![(1 << 53) + 1])))]);
+        assert!(matches!(
+            unify_batches(&[rounded, batch(vec![("value", Arc::new(Float64Array::from(vec![1.5])))])]),
+            Err(Error::InvalidType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unified_batches() {
+        let declared = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("score", DataType::Null, true),
+        ]));
+        let batches = vec![
+            batch(vec![("id", Arc::new(Int32Array::from(vec![1]))), ("score", Arc::new(NullArray::new(1)))]),
+            batch(vec![("id", Arc::new(Int64Array::from(vec![2]))), ("score", Arc::new(NullArray::new(1)))]),
+            batch(vec![("id", Arc::new(Int64Array::from(vec![3]))), ("score", Arc::new(Int64Array::from(vec![7])))]),
+            batch(vec![("id", Arc::new(Int64Array::from(vec![4]))), ("score", Arc::new(NullArray::new(1)))]),
+        ];
+        let mut unified = UnifiedBatches::new(declared.clone(), batches.clone().into_iter().map(Ok));
+        let expected =
+            Schema::new(vec![Field::new("id", DataType::Int64, false), Field::new("score", DataType::Int64, true)]);
+
+        // The first batches are held until the type of `score` is known.
+        let first = unified.next().unwrap().unwrap();
+        assert_eq!(unified.schema().as_ref(), &expected);
+        assert_eq!(first.schema().as_ref(), &expected);
+        assert_eq!(first.column(0).as_ref(), &Int64Array::from(vec![1]) as &dyn Array);
+        assert_eq!(first.column(1).as_ref(), &Int64Array::from(vec![None]) as &dyn Array);
+        let rest = unified.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(rest.len(), 3);
+        assert!(rest.iter().all(|batch| batch.schema().as_ref() == &expected));
+        assert_eq!(rest[1].column(1).as_ref(), &Int64Array::from(vec![7]) as &dyn Array);
+
+        // A column only having null values keeps the type `Null`.
+        let unified = UnifiedBatches::new(declared.clone(), batches[..2].iter().cloned().map(Ok));
+        let all_null = unified.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(all_null.len(), 2);
+        assert_eq!(all_null[1].schema().field(1).data_type(), &DataType::Null);
+
+        // Once a type is known, a value that cannot be cast to it is an error.
+        let mut unified = UnifiedBatches::new(
+            declared.clone(),
+            vec![
+                batch(vec![
+                    ("id", Arc::new(Int64Array::from(vec![1]))),
+                    ("score", Arc::new(Int64Array::from(vec![1]))),
+                ]),
+                batch(vec![
+                    ("id", Arc::new(Int64Array::from(vec![2]))),
+                    ("score", Arc::new(Float64Array::from(vec![1.5]))),
+                ]),
+            ]
+            .into_iter()
+            .map(Ok),
+        );
+        assert!(unified.next().unwrap().is_ok());
+        assert!(matches!(unified.next(), Some(Err(Error::InvalidType { .. }))));
+        assert!(unified.next().is_none());
+
+        // An error of the query is returned after the record batches held before it, and ends the iteration.
+        let mut unified = UnifiedBatches::new(
+            declared,
+            vec![Ok(batches[0].clone()), Err(Error::NotFound), Ok(batches[2].clone())].into_iter(),
+        );
+        let held = unified.next().unwrap().unwrap();
+        assert_eq!(held.column(0).as_ref(), &Int64Array::from(vec![1]) as &dyn Array);
+        assert_eq!(held.schema().field(1).data_type(), &DataType::Null);
+        assert!(matches!(unified.next(), Some(Err(Error::NotFound))));
+        assert!(unified.next().is_none());
+    }
+
+    #[test]
+    fn test_unify_batches() {
+        let batches = unify_batches(&[
+            batch(vec![("id", Arc::new(NullArray::new(2))), ("name", Arc::new(StringArray::from(vec!["a", "b"])))]),
+            batch(vec![("id", Arc::new(Int64Array::from(vec![3]))), ("name", Arc::new(NullArray::new(1)))]),
+        ])
+        .unwrap();
+        assert_eq!(batches[0].schema(), batches[1].schema());
+        assert_eq!(batches[0].column(0).as_ref(), &Int64Array::from(vec![None, None]) as &dyn Array);
+        assert_eq!(batches[0].column(1).as_ref(), &StringArray::from(vec!["a", "b"]) as &dyn Array);
+        assert_eq!(batches[1].column(0).as_ref(), &Int64Array::from(vec![3]) as &dyn Array);
+        assert_eq!(batches[1].column(1).as_ref(), &StringArray::from(vec![None::<&str>]) as &dyn Array);
+        assert!(unify_batches(&[]).unwrap().is_empty());
+    }
+}
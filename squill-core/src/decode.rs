@@ -1,11 +1,51 @@
+use crate::values::{self, Value};
 use crate::{Error, Result};
 use arrow_array::array::Array;
-use arrow_schema::{DataType, TimeUnit};
+use arrow_schema::{DataType, IntervalUnit, TimeUnit};
 use chrono::{DateTime, Utc};
 
 /// A trait to decode values from an Arrow array.
+///
+/// This is the extension point to decode a column into a type of your own (e.g. an Arrow extension type), once
+/// implemented the type can be used with [Row::get](crate::row::Row::get) and [Row::try_get](crate::row::Row::try_get)
+/// like any other type. To decode a single column without implementing the trait, see
+/// [Row::get_with](crate::row::Row::get_with).
+///
+/// ```rust
+/// use arrow_array::{Array, StringArray};
+/// use squill_core::decode::Decode;
+/// use squill_core::{Error, Result};
+///
+/// struct Email(String);
+///
+/// impl Decode for Email {
+///     fn decode(array: &dyn Array, index: usize) -> Self {
+///         Self::try_decode(array, index).unwrap()
+///     }
+///
+///     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+///         let value = String::try_decode(array, index)?;
+///         match value.contains('@') {
+///             true => Ok(Email(value)),
+///             false => Err(Error::InvalidType { expected: "Email".to_string(), actual: value }),
+///         }
+///     }
+/// }
+///
+/// let array = StringArray::from(vec!["alice@example.com", "bob"]);
+/// assert_eq!(Email::decode(&array, 0).0, "alice@example.com");
+/// assert!(Email::try_decode(&array, 1).is_err());
+/// ```
 pub trait Decode: Sized {
+    /// Decode the value at the given index of the array.
+    ///
+    /// # Panics
+    /// Panics if the value cannot be decoded (i.e. the type of the array is not the expected one).
     fn decode(array: &dyn Array, index: usize) -> Self;
+
+    /// Decode the value at the given index of the array.
+    ///
+    /// Returns an error if the index is out of bounds or the value cannot be decoded.
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self>;
 }
 
@@ -21,6 +61,22 @@ pub fn is_null(array: &dyn Array, index: usize) -> bool {
     }
 }
 
+/// Check that the value at the given index can be decoded into a type that cannot represent a null.
+///
+/// The `try_decode` implementations of [Decode] call this first so decoding a null value fails with an explicit error
+/// instead of decoding the placeholder stored by Arrow in the null slots (i.e. `0`, `false` or an empty string), which
+/// is still what `decode` returns. The null values are expected to be checked using [is_null] beforehand, as done by
+/// [Row::try_get_nullable](crate::row::Row::try_get_nullable).
+pub(crate) fn check_not_null(array: &dyn Array, index: usize) -> Result<()> {
+    if index >= array.len() {
+        return Err(Error::OutOfBounds { index });
+    }
+    if is_null(array, index) {
+        return Err(Error::InvalidType { expected: "non-null value".to_string(), actual: "null".to_string() });
+    }
+    Ok(())
+}
+
 macro_rules! impl_decode {
     ($type:ty, $array_type:ident) => {
         impl Decode for $type {
@@ -28,9 +84,7 @@ macro_rules! impl_decode {
                 array.as_any().downcast_ref::<arrow_array::$array_type>().unwrap().value(index).into()
             }
             fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
-                if index >= array.len() {
-                    return Err(Error::OutOfBounds { index });
-                }
+                check_not_null(array, index)?;
                 match array.as_any().downcast_ref::<arrow_array::$array_type>() {
                     Some(array) => Ok(array.value(index).into()),
                     None => Err(Error::InvalidType {
@@ -70,9 +124,7 @@ impl Decode for bool {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
-        if index >= array.len() {
-            return Err(Error::OutOfBounds { index });
-        }
+        check_not_null(array, index)?;
         match array.data_type() {
             DataType::Boolean => Ok(array.as_any().downcast_ref::<arrow_array::BooleanArray>().unwrap().value(index)),
             DataType::Int64 => Ok(array.as_any().downcast_ref::<arrow_array::Int64Array>().unwrap().value(index) != 0),
@@ -81,6 +133,42 @@ impl Decode for bool {
     }
 }
 
+/// Decoding a fixed-size array of bytes from a {{arrow_array::Array}}
+///
+/// This is intended for fixed-width binary values such as hashes or keys. The bytes are decoded from a
+/// {{arrow_array::FixedSizeBinaryArray}} or a {{arrow_array::BinaryArray}}, the length of the value must be `N`.
+impl<const N: usize> Decode for [u8; N] {
+    fn decode(array: &dyn Array, index: usize) -> Self {
+        match Self::try_decode(array, index) {
+            Ok(bytes) => bytes,
+            Err(e) => panic!("Unable to decode [u8; {}] (reason: {:?})", N, e),
+        }
+    }
+
+    fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
+        let bytes = match array.data_type() {
+            DataType::FixedSizeBinary(_) => {
+                array.as_any().downcast_ref::<arrow_array::FixedSizeBinaryArray>().unwrap().value(index)
+            }
+            DataType::Binary => array.as_any().downcast_ref::<arrow_array::BinaryArray>().unwrap().value(index),
+            DataType::LargeBinary => {
+                array.as_any().downcast_ref::<arrow_array::LargeBinaryArray>().unwrap().value(index)
+            }
+            _ => {
+                return Err(Error::InvalidType {
+                    expected: format!("FixedSizeBinary({}) or Binary", N),
+                    actual: array.data_type().to_string(),
+                })
+            }
+        };
+        bytes.try_into().map_err(|_| Error::InvalidType {
+            expected: format!("{} bytes", N),
+            actual: format!("{} bytes", bytes.len()),
+        })
+    }
+}
+
 /// Decoding a UUID from a {{arrow_array::Array}}
 impl Decode for uuid::Uuid {
     fn decode(array: &dyn Array, index: usize) -> Self {
@@ -91,9 +179,7 @@ impl Decode for uuid::Uuid {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
-        if index >= array.len() {
-            return Err(Error::OutOfBounds { index });
-        }
+        check_not_null(array, index)?;
         match array.data_type() {
             DataType::Utf8 => {
                 let str = array.as_any().downcast_ref::<arrow_array::StringArray>().unwrap().value(index);
@@ -112,6 +198,9 @@ impl Decode for uuid::Uuid {
 }
 
 /// Decoding a Decimal from {{arrow_array::Array}}
+///
+/// The decimal is usually decoded from a {{arrow_array::Decimal128Array}} but it can also be decoded from an integer
+/// array (i.e. a PostgreSQL `MONEY`) or from its textual representation (i.e. a decimal stored as text by SQLite).
 impl Decode for rust_decimal::Decimal {
     fn decode(array: &dyn Array, index: usize) -> Self {
         match Self::try_decode(array, index) {
@@ -121,13 +210,27 @@ impl Decode for rust_decimal::Decimal {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
-        if index >= array.len() {
-            return Err(Error::OutOfBounds { index });
-        }
-        match array.as_any().downcast_ref::<arrow_array::Decimal128Array>() {
-            Some(array) => Ok(rust_decimal::Decimal::from_i128_with_scale(array.value(index), array.scale() as u32)),
-            None => Err(Error::InvalidType {
-                expected: "Decimal128Array".to_string(), // FIXME:
+        check_not_null(array, index)?;
+        match array.data_type() {
+            DataType::Decimal128(_, _) => {
+                let array = array.as_any().downcast_ref::<arrow_array::Decimal128Array>().unwrap();
+                Ok(rust_decimal::Decimal::from_i128_with_scale(array.value(index), array.scale() as u32))
+            }
+            DataType::Int64 => {
+                Ok(array.as_any().downcast_ref::<arrow_array::Int64Array>().unwrap().value(index).into())
+            }
+            DataType::Int32 => {
+                Ok(array.as_any().downcast_ref::<arrow_array::Int32Array>().unwrap().value(index).into())
+            }
+            DataType::Utf8 => {
+                let str = array.as_any().downcast_ref::<arrow_array::StringArray>().unwrap().value(index);
+                <rust_decimal::Decimal as std::str::FromStr>::from_str(str).map_err(|e| Error::InvalidType {
+                    expected: "Decimal".to_string(),
+                    actual: format!("{:?} ({})", str, e),
+                })
+            }
+            _ => Err(Error::InvalidType {
+                expected: "Decimal128, Int64, Int32 or Utf8".to_string(),
                 actual: array.data_type().to_string(),
             }),
         }
@@ -144,6 +247,7 @@ impl Decode for chrono::DateTime<chrono::Utc> {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
         match array.data_type() {
             DataType::Timestamp(TimeUnit::Second, _) => {
                 let secs = array.as_any().downcast_ref::<arrow_array::TimestampSecondArray>().unwrap().value(index);
@@ -210,6 +314,30 @@ impl Decode for chrono::DateTime<chrono::Utc> {
     }
 }
 
+/// Decoding a DateTime with the offset of the time zone of the column.
+///
+/// The offset is the one of the time zone of the Arrow `Timestamp` type at that instant, or UTC if the column has no
+/// time zone. Any other type is decoded as a `DateTime<Utc>`.
+impl Decode for chrono::DateTime<chrono::FixedOffset> {
+    fn decode(array: &dyn Array, index: usize) -> Self {
+        match Self::try_decode(array, index) {
+            Ok(datetime) => datetime,
+            Err(e) => panic!("Unable to decode DateTime (reason: {:?})", e),
+        }
+    }
+
+    fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        let datetime = chrono::DateTime::<Utc>::try_decode(array, index)?;
+        match array.data_type() {
+            DataType::Timestamp(_, Some(tz)) => {
+                let tz = tz.parse::<arrow_array::timezone::Tz>().map_err(|e| Error::ArrowError { error: e })?;
+                Ok(datetime.with_timezone(&tz).fixed_offset())
+            }
+            _ => Ok(datetime.fixed_offset()),
+        }
+    }
+}
+
 /// Decoding a NaiveTime.
 impl Decode for chrono::NaiveTime {
     fn decode(array: &dyn Array, index: usize) -> Self {
@@ -220,6 +348,7 @@ impl Decode for chrono::NaiveTime {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
         match array.as_any().downcast_ref::<arrow_array::Time64MicrosecondArray>() {
             Some(array) => {
                 let time_micros = array.value(index);
@@ -240,6 +369,57 @@ impl Decode for chrono::NaiveTime {
     }
 }
 
+/// Decoding a Duration.
+///
+/// The Arrow `Duration` type is an elapsed time and is decoded as is. The `Time64` type is a time of day and is decoded
+/// as the time elapsed since midnight, it is up to the caller to know whether this makes sense (i.e. a column holding
+/// the duration of an event using a `TIME` type).
+impl Decode for chrono::Duration {
+    fn decode(array: &dyn Array, index: usize) -> Self {
+        match Self::try_decode(array, index) {
+            Ok(duration) => duration,
+            Err(e) => panic!("Unable to decode Duration (reason: {:?})", e),
+        }
+    }
+
+    fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
+        macro_rules! value_of {
+            ($array_type:ident) => {
+                array.as_any().downcast_ref::<arrow_array::$array_type>().unwrap().value(index)
+            };
+        }
+        let duration = match array.data_type() {
+            DataType::Duration(TimeUnit::Second) => chrono::Duration::try_seconds(value_of!(DurationSecondArray)),
+            DataType::Duration(TimeUnit::Millisecond) => {
+                chrono::Duration::try_milliseconds(value_of!(DurationMillisecondArray))
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                Some(chrono::Duration::microseconds(value_of!(DurationMicrosecondArray)))
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                Some(chrono::Duration::nanoseconds(value_of!(DurationNanosecondArray)))
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                Some(chrono::Duration::microseconds(value_of!(Time64MicrosecondArray)))
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                Some(chrono::Duration::nanoseconds(value_of!(Time64NanosecondArray)))
+            }
+            _ => {
+                return Err(Error::InvalidType {
+                    expected: "Duration or Time64".to_string(),
+                    actual: array.data_type().to_string(),
+                })
+            }
+        };
+        duration.ok_or_else(|| Error::InvalidType {
+            expected: "Duration within the range of chrono::Duration".to_string(),
+            actual: format!("out of range {}", array.data_type()),
+        })
+    }
+}
+
 /// Decoding a NaiveDate.
 impl Decode for chrono::NaiveDate {
     fn decode(array: &dyn Array, index: usize) -> Self {
@@ -250,6 +430,7 @@ impl Decode for chrono::NaiveDate {
     }
 
     fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
         match array.data_type() {
             DataType::Date32 => {
                 // Date32 is the number of days since the UNIX epoch.
@@ -275,12 +456,125 @@ impl Decode for chrono::NaiveDate {
     }
 }
 
+/// Decoding a tuple from a {{arrow_array::StructArray}}
+///
+/// The fields of the struct are decoded by position, this allows decoding composite columns such as DuckDB's `STRUCT`
+/// into a tuple, e.g. `{'a': 1, 'b': 'x'}` into `(i32, String)`. The struct must have exactly as many fields as the
+/// tuple has elements.
+macro_rules! impl_decode_tuple {
+    ($len:expr, $($name:ident: $index:tt),+) => {
+        impl<$($name: Decode),+> Decode for ($($name,)+) {
+            fn decode(array: &dyn Array, index: usize) -> Self {
+                match Self::try_decode(array, index) {
+                    Ok(value) => value,
+                    Err(e) => panic!("Unable to decode a tuple (reason: {:?})", e),
+                }
+            }
+
+            fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+                check_not_null(array, index)?;
+                match array.as_any().downcast_ref::<arrow_array::StructArray>() {
+                    Some(struct_array) if struct_array.num_columns() == $len => {
+                        Ok(($($name::try_decode(struct_array.column($index).as_ref(), index)?,)+))
+                    }
+                    _ => Err(Error::InvalidType {
+                        expected: format!("Struct with {} fields", $len),
+                        actual: array.data_type().to_string(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_decode_tuple!(1, A: 0);
+impl_decode_tuple!(2, A: 0, B: 1);
+impl_decode_tuple!(3, A: 0, B: 1, C: 2);
+impl_decode_tuple!(4, A: 0, B: 1, C: 2, D: 3);
+impl_decode_tuple!(5, A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_decode_tuple!(6, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_decode_tuple!(7, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_decode_tuple!(8, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+/// Decoding a [Value] from an {{arrow_array::Array}}.
+///
+/// This is useful when the type of a column is not known in advance, a null is decoded as [Value::Null].
+impl Decode for Value {
+    fn decode(array: &dyn Array, index: usize) -> Self {
+        match Self::try_decode(array, index) {
+            Ok(value) => value,
+            Err(e) => panic!("Unable to decode a Value (reason: {:?})", e),
+        }
+    }
+
+    fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        if index >= array.len() {
+            return Err(Error::OutOfBounds { index });
+        }
+        if is_null(array, index) {
+            return Ok(Value::Null);
+        }
+        macro_rules! value_of {
+            ($array_type:ident) => {
+                array.as_any().downcast_ref::<arrow_array::$array_type>().unwrap().value(index)
+            };
+        }
+        match array.data_type() {
+            DataType::Boolean => Ok(Value::Bool(value_of!(BooleanArray))),
+            DataType::Int8 => Ok(Value::Int8(value_of!(Int8Array))),
+            DataType::Int16 => Ok(Value::Int16(value_of!(Int16Array))),
+            DataType::Int32 => Ok(Value::Int32(value_of!(Int32Array))),
+            DataType::Int64 => Ok(Value::Int64(value_of!(Int64Array))),
+            DataType::UInt8 => Ok(Value::UInt8(value_of!(UInt8Array))),
+            DataType::UInt16 => Ok(Value::UInt16(value_of!(UInt16Array))),
+            DataType::UInt32 => Ok(Value::UInt32(value_of!(UInt32Array))),
+            DataType::UInt64 => Ok(Value::UInt64(value_of!(UInt64Array))),
+            DataType::Float32 => Ok(Value::Float32(value_of!(Float32Array))),
+            DataType::Float64 => Ok(Value::Float64(value_of!(Float64Array))),
+            DataType::Utf8 => Ok(Value::String(value_of!(StringArray).to_string())),
+            DataType::LargeUtf8 => Ok(Value::String(value_of!(LargeStringArray).to_string())),
+            DataType::Binary => Ok(Value::Blob(value_of!(BinaryArray).to_vec())),
+            DataType::LargeBinary => Ok(Value::Blob(value_of!(LargeBinaryArray).to_vec())),
+            DataType::Date32 => Ok(Value::Date32(value_of!(Date32Array))),
+            DataType::Timestamp(unit, tz) => {
+                let (unit, value) = match unit {
+                    TimeUnit::Second => (values::TimeUnit::Second, value_of!(TimestampSecondArray)),
+                    TimeUnit::Millisecond => (values::TimeUnit::Millisecond, value_of!(TimestampMillisecondArray)),
+                    TimeUnit::Microsecond => (values::TimeUnit::Microsecond, value_of!(TimestampMicrosecondArray)),
+                    TimeUnit::Nanosecond => (values::TimeUnit::Nanosecond, value_of!(TimestampNanosecondArray)),
+                };
+                match tz {
+                    Some(tz) => Ok(Value::TimestampTz(unit, value, tz.to_string())),
+                    None => Ok(Value::Timestamp(unit, value)),
+                }
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                Ok(Value::Time64(values::TimeUnit::Microsecond, value_of!(Time64MicrosecondArray)))
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                Ok(Value::Time64(values::TimeUnit::Nanosecond, value_of!(Time64NanosecondArray)))
+            }
+            DataType::Interval(IntervalUnit::MonthDayNano) => {
+                let interval = value_of!(IntervalMonthDayNanoArray);
+                Ok(Value::Interval { months: interval.months, days: interval.days, nanos: interval.nanoseconds })
+            }
+            DataType::Decimal128(_, scale) => Ok(Value::Decimal(rust_decimal::Decimal::from_i128_with_scale(
+                value_of!(Decimal128Array),
+                *scale as u32,
+            ))),
+            _ => Err(Error::UnsupportedDataType { data_type: array.data_type().to_string() }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use arrow_array::*;
+    use arrow_buffer::NullBuffer;
     use chrono::Timelike;
     use rust_decimal::Decimal;
+    use std::sync::Arc;
 
     #[test]
     fn test_primitive_types() {
@@ -302,6 +596,70 @@ mod tests {
         assert_eq!(String::decode(&StringArray::from(vec!["test".to_string()]), 0), "test");
     }
 
+    #[test]
+    fn test_primitive_types_null() {
+        // A null is reported instead of decoding the placeholder stored by Arrow (i.e. `0` or an empty string).
+        let is_null_error =
+            |error: Option<Error>| matches!(error, Some(Error::InvalidType { actual, .. }) if actual == "null");
+        assert!(is_null_error(i64::try_decode(&Int64Array::from(vec![None]), 0).err()));
+        assert!(is_null_error(f64::try_decode(&Float64Array::from(vec![None]), 0).err()));
+        assert!(is_null_error(String::try_decode(&StringArray::from(vec![None::<&str>]), 0).err()));
+        assert!(is_null_error(Vec::<u8>::try_decode(&BinaryArray::from(vec![None::<&[u8]>]), 0).err()));
+        assert!(is_null_error(i64::try_decode(&NullArray::new(1), 0).err()));
+        assert!(matches!(i64::try_decode(&Int64Array::from(vec![None]), 1), Err(Error::OutOfBounds { index: 1 })));
+        assert_eq!(i64::try_decode(&Int64Array::from(vec![None, Some(42)]), 1).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_tuple() {
+        let struct_array = StructArray::from(vec![
+            (
+                Arc::new(arrow_schema::Field::new("a", DataType::Int32, false)),
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            ),
+            (
+                Arc::new(arrow_schema::Field::new("b", DataType::Utf8, false)),
+                Arc::new(StringArray::from(vec!["x", "y"])) as ArrayRef,
+            ),
+        ]);
+        assert_eq!(<(i32, String)>::decode(&struct_array, 0), (1, "x".to_string()));
+        assert_eq!(<(i32, String)>::decode(&struct_array, 1), (2, "y".to_string()));
+        assert_eq!(<(Value, Value)>::decode(&struct_array, 1), (Value::Int32(2), Value::String("y".to_string())));
+        assert!(matches!(<(i32,)>::try_decode(&struct_array, 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(<(String, String)>::try_decode(&struct_array, 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(<(i32, String)>::try_decode(&struct_array, 2), Err(Error::OutOfBounds { .. })));
+        assert!(matches!(<(i32, String)>::try_decode(&Int32Array::from(vec![1]), 0), Err(Error::InvalidType { .. })));
+
+        // A null struct cannot be decoded into a tuple.
+        let null_struct = StructArray::new(
+            struct_array.fields().clone(),
+            struct_array.columns().to_vec(),
+            Some(NullBuffer::from(vec![true, false])),
+        );
+        assert_eq!(<(i32, String)>::decode(&null_struct, 0), (1, "x".to_string()));
+        assert!(matches!(<(i32, String)>::try_decode(&null_struct, 1), Err(Error::InvalidType { .. })));
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!(Value::decode(&Int32Array::from(vec![Some(1), None]), 0), Value::Int32(1));
+        assert_eq!(Value::decode(&Int32Array::from(vec![Some(1), None]), 1), Value::Null);
+        assert_eq!(Value::decode(&NullArray::new(1), 0), Value::Null);
+        assert_eq!(Value::decode(&StringArray::from(vec!["hello"]), 0), Value::String("hello".to_string()));
+        assert_eq!(Value::decode(&BinaryArray::from(vec![&[0xde_u8, 0xad][..]]), 0), Value::Blob(vec![0xde, 0xad]));
+        assert_eq!(Value::decode(&Date32Array::from(vec![18628]), 0), Value::Date32(18628));
+        assert_eq!(
+            Value::decode(&TimestampMillisecondArray::from(vec![1720070496101]), 0),
+            Value::Timestamp(values::TimeUnit::Millisecond, 1720070496101)
+        );
+        assert_eq!(
+            Value::decode(&Decimal128Array::from(vec![1999]).with_precision_and_scale(10, 2).unwrap(), 0),
+            Value::Decimal(Decimal::from_i128_with_scale(1999, 2))
+        );
+        assert!(Value::try_decode(&Int32Array::from(vec![1]), 1).is_err());
+        assert!(Value::try_decode(&Time32SecondArray::from(vec![1]), 0).is_err());
+    }
+
     #[test]
     fn test_uuid() {
         assert_eq!(
@@ -310,6 +668,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fixed_size_bytes() {
+        let key: [u8; 16] = std::array::from_fn(|i| i as u8);
+        let hash: [u8; 32] = std::array::from_fn(|i| 0xff - i as u8);
+
+        // FixedSizeBinary
+        let array = FixedSizeBinaryArray::try_from_iter(vec![key.to_vec()].into_iter()).unwrap();
+        assert_eq!(<[u8; 16]>::decode(&array, 0), key);
+        let array = FixedSizeBinaryArray::try_from_iter(vec![hash.to_vec()].into_iter()).unwrap();
+        assert_eq!(<[u8; 32]>::decode(&array, 0), hash);
+
+        // Binary
+        let array = BinaryArray::from(vec![&key[..], &hash[..]]);
+        assert_eq!(<[u8; 16]>::decode(&array, 0), key);
+        assert_eq!(<[u8; 32]>::decode(&array, 1), hash);
+
+        // Errors
+        assert!(matches!(
+            <[u8; 16]>::try_decode(&array, 1),
+            Err(Error::InvalidType { expected, actual }) if expected == "16 bytes" && actual == "32 bytes"
+        ));
+        assert!(matches!(<[u8; 32]>::try_decode(&array, 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(<[u8; 16]>::try_decode(&StringArray::from(vec!["a"]), 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(<[u8; 16]>::try_decode(&array, 2), Err(Error::OutOfBounds { index: 2 })));
+    }
+
     #[test]
     fn test_decode_decimal() {
         assert_eq!(
@@ -319,6 +703,12 @@ mod tests {
             ),
             Decimal::from_i128_with_scale(1999, 2)
         );
+        assert_eq!(Decimal::decode(&Int64Array::from(vec![1999]), 0), Decimal::from(1999));
+        assert_eq!(Decimal::decode(&Int32Array::from(vec![-42]), 0), Decimal::from(-42));
+        assert_eq!(Decimal::decode(&StringArray::from(vec!["19.99"]), 0), Decimal::from_i128_with_scale(1999, 2));
+        assert!(matches!(Decimal::try_decode(&StringArray::from(vec!["abc"]), 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(Decimal::try_decode(&Float64Array::from(vec![1.5]), 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(Decimal::try_decode(&Int64Array::from(vec![1]), 1), Err(Error::OutOfBounds { .. })));
     }
 
     #[test]
@@ -360,4 +750,26 @@ mod tests {
             expected_time
         );
     }
+
+    #[test]
+    fn test_chrono_duration() {
+        use chrono::Duration;
+
+        let expected = Duration::seconds(3_723) + Duration::microseconds(456);
+        assert_eq!(Duration::decode(&DurationSecondArray::from(vec![-90]), 0), Duration::seconds(-90));
+        assert_eq!(Duration::decode(&DurationMillisecondArray::from(vec![3_723_000]), 0), Duration::seconds(3_723));
+        assert_eq!(Duration::decode(&DurationMicrosecondArray::from(vec![3_723_000_456]), 0), expected);
+        assert_eq!(Duration::decode(&DurationNanosecondArray::from(vec![3_723_000_456_000]), 0), expected);
+
+        // A time of day is decoded as the time elapsed since midnight.
+        assert_eq!(Duration::decode(&Time64MicrosecondArray::from(vec![3_723_000_456]), 0), expected);
+        assert_eq!(Duration::decode(&Time64NanosecondArray::from(vec![3_723_000_456_000]), 0), expected);
+
+        assert!(matches!(
+            Duration::try_decode(&DurationSecondArray::from(vec![i64::MAX]), 0),
+            Err(Error::InvalidType { .. })
+        ));
+        assert!(matches!(Duration::try_decode(&Int64Array::from(vec![1]), 0), Err(Error::InvalidType { .. })));
+        assert!(matches!(Duration::try_decode(&DurationSecondArray::from(vec![1]), 1), Err(Error::OutOfBounds { .. })));
+    }
 }
@@ -0,0 +1,91 @@
+//! The implementation of [Decode] for the geometries of `geo-types` (only available with the `geo` feature).
+//!
+//! The spatial extensions of the databases (i.e. PostGIS or the DuckDB spatial extension) return the geometries as
+//! binary values in the Well-Known Binary (WKB) format, they are parsed into a [geo_types::Geometry] using the `wkb`
+//! crate.
+
+use crate::decode::{check_not_null, Decode};
+use crate::{Error, Result};
+use arrow_array::array::Array;
+use arrow_schema::DataType;
+
+/// Decoding a geometry from a {{arrow_array::Array}}
+///
+/// The geometry is parsed from its WKB representation stored in a {{arrow_array::BinaryArray}} or a
+/// {{arrow_array::LargeBinaryArray}}.
+impl Decode for geo_types::Geometry<f64> {
+    fn decode(array: &dyn Array, index: usize) -> Self {
+        match Self::try_decode(array, index) {
+            Ok(geometry) => geometry,
+            Err(e) => panic!("Unable to decode a geometry (reason: {:?})", e),
+        }
+    }
+
+    fn try_decode(array: &dyn Array, index: usize) -> Result<Self> {
+        check_not_null(array, index)?;
+        let mut bytes = match array.data_type() {
+            DataType::Binary => array.as_any().downcast_ref::<arrow_array::BinaryArray>().unwrap().value(index),
+            DataType::LargeBinary => {
+                array.as_any().downcast_ref::<arrow_array::LargeBinaryArray>().unwrap().value(index)
+            }
+            _ => {
+                return Err(Error::InvalidType {
+                    expected: "Binary".to_string(),
+                    actual: array.data_type().to_string(),
+                })
+            }
+        };
+        wkb::wkb_to_geom(&mut bytes)
+            .map_err(|e| Error::InternalError { error: format!("Invalid WKB geometry: {:?}", e).into() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{BinaryArray, Int32Array, LargeBinaryArray};
+    use geo_types::{coord, Geometry, LineString, Point};
+
+    // POINT(1 2)
+    const POINT_WKB: [u8; 21] = [
+        0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x40,
+    ];
+
+    // LINESTRING(0 0, 1 1, 2 4)
+    const LINESTRING_WKB: [u8; 57] = [
+        0x01, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0xf0, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x10, 0x40,
+    ];
+
+    #[test]
+    fn test_decode_point() {
+        let array = BinaryArray::from(vec![&POINT_WKB[..]]);
+        assert_eq!(Geometry::decode(&array, 0), Geometry::Point(Point::new(1.0, 2.0)));
+        let array = LargeBinaryArray::from(vec![&POINT_WKB[..]]);
+        assert_eq!(Geometry::decode(&array, 0), Geometry::Point(Point::new(1.0, 2.0)));
+    }
+
+    #[test]
+    fn test_decode_linestring() {
+        let array = BinaryArray::from(vec![&LINESTRING_WKB[..]]);
+        assert_eq!(
+            Geometry::decode(&array, 0),
+            Geometry::LineString(LineString::new(vec![
+                coord! { x: 0.0, y: 0.0 },
+                coord! { x: 1.0, y: 1.0 },
+                coord! { x: 2.0, y: 4.0 }
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let array = BinaryArray::from(vec![&POINT_WKB[..10]]);
+        assert!(matches!(Geometry::try_decode(&array, 0), Err(Error::InternalError { .. })));
+        assert!(matches!(Geometry::try_decode(&array, 1), Err(Error::OutOfBounds { .. })));
+        assert!(matches!(Geometry::try_decode(&Int32Array::from(vec![1]), 0), Err(Error::InvalidType { .. })));
+    }
+}
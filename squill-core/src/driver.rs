@@ -1,6 +1,9 @@
 use crate::parameters::Parameters;
-use arrow_array::RecordBatch;
-use arrow_schema::SchemaRef;
+use crate::uri::redact_uri;
+use crate::Error;
+use arrow_array::{Int64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::any::Any;
 use std::sync::Arc;
 
 #[cfg(any(test, feature = "mock"))]
@@ -18,8 +21,27 @@ pub type DriverError = Box<dyn std::error::Error + Send + Sync>;
 
 pub type Result<T> = std::result::Result<T, DriverError>;
 
+/// A trait to get a reference to a value as [Any].
+///
+/// This trait is implemented for all the `'static` types and is used to downcast a driver connection into its
+/// concrete type in order to access the features that are specific to a driver.
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 #[cfg_attr(any(test, feature = "mock"), automock)]
-pub trait DriverConnection {
+pub trait DriverConnection: AsAny {
     /// Get the name of the driver.
     ///
     /// The name of the driver should be one of the schemes used to register the driver with the factory but it's not
@@ -35,9 +57,55 @@ pub trait DriverConnection {
     where
         'c: 's;
 
+    /// Prepare a statement giving the types of its first parameters.
+    ///
+    /// The types are hints for the parameters whose type cannot be inferred by the data source (i.e. `SELECT ? + ?`),
+    /// the types of the remaining parameters are inferred as with [`prepare`](Self::prepare). The default
+    /// implementation ignores the types, for drivers not needing them (i.e. SQLite).
+    fn prepare_typed<'c, 's>(
+        &'c mut self,
+        statement: &str,
+        _types: &[DataType],
+    ) -> Result<Box<dyn DriverStatement + 's>>
+    where
+        'c: 's,
+    {
+        self.prepare(statement)
+    }
+
     /// Check if the connection is alive.
     fn ping(&mut self) -> Result<()>;
 
+    /// Describe a statement without executing it.
+    ///
+    /// Returns the schema of the rows returned by the statement and the number of its parameters. The default
+    /// implementation prepares the statement and relies on [DriverStatement::schema], drivers only knowing the schema of
+    /// a statement once executed should override it.
+    fn describe(&mut self, statement: &str) -> Result<Described> {
+        let stmt = self.prepare(statement)?;
+        Ok(Described { schema: stmt.schema(), param_count: stmt.parameter_count() })
+    }
+
+    /// Get a handle to interrupt the statement running on the connection from another thread.
+    ///
+    /// Returns `None` if the driver doesn't support interrupting a statement (the default implementation).
+    fn interrupt_handle(&self) -> Option<Box<dyn DriverInterruptHandle>> {
+        None
+    }
+
+    /// Get the statistics of the cache of prepared statements of the connection.
+    ///
+    /// Returns `None` if the driver doesn't cache the prepared statements (the default implementation).
+    fn cache_stats(&self) -> Option<CacheStats> {
+        None
+    }
+
+    /// Remove all the prepared statements from the cache of the connection.
+    ///
+    /// The statements currently in use are not affected. This is a no-op if the driver doesn't cache the prepared
+    /// statements (the default implementation).
+    fn clear_prepared_cache(&mut self) {}
+
     /// Close the connection.
     ///
     /// Since the connection may be borrowed, the connection should be closed when the last reference to the connection
@@ -45,6 +113,28 @@ pub trait DriverConnection {
     fn close(self: Box<Self>) -> Result<()>;
 }
 
+impl dyn DriverConnection {
+    /// Downcast the connection into the concrete type of the driver.
+    ///
+    /// Returns `None` if the connection is not of the given type.
+    ///
+    /// ```rust,ignore
+    /// let mut conn = Factory::open("duckdb:///:memory:")?;
+    /// let duckdb = conn.downcast_mut::<squill_duckdb::DuckDB>().unwrap();
+    /// ```
+    pub fn downcast_mut<T: DriverConnection>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
+
+/// A handle to interrupt the statement running on a connection from another thread.
+pub trait DriverInterruptHandle: Send + Sync {
+    /// Interrupt the statement running on the connection.
+    ///
+    /// The interrupted statement fails with [crate::Error::Cancelled]. This is a no-op if no statement is running.
+    fn interrupt(&self);
+}
+
 /// A prepared statement ready to be executed.
 ///
 /// A prepared statement can be executed multiple times with different parameters.
@@ -59,17 +149,65 @@ pub trait DriverStatement {
     /// Executing a statement that starts with "SELECT" my return an error depending on the driver implementation.
     fn execute(&mut self, parameters: Option<Parameters>) -> Result<u64>;
 
+    /// Execute the statement and return the details of the execution.
+    ///
+    /// Drivers should override this function when more than the number of rows affected is available (the default
+    /// implementation only calls [`execute`](Self::execute)).
+    fn execute_detailed(&mut self, parameters: Option<Parameters>) -> Result<ExecuteOutcome> {
+        Ok(ExecuteOutcome { rows_affected: self.execute(parameters)?, ..Default::default() })
+    }
+
+    /// Execute the statement once for each set of parameters.
+    ///
+    /// Drivers should override this function when the data source offers a more efficient way to execute a statement
+    /// many times than executing it for each set of parameters (the default implementation).
+    ///
+    /// Only SQLite batches the executions (in a single transaction). MySQL has no bulk execution in its protocol and
+    /// the blocking PostgreSQL client doesn't expose pipelining, so both still execute the statement once per set of
+    /// parameters with a round trip each.
+    ///
+    /// Returns the total number of rows affected by the executions.
+    fn execute_many(&mut self, parameters: Vec<Parameters>) -> Result<u64> {
+        let mut affected_rows = 0;
+        for parameters in parameters {
+            affected_rows += self.execute(Some(parameters))?;
+        }
+        Ok(affected_rows)
+    }
+
     /// Execute a `SELECT` statement.
     ///
     /// The number of parameters must match the number of placeholders in the statement otherwise an error will be
     /// returned.
     ///
-    /// Returns an iterator over the record batches returned by the statement.
+    /// Returns an iterator over the record batches returned by the statement. A statement that doesn't return rows
+    /// (i.e. `INSERT`, `UPDATE`) is executed and, like DuckDB does, a single row is returned with a single
+    /// [AFFECTED_ROWS_COLUMN] column giving the number of rows affected (see [affected_rows_batch]).
     fn query<'s>(
         &'s mut self,
         parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>>;
 
+    /// Execute a `SELECT` statement and return the schema of its record batches along with an iterator over them.
+    ///
+    /// Unlike [`schema`](Self::schema), the schema is available before iterating over the record batches, even if the
+    /// query returns no rows. The default implementation returns the schema of the first record batch, which is read
+    /// ahead, or the [`prepared_schema`](Self::prepared_schema) if the query returns no record batch (an empty schema
+    /// if it is not known).
+    #[allow(clippy::type_complexity)]
+    fn query_with_schema<'s>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+    ) -> Result<(SchemaRef, Box<dyn Iterator<Item = Result<RecordBatch>> + 's>)> {
+        let schema = self.prepared_schema().unwrap_or_else(|| Arc::new(Schema::empty()));
+        let mut batches = self.query(parameters)?;
+        match batches.next() {
+            Some(Ok(batch)) => Ok((batch.schema(), Box::new(std::iter::once(Ok(batch)).chain(batches)))),
+            Some(Err(e)) => Err(e),
+            None => Ok((schema, batches)),
+        }
+    }
+
     /// Get the schema of the last [`query`](Self::query) execution of the statement.
     ///
     /// Returns the schema of the record batches from the last [`query`](Self::query) execution.
@@ -79,9 +217,113 @@ pub trait DriverStatement {
     /// iterator returned by [`query`](Self::query) is still alive. This function should be called after the iterator
     /// returned by [`query`](Self::query) is consumed at least once and after the iterator is dropped.
     ///
+    /// The schema of a statement that doesn't return rows (i.e. `INSERT`) is empty, even though
+    /// [`query`](Self::query) returns a row giving the number of rows affected (see [affected_rows_schema]).
+    ///
     /// WARNING: This function may panic if the statement was not queried before calling this function or if the
-    /// iterator returned by [`query`](Self::query) was not consumed at least once.
+    /// iterator returned by [`query`](Self::query) was not consumed at least once. Use
+    /// [`query_with_schema`](Self::query_with_schema) to get the schema along with the record batches instead.
     fn schema(&self) -> SchemaRef;
+
+    /// Get the schema of the statement if it is known without executing the statement.
+    ///
+    /// Unlike [`schema`](Self::schema), this function never panics. Returns `None` if the driver only knows the schema
+    /// once the statement is executed (the default implementation, i.e. DuckDB).
+    fn prepared_schema(&self) -> Option<SchemaRef> {
+        None
+    }
+
+    /// Reset the statement so it can be reused.
+    ///
+    /// The parameters bound by the previous execution are cleared and the state of the cursor of the last
+    /// [`query`](Self::query) is discarded. The default implementation does nothing, for drivers not keeping any state
+    /// between executions.
+    fn reset(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get an estimate of the number of rows returned by the last [`query`](Self::query) execution of the statement.
+    ///
+    /// Returns `None` if the driver doesn't know the number of rows (the default implementation). Depending on the
+    /// driver, the estimate may only be available once the iterator returned by [`query`](Self::query) has been
+    /// exhausted (i.e. PostgreSQL).
+    fn estimated_row_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Get the number of parameters (placeholders) of the statement.
+    ///
+    /// The default implementation counts the types given by [`parameter_types`](Self::parameter_types) and returns `0`
+    /// if they are unknown, drivers should override it when the number of parameters is known.
+    fn parameter_count(&self) -> usize {
+        self.parameter_types().map_or(0, |types| types.len())
+    }
+
+    /// Get the types expected for the parameters of the statement.
+    ///
+    /// Returns `None` if the driver doesn't know the types of the parameters (the default implementation).
+    fn parameter_types(&self) -> Option<Vec<DataType>> {
+        None
+    }
+
+    /// Set the maximum number of rows of the record batches returned by the next [`query`](Self::query) executions,
+    /// overriding the `max_batch_rows` of the [DriverOptions] of the connection (`None` to restore it).
+    ///
+    /// The default implementation does nothing, for drivers not building the record batches themselves.
+    fn set_max_batch_rows(&mut self, _max_batch_rows: Option<usize>) {}
+}
+
+/// The description of a statement returned by [DriverConnection::describe].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Described {
+    /// The schema of the rows returned by the statement (empty if the statement doesn't return any row).
+    pub schema: SchemaRef,
+
+    /// The number of parameters of the statement.
+    pub param_count: usize,
+}
+
+/// The statistics of the cache of prepared statements returned by [DriverConnection::cache_stats].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of statements prepared that were found in the cache.
+    pub hits: u64,
+
+    /// The number of statements prepared that were not found in the cache.
+    pub misses: u64,
+
+    /// The number of prepared statements currently in the cache.
+    pub size: usize,
+}
+
+/// The name of the column of the row returned when querying a statement that doesn't return rows.
+pub const AFFECTED_ROWS_COLUMN: &str = "Count";
+
+/// Get the schema of the row returned when querying a statement that doesn't return rows.
+pub fn affected_rows_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new(AFFECTED_ROWS_COLUMN, DataType::Int64, true)]))
+}
+
+/// Build the record batch returned when querying a statement that doesn't return rows.
+///
+/// The batch has a single row with the number of rows affected by the statement (see [DriverStatement::query]).
+pub fn affected_rows_batch(affected_rows: u64) -> Result<RecordBatch> {
+    let affected_rows = i64::try_from(affected_rows)?;
+    Ok(RecordBatch::try_new(affected_rows_schema(), vec![Arc::new(Int64Array::from(vec![affected_rows]))])?)
+}
+
+/// The details of the execution of a statement returned by [DriverStatement::execute_detailed].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecuteOutcome {
+    /// The number of rows affected by the statement.
+    pub rows_affected: u64,
+
+    /// The id generated for the row inserted by the statement, if any (i.e. the `rowid` with SQLite, the value of the
+    /// `AUTO_INCREMENT` column with MySQL).
+    pub last_insert_id: Option<i64>,
+
+    /// The warnings raised by the execution of the statement (i.e. the result of `SHOW WARNINGS` with MySQL).
+    pub warnings: Vec<String>,
 }
 
 #[cfg_attr(any(test, feature = "mock"), automock)]
@@ -89,6 +331,15 @@ pub trait DriverFactory: Sync + Send {
     /// Get the schemes associated with the driver.
     fn schemes(&self) -> &'static [&'static str];
     fn open(&self, uri: &str, options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>>;
+
+    /// Apply the suffix of a scheme to an URI (i.e. `tls` for `mysql+tls://...`).
+    ///
+    /// An URI whose scheme is not registered but is one of the schemes of the driver followed by `+suffix` is opened by
+    /// the driver: the suffix is stripped from the URI and this function returns the URI to open, typically by turning
+    /// the suffix into a parameter of the URI. The default implementation rejects any suffix.
+    fn apply_scheme_suffix(&self, uri: &str, suffix: &str) -> Result<String> {
+        Err(Error::InvalidUri { uri: redact_uri(uri), reason: format!("Unsupported scheme suffix: {}", suffix) }.into())
+    }
 }
 
 /// The options that can be used by any driver.
@@ -54,12 +54,41 @@ pub enum Error {
 
     NotFound,
 
+    /// The operation was cancelled before its completion (i.e. a statement interrupted from another thread).
+    Cancelled,
+
+    /// Some tables expected to exist in the database are missing.
+    MissingTables {
+        tables: Vec<String>,
+    },
+
     Timeout,
 
+    /// The driver is reporting that the transaction was aborted to resolve a deadlock with another transaction.
+    Deadlock {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// The driver is reporting that the transaction could not be serialized because of concurrent transactions.
+    SerializationFailure {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// The driver is reporting that the database is locked by another connection for longer than it was willing to
+    /// wait (i.e. SQLite's busy timeout).
+    Busy {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     OutOfBounds {
         index: usize,
     },
 
+    /// The driver is reporting that a write was attempted on a read-only connection, database or transaction.
+    ReadOnly {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     /// The driver is reporting that it is out of memory.
     OutOfMemory {
         error: Box<dyn std::error::Error + Send + Sync>,
@@ -73,12 +102,56 @@ pub enum Error {
         message: String,
     },
 
+    /// An error occurred while decoding the value of a column.
+    Column {
+        /// The name of the column.
+        name: String,
+        /// The index of the column.
+        index: usize,
+        /// The error raised when decoding the value.
+        source: Box<Error>,
+    },
+
     /// An error that doesn't fit in any of the other error types.
     DriverError {
         error: Box<dyn std::error::Error + Send + Sync>,
     },
 }
 
+impl Error {
+    /// Check if the operation that failed with this error is worth retrying.
+    ///
+    /// An error is retryable when it is caused by a transient condition such as a timeout, a failed connection, a
+    /// deadlock, a serialization failure or a busy database, the same operation having a chance to succeed if attempted
+    /// again (in a new transaction). The other errors (i.e. a constraint violation or an invalid type) will fail again.
+    ///
+    /// The operation should be retried after a delay growing with the attempts, so the transactions in conflict don't
+    /// keep failing together and an overloaded server is not flooded.
+    ///
+    /// ```rust,ignore
+    /// let mut delay = Duration::from_millis(10);
+    /// loop {
+    ///     match conn.execute("UPDATE accounts SET balance = balance - 1 WHERE id = 1", None) {
+    ///         Err(error) if error.is_retryable() && delay <= Duration::from_secs(1) => {
+    ///             std::thread::sleep(delay);
+    ///             delay *= 2;
+    ///         }
+    ///         result => break result,
+    ///     }
+    /// }
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::Timeout
+                | Error::ConnectionFailed { .. }
+                | Error::Deadlock { .. }
+                | Error::SerializationFailure { .. }
+                | Error::Busy { .. }
+        )
+    }
+}
+
 impl From<crate::driver::DriverError> for Error {
     fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
         match err.downcast::<Error>() {
@@ -112,6 +185,7 @@ impl std::fmt::Display for Error {
             Error::ArrowError { error } => write!(f, "{}", error),
             Error::ConstraintViolation { error } => write!(f, "{}", error),
             Error::ConnectionFailed { message } => write!(f, "{}", message),
+            Error::Column { name, index, source } => write!(f, "Column '{}' (index {}): {}", name, index, source),
             Error::StorageFull { error } => write!(f, "{}", error),
             Error::DriverError { error } => write!(f, "{}", error),
             Error::DriverNotFound { scheme } => write!(f, "No driver found for scheme: {}", scheme),
@@ -129,12 +203,49 @@ impl std::fmt::Display for Error {
             }
             Error::InvalidUri { uri, reason } => write!(f, "Invalid URI: {} (reason: {})", uri, reason),
             Error::NotFound => write!(f, "Not found"),
+            Error::Cancelled => write!(f, "Operation cancelled"),
+            Error::MissingTables { tables } => write!(f, "Missing tables: {}", tables.join(", ")),
             Error::OutOfBounds { index } => write!(f, "Out of bounds index {}", index),
             Error::OutOfMemory { error } => write!(f, "{}", error),
+            Error::ReadOnly { error } => write!(f, "{}", error),
             Error::UnsupportedDataType { data_type } => write!(f, "Unsupported type: {}", data_type),
             Error::Timeout => write!(f, "Operation timed out"),
+            Error::Deadlock { error } => write!(f, "{}", error),
+            Error::SerializationFailure { error } => write!(f, "{}", error),
+            Error::Busy { error } => write!(f, "{}", error),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Column { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn test_is_retryable() {
+        // Transient errors
+        assert!(Error::Timeout.is_retryable());
+        assert!(Error::ConnectionFailed { message: "connection refused".to_string() }.is_retryable());
+        assert!(Error::Deadlock { error: "deadlock detected".into() }.is_retryable());
+        assert!(Error::SerializationFailure { error: "could not serialize access".into() }.is_retryable());
+        assert!(Error::Busy { error: "database is locked".into() }.is_retryable());
+
+        // Permanent errors
+        assert!(!Error::ConstraintViolation { error: "UNIQUE constraint failed".into() }.is_retryable());
+        assert!(!Error::InvalidType { expected: "Int32".to_string(), actual: "Utf8".to_string() }.is_retryable());
+        assert!(!Error::InvalidParameterCount { expected: 1, actual: 2 }.is_retryable());
+        assert!(!Error::NotFound.is_retryable());
+        assert!(!Error::ReadOnly { error: "attempt to write a readonly database".into() }.is_retryable());
+        assert!(!Error::Cancelled.is_retryable());
+        assert!(!Error::DriverError { error: "syntax error".into() }.is_retryable());
+    }
+}
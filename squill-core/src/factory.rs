@@ -2,6 +2,7 @@ use crate::driver::DriverConnection;
 use crate::driver::DriverFactory;
 use crate::driver::DriverOptionsRef;
 use crate::error::Error;
+use crate::uri::{expand_env_vars, parse_scheme};
 use crate::Result;
 use lazy_static::lazy_static;
 use path_slash::PathExt;
@@ -38,19 +39,27 @@ impl Factory {
 
     /// Open a connection to a database using the given URI and options.
     ///
-    /// The URI must start with a scheme followed by a colon and the rest of the URI is driver-specific.
+    /// The URI must start with a scheme followed by a colon and the rest of the URI is driver-specific. The scheme can
+    /// have a suffix (i.e. `mysql+tls://`) which is applied by the driver, see [DriverFactory::apply_scheme_suffix].
+    /// A malformed scheme is reported with the offset of the error in the URI, see [parse_scheme].
+    ///
+    /// The user info and the host of the URI can reference environment variables (i.e.
+    /// `postgres://${PGUSER}:${PGPASSWORD}@${PGHOST}/db`), see [expand_env_vars].
     pub fn open_with_options(uri: &str, options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>> {
-        // Extract the scheme from the URI.
-        if let Some(captures) = regex::Regex::new("^([a-zA-Z][a-zA-Z0-9+.-]*):")?.captures(uri) {
-            // It is safe to unwrap because the regex has matched and the capture group must be present otherwise the
-            // regex would not match.
-            let scheme = captures.get(1).unwrap().as_str();
-            match DRIVER_FACTORIES.find(scheme) {
-                Some(driver) => return driver.open(uri, options).map_err(Error::from),
-                None => return Err(Error::DriverNotFound { scheme: scheme.to_string() }),
+        let uri = &expand_env_vars(uri)?;
+        let scheme = parse_scheme(uri)?;
+        if let Some(driver) = DRIVER_FACTORIES.find(scheme) {
+            return driver.open(uri, options).map_err(Error::from);
+        }
+        // A scheme with a suffix (i.e. `mysql+tls`) is opened by the driver of the scheme without the suffix.
+        if let Some((base_scheme, suffix)) = scheme.split_once('+') {
+            if let Some(driver) = DRIVER_FACTORIES.find(base_scheme) {
+                let uri = format!("{}{}", base_scheme, &uri[scheme.len()..]);
+                let uri = driver.apply_scheme_suffix(&uri, suffix)?;
+                return driver.open(&uri, options).map_err(Error::from);
             }
         }
-        Err(Error::InvalidUri { uri: uri.to_string(), reason: "No scheme found".to_string() })
+        Err(Error::DriverNotFound { scheme: scheme.to_string() })
     }
 
     /// Make a file path suitable for an URI.
@@ -88,7 +97,27 @@ mod tests {
     fn test_register() {
         assert!(Factory::open("mock://").is_ok());
         assert!(Factory::open("unknown://").is_err());
-        assert!(Factory::open("invalid/:://").is_err());
-        assert!(Factory::open("").is_err());
+        assert!(matches!(Factory::open("invalid/:://"), Err(Error::InputError { offset: 7, .. })));
+        assert!(matches!(Factory::open(""), Err(Error::InputError { offset: 0, .. })));
+    }
+
+    #[test]
+    fn test_scheme_suffix() {
+        // The mock driver turns the suffix into a parameter of the URI.
+        assert!(Factory::open("mock+tls://").is_ok());
+        assert!(Factory::open("mock+error://").is_err());
+        assert!(
+            matches!(Factory::open("unknown+tls://"), Err(Error::DriverNotFound { scheme }) if scheme == "unknown+tls")
+        );
+    }
+
+    #[test]
+    fn test_open_with_env_vars() {
+        std::env::set_var("TEST_FACTORY_MOCK_USER", "user");
+        assert!(Factory::open("mock://${TEST_FACTORY_MOCK_USER}@localhost").is_ok());
+        assert!(matches!(
+            Factory::open("mock://${TEST_FACTORY_UNDEFINED}@localhost"),
+            Err(Error::InvalidUri { reason, .. }) if reason.contains("TEST_FACTORY_UNDEFINED")
+        ));
     }
 }
@@ -6,10 +6,20 @@ pub mod driver;
 pub mod error;
 pub mod factory;
 pub mod macros;
+pub mod pagination;
 pub mod parameters;
+pub mod placeholders;
+pub mod quote;
 pub mod row;
+pub mod uri;
 pub mod values;
 
+#[cfg(feature = "serde")]
+mod value_serde;
+
+#[cfg(feature = "geo")]
+mod decode_geo;
+
 /// The mock module is only available when running test or when the `mock` feature is enabled.
 /// It provides a mock implementation of the driver and connection to be used in tests.
 #[cfg(any(test, feature = "mock"))]
@@ -34,7 +34,56 @@ macro_rules! query {
                 $rest.into(),
             )*
         ];
-        $statement.query(Some($crate::parameters::Parameters::Positional(bind_parameters)))
+        if bind_parameters.is_empty() {
+            $statement.query(None)
+        }
+        else {
+            $statement.query(Some($crate::parameters::Parameters::Positional(bind_parameters)))
+        }
+    }};
+}
+
+/// Execute a command with named parameters given as `"name" => value` pairs.
+///
+/// ```rust,ignore
+/// execute_named!(conn, "INSERT INTO employee (id, name) VALUES (:id, :name)", "id" => 1, "name" => "Alice")?;
+/// ```
+#[macro_export]
+macro_rules! execute_named {
+    ($conn:expr, $command:expr $(, $name:expr => $value:expr)* $(,)?) => {{
+        let bind_parameters: Vec<(String, $crate::values::Value)> = vec![
+            $(
+                ($name.to_string(), $value.into()),
+            )*
+        ];
+        if bind_parameters.is_empty() {
+            $conn.execute($command, None)
+        }
+        else {
+            $conn.execute($command, Some($crate::parameters::Parameters::Named(bind_parameters)))
+        }
+    }};
+}
+
+/// Query a statement with named parameters given as `"name" => value` pairs.
+///
+/// ```rust,ignore
+/// let rows = query_named!(stmt, "id" => 1, "name" => "Alice")?;
+/// ```
+#[macro_export]
+macro_rules! query_named {
+    ($statement:expr $(, $name:expr => $value:expr)* $(,)?) => {{
+        let bind_parameters: Vec<(String, $crate::values::Value)> = vec![
+            $(
+                ($name.to_string(), $value.into()),
+            )*
+        ];
+        if bind_parameters.is_empty() {
+            $statement.query(None)
+        }
+        else {
+            $statement.query(Some($crate::parameters::Parameters::Named(bind_parameters)))
+        }
     }};
 }
 
@@ -136,3 +185,37 @@ macro_rules! assert_ok_some {
         $crate::assert_some!(tokio_test::assert_ok!($expr))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::factory::Factory;
+    use crate::mock::{RecordedCall, RecordingMock};
+    use crate::parameters::Parameters;
+    use crate::values::Value;
+
+    #[test]
+    fn test_query_macros() {
+        let recorder = RecordingMock::new();
+        let mut conn = Factory::open(&recorder.uri()).unwrap();
+        let mut stmt = conn.prepare("SELECT 1 WHERE ?").unwrap();
+        recorder.clear();
+
+        // Without parameters, the statement is queried with `None` rather than an empty list of parameters.
+        assert!(query!(stmt).is_ok());
+        assert!(query_named!(stmt).is_ok());
+        assert!(query!(stmt, 1).is_ok());
+        assert!(query_named!(stmt, "id" => 1).is_ok());
+        assert_eq!(
+            recorder.calls(),
+            vec![
+                RecordedCall::Query("SELECT 1 WHERE ?".to_string(), None),
+                RecordedCall::Query("SELECT 1 WHERE ?".to_string(), None),
+                RecordedCall::Query("SELECT 1 WHERE ?".to_string(), params!(1)),
+                RecordedCall::Query(
+                    "SELECT 1 WHERE ?".to_string(),
+                    Some(Parameters::Named(vec![("id".to_string(), Value::Int32(1))]))
+                ),
+            ]
+        );
+    }
+}
@@ -1,9 +1,127 @@
+use crate::driver::Described;
+use crate::driver::ExecuteOutcome;
 use crate::driver::MockDriverConnection;
 use crate::driver::MockDriverFactory;
 use crate::driver::MockDriverStatement;
 use crate::driver::Result;
+use crate::parameters::Parameters;
+use crate::uri::{parse_millis, take_query_param};
 use arrow_array::RecordBatch;
-use std::sync::Arc;
+use arrow_schema::SchemaRef;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The name of the URI query parameter to delay the record batches returned by the mock driver (in milliseconds).
+pub const DELAY_PARAM: &str = "delay_ms";
+
+/// The name of the URI query parameter to record the calls made on a mock connection (see [RecordingMock]).
+pub const RECORD_PARAM: &str = "record";
+
+/// A call made on a mock connection or one of its statements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedCall {
+    /// A statement was prepared.
+    Prepare(String),
+
+    /// A prepared statement was executed with the given parameters.
+    Execute(String, Option<Parameters>),
+
+    /// A prepared statement was queried with the given parameters.
+    Query(String, Option<Parameters>),
+}
+
+/// The calls recorded by a mock connection.
+type Recording = Arc<Mutex<Vec<RecordedCall>>>;
+
+lazy_static! {
+    static ref RECORDINGS: Mutex<HashMap<String, Recording>> = Mutex::new(HashMap::new());
+}
+
+/// A recorder of the calls made on mock connections.
+///
+/// The calls made on the connections opened with [RecordingMock::uri] are recorded and can be checked to assert that
+/// the code under test issued the expected statements with the expected parameters.
+///
+/// ```rust
+/// use squill_core::factory::Factory;
+/// use squill_core::mock::{RecordedCall, RecordingMock};
+/// use squill_core::params;
+///
+/// let recorder = RecordingMock::new();
+/// let mut conn = Factory::open(&recorder.uri()).unwrap();
+/// conn.prepare("INSERT ?").unwrap().execute(params!(1)).unwrap();
+/// assert_eq!(
+///     recorder.calls(),
+///     vec![RecordedCall::Prepare("INSERT ?".to_string()), RecordedCall::Execute("INSERT ?".to_string(), params!(1))]
+/// );
+/// ```
+pub struct RecordingMock {
+    name: String,
+}
+
+impl RecordingMock {
+    /// Create a new recorder.
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        let name = format!("recording_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        RECORDINGS.lock().unwrap().insert(name.clone(), Recording::default());
+        Self { name }
+    }
+
+    /// The name of the recorder, used by [recorded_calls].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The URI to open a mock connection recording its calls into this recorder.
+    pub fn uri(&self) -> String {
+        format!("mock://?{}={}", RECORD_PARAM, self.name)
+    }
+
+    /// Get the calls recorded so far.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        recorded_calls(&self.name)
+    }
+
+    /// Forget the calls recorded so far.
+    pub fn clear(&self) {
+        if let Some(recording) = RECORDINGS.lock().unwrap().get(&self.name) {
+            recording.lock().unwrap().clear();
+        }
+    }
+}
+
+impl Default for RecordingMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RecordingMock {
+    fn drop(&mut self) {
+        RECORDINGS.lock().unwrap().remove(&self.name);
+    }
+}
+
+/// Get the calls recorded by the recorder with the given name.
+///
+/// Returns an empty list if there is no such recorder.
+pub fn recorded_calls(name: &str) -> Vec<RecordedCall> {
+    match RECORDINGS.lock().unwrap().get(name) {
+        Some(recording) => recording.lock().unwrap().clone(),
+        None => Vec::new(),
+    }
+}
+
+/// Record a call if the connection is recording.
+fn record(recording: &Option<Recording>, call: RecordedCall) {
+    if let Some(recording) = recording {
+        recording.lock().unwrap().push(call);
+    }
+}
 
 /// A factory for mocking a {{DriverConnection}}.
 ///
@@ -42,14 +160,36 @@ use std::sync::Arc;
 /// drop(stmt);
 /// let mut stmt = conn.prepare("INSERT 1").unwrap(); // anything else returns an error
 /// assert!(stmt.query(None).is_err());
+/// drop(stmt);
+///
+/// // Opening a connection with the URI "mock://?delay_ms=50" waits 50ms before returning each record batch
+/// let mut conn = Factory::open("mock://?delay_ms=50").unwrap();
+/// let mut stmt = conn.prepare("SELECT 1").unwrap();
+/// let started = std::time::Instant::now();
+/// assert!(stmt.query(None).unwrap().next().unwrap().is_ok());
+/// assert!(started.elapsed() >= std::time::Duration::from_millis(50));
 /// ```
 impl MockDriverFactory {
     pub fn register_with_default(schemes: &'static [&'static str]) {
         let mut mock_factory = MockDriverFactory::default();
-        mock_factory.expect_open().returning(|uri, _options| match uri.contains("?error") {
-            false => Ok(Box::new(MockDriverConnection::with_default())),
-            true => Err("Invalid URI".into()),
+        mock_factory.expect_open().returning(|uri, _options| {
+            if uri.contains("?error") {
+                return Err("Invalid URI".into());
+            }
+            let delay = match take_query_param(uri, DELAY_PARAM)? {
+                (_, Some(value)) => parse_millis(uri, DELAY_PARAM, &value)?,
+                (_, None) => Duration::ZERO,
+            };
+            let recording = match take_query_param(uri, RECORD_PARAM)? {
+                (_, Some(name)) => match RECORDINGS.lock().unwrap().get(&name) {
+                    Some(recording) => Some(recording.clone()),
+                    None => return Err(format!("Unknown recording: {}", name).into()),
+                },
+                (_, None) => None,
+            };
+            Ok(Box::new(MockDriverConnection::with_recording(delay, recording)))
         });
+        mock_factory.expect_apply_scheme_suffix().returning(|uri, suffix| Ok(format!("{}?{}", uri, suffix)));
         mock_factory.register(schemes);
     }
 
@@ -59,64 +199,133 @@ impl MockDriverFactory {
     }
 }
 
+/// Execute a statement: any statement that doesn't start with "SELECT" affects 1 row.
+fn mock_execute(stmt: &str, parameters: Option<Parameters>) -> Result<u64> {
+    match stmt.starts_with("SELECT ") {
+        false => {
+            if parameters.is_some() && stmt.matches('?').count() != parameters.unwrap().len() {
+                return Err("Invalid parameter count".into());
+            }
+            Ok(1)
+        }
+        true => Err("Invalid statement".into()),
+    }
+}
+
+/// The schema of the rows returned by the mock statements.
+fn mock_schema() -> SchemaRef {
+    Arc::new(arrow_schema::Schema::new(vec![
+        arrow_schema::Field::new("id", arrow_schema::DataType::Int32, true),
+        arrow_schema::Field::new("username", arrow_schema::DataType::Utf8, true),
+    ]))
+}
+
+/// Query a statement: `SELECT <count>` returns a single batch of `count` rows, no batch if `count` is 0, and a failing
+/// batch if `count` is negative.
+fn mock_query(
+    stmt: &str,
+    parameters: Option<Parameters>,
+    delay: Duration,
+    recording: &Option<Recording>,
+) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>>>> {
+    record(recording, RecordedCall::Query(stmt.to_string(), parameters.clone()));
+    if parameters.is_some() && stmt.matches('?').count() != parameters.unwrap().len() {
+        return Err("Invalid parameter count".into());
+    }
+    match regex::Regex::new(r"^SELECT\s+(-?[0-9]+)").unwrap().captures(stmt) {
+        Some(captures) => {
+            let count = captures.get(1).unwrap().as_str().parse::<i64>().unwrap();
+            match count {
+                _ if count < 0 => {
+                    // Fails at the first iteration
+                    Ok(Box::new(std::iter::once(Err("Invalid count".into()) as Result<RecordBatch>)))
+                }
+                0 => {
+                    // No records
+                    Ok(Box::new(std::iter::empty()))
+                }
+                _ => {
+                    // Returns a single batch that contains `count` of records
+                    let ids: Vec<Option<i32>> = (1..=count).map(|n| Some(n as i32)).collect();
+                    let usernames: Vec<Option<String>> = (1..=count).map(|n| Some(format!("user{}", n))).collect();
+                    let record_batch = RecordBatch::try_new(
+                        mock_schema(),
+                        vec![
+                            std::sync::Arc::new(arrow_array::Int32Array::from(ids)),
+                            std::sync::Arc::new(arrow_array::StringArray::from(usernames)),
+                        ],
+                    )
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>);
+                    Ok(Box::new(std::iter::once_with(move || {
+                        if !delay.is_zero() {
+                            std::thread::sleep(delay);
+                        }
+                        record_batch
+                    })))
+                }
+            }
+        }
+        None => Err(format!("Invalid statement: {}", stmt).into()),
+    }
+}
+
 impl MockDriverStatement {
     pub fn with_default(stmt: String) -> MockDriverStatement {
+        Self::with_delay(stmt, Duration::ZERO)
+    }
+
+    /// Create a mock statement waiting for the given delay before returning each record batch.
+    pub fn with_delay(stmt: String, delay: Duration) -> MockDriverStatement {
+        Self::with_recording(stmt, delay, None)
+    }
+
+    fn with_recording(stmt: String, delay: Duration, recording: Option<Recording>) -> MockDriverStatement {
         let query_stmt = stmt.clone();
         let execute_stmt = stmt.clone();
+        let execute_recording = recording.clone();
+        let execute_many_recording = recording.clone();
         let mut mock_statement = MockDriverStatement::new();
-        mock_statement.expect_schema().returning(|| {
-            Arc::new(arrow_schema::Schema::new(vec![
-                arrow_schema::Field::new("id", arrow_schema::DataType::Int32, true),
-                arrow_schema::Field::new("username", arrow_schema::DataType::Utf8, true),
-            ]))
+        mock_statement.expect_schema().returning(mock_schema);
+        mock_statement.expect_prepared_schema().returning(|| Some(mock_schema()));
+        mock_statement.expect_estimated_row_count().return_const(None);
+        mock_statement.expect_reset().returning(|| Ok(()));
+        mock_statement.expect_parameter_count().return_const(0_usize);
+        mock_statement.expect_parameter_types().return_const(None);
+        mock_statement.expect_set_max_batch_rows().return_const(());
+        let execute_many_stmt = stmt.clone();
+        let execute_detailed_stmt = stmt.clone();
+        let execute_detailed_recording = recording.clone();
+        mock_statement.expect_execute().returning(move |parameters| {
+            record(&execute_recording, RecordedCall::Execute(execute_stmt.clone(), parameters.clone()));
+            mock_execute(&execute_stmt, parameters)
         });
-        mock_statement.expect_execute().returning(move |parameters| match execute_stmt.starts_with("SELECT ") {
-            false => {
-                if parameters.is_some() && execute_stmt.matches('?').count() != parameters.unwrap().len() {
-                    return Err("Invalid parameter count".into());
-                }
-                Ok(1)
-            }
-            true => Err("Invalid statement".into()),
+        mock_statement.expect_execute_detailed().returning(move |parameters| {
+            record(
+                &execute_detailed_recording,
+                RecordedCall::Execute(execute_detailed_stmt.clone(), parameters.clone()),
+            );
+            Ok(ExecuteOutcome {
+                rows_affected: mock_execute(&execute_detailed_stmt, parameters)?,
+                ..Default::default()
+            })
         });
-        mock_statement.expect_query().returning(move |parameters| {
-            if parameters.is_some() && query_stmt.matches('?').count() != parameters.unwrap().len() {
-                return Err("Invalid parameter count".into());
-            }
-            match regex::Regex::new(r"^SELECT\s+(-?[0-9]+)").unwrap().captures(query_stmt.as_str()) {
-                Some(captures) => {
-                    let count = captures.get(1).unwrap().as_str().parse::<i64>().unwrap();
-                    match count {
-                        _ if count < 0 => {
-                            // Fails at the first iteration
-                            Ok(Box::new(std::iter::once(Err("Invalid count".into()) as Result<RecordBatch>)))
-                        }
-                        0 => {
-                            // No records
-                            Ok(Box::new(std::iter::empty()))
-                        }
-                        _ => {
-                            // Returns a single batch that contains `count` of records
-                            let ids: Vec<Option<i32>> = (1..=count).map(|n| Some(n as i32)).collect();
-                            let usernames: Vec<Option<String>> =
-                                (1..=count).map(|n| Some(format!("user{}", n))).collect();
-                            let record_batch = RecordBatch::try_new(
-                                std::sync::Arc::new(arrow_schema::Schema::new(vec![
-                                    arrow_schema::Field::new("id", arrow_schema::DataType::Int32, true),
-                                    arrow_schema::Field::new("username", arrow_schema::DataType::Utf8, true),
-                                ])),
-                                vec![
-                                    std::sync::Arc::new(arrow_array::Int32Array::from(ids)),
-                                    std::sync::Arc::new(arrow_array::StringArray::from(usernames)),
-                                ],
-                            )
-                            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>);
-                            Ok(Box::new(std::iter::once(record_batch)))
-                        }
-                    }
-                }
-                None => Err(format!("Invalid statement: {}", query_stmt).into()),
-            }
+        mock_statement.expect_execute_many().returning(move |parameters| {
+            parameters.into_iter().try_fold(0, |count, parameters| {
+                record(
+                    &execute_many_recording,
+                    RecordedCall::Execute(execute_many_stmt.clone(), Some(parameters.clone())),
+                );
+                Ok(count + mock_execute(&execute_many_stmt, Some(parameters))?)
+            })
+        });
+        let query_with_schema_stmt = stmt.clone();
+        let query_with_schema_recording = recording.clone();
+        mock_statement
+            .expect_query()
+            .returning(move |parameters| mock_query(&query_stmt, parameters, delay, &recording));
+        mock_statement.expect_query_with_schema().returning(move |parameters| {
+            let iterator = mock_query(&query_with_schema_stmt, parameters, delay, &query_with_schema_recording)?;
+            Ok((mock_schema(), iterator))
         });
         mock_statement
     }
@@ -124,13 +333,34 @@ impl MockDriverStatement {
 
 impl MockDriverConnection {
     pub fn with_default() -> MockDriverConnection {
+        Self::with_delay(Duration::ZERO)
+    }
+
+    /// Create a mock connection which statements are waiting for the given delay before returning each record batch.
+    pub fn with_delay(delay: Duration) -> MockDriverConnection {
+        Self::with_recording(delay, None)
+    }
+
+    fn with_recording(delay: Duration, recording: Option<Recording>) -> MockDriverConnection {
         let mut mock_connection = MockDriverConnection::default();
         mock_connection.expect_driver_name().return_const("mock".to_string());
         mock_connection.expect_ping().returning(|| Ok(()));
+        mock_connection.expect_interrupt_handle().returning(|| None);
+        mock_connection.expect_describe().returning(|_| Ok(Described { schema: mock_schema(), param_count: 0 }));
+        mock_connection.expect_cache_stats().return_const(None);
+        mock_connection.expect_clear_prepared_cache().return_const(());
         mock_connection.expect_close().returning(|| Ok(()));
-        mock_connection.expect_prepare().returning(|stmt| match stmt {
-            "XINSERT" => Err("Invalid statement".into()),
-            _ => Ok(Box::new(MockDriverStatement::with_default(stmt.to_string()))),
+        let prepare_typed_recording = recording.clone();
+        mock_connection.expect_prepare_typed().returning(move |stmt, _types| {
+            record(&prepare_typed_recording, RecordedCall::Prepare(stmt.to_string()));
+            Ok(Box::new(MockDriverStatement::with_recording(stmt.to_string(), delay, prepare_typed_recording.clone())))
+        });
+        mock_connection.expect_prepare().returning(move |stmt| {
+            record(&recording, RecordedCall::Prepare(stmt.to_string()));
+            match stmt {
+                "XINSERT" => Err("Invalid statement".into()),
+                _ => Ok(Box::new(MockDriverStatement::with_recording(stmt.to_string(), delay, recording.clone()))),
+            }
         });
         mock_connection
     }
@@ -142,3 +372,51 @@ pub fn register_driver() {
         MockDriverFactory::register_with_default(&["mock"]);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::Factory;
+    use crate::params;
+
+    #[test]
+    fn test_recording_mock() {
+        let recorder = RecordingMock::new();
+        let mut conn = Factory::open(&recorder.uri()).unwrap();
+        assert!(conn.prepare("INSERT ?").unwrap().execute(params!(1)).is_ok());
+        let mut stmt = conn.prepare("SELECT 2").unwrap();
+        assert_eq!(stmt.query(None).unwrap().count(), 1);
+        assert!(stmt.query(None).is_ok());
+        drop(stmt);
+        assert!(conn.prepare("XINSERT").is_err());
+
+        assert_eq!(
+            recorder.calls(),
+            vec![
+                RecordedCall::Prepare("INSERT ?".to_string()),
+                RecordedCall::Execute("INSERT ?".to_string(), params!(1)),
+                RecordedCall::Prepare("SELECT 2".to_string()),
+                RecordedCall::Query("SELECT 2".to_string(), None),
+                RecordedCall::Query("SELECT 2".to_string(), None),
+                RecordedCall::Prepare("XINSERT".to_string()),
+            ]
+        );
+        assert_eq!(recorded_calls(recorder.name()), recorder.calls());
+
+        // Each recorder has its own calls.
+        let other_recorder = RecordingMock::new();
+        assert!(other_recorder.calls().is_empty());
+        recorder.clear();
+        assert!(recorder.calls().is_empty());
+
+        // The recording is forgotten once the recorder is dropped.
+        let name = recorder.name().to_string();
+        let uri = recorder.uri();
+        drop(recorder);
+        assert!(recorded_calls(&name).is_empty());
+        assert!(Factory::open(&uri).is_err());
+
+        // A connection without recording.
+        assert!(Factory::open("mock://").unwrap().prepare("SELECT 1").is_ok());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::parameters::Parameters;
+use crate::row::Row;
+use crate::values::Value;
+use crate::Result;
+
+/// A helper to page through the result of a query using keyset pagination.
+///
+/// Unlike a pagination using `OFFSET`, each page is fetched by looking for the rows following the last key of the
+/// previous page, which guarantees that there are no gaps or duplicates between pages as long as the key is unique.
+///
+/// The pager is not bound to a connection, the rows of each page are fetched by a closure given the statement and the
+/// parameters to be executed. With a blocking connection:
+///
+/// ```rust,ignore
+/// let mut pager = KeysetPager::new("SELECT id, name FROM employee", "id", 100);
+/// while let Some(rows) = pager.next_page(|statement, parameters| conn.query_map_rows(statement, parameters, Ok))? {
+///     for row in rows {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The key of the last row is bound using a `?` placeholder, with PostgreSQL the rewriting of the placeholders must be
+/// enabled on the connection.
+pub struct KeysetPager {
+    base_query: String,
+    key_column: String,
+    page_size: usize,
+    last_key: Option<Value>,
+    done: bool,
+}
+
+impl KeysetPager {
+    /// Create a new pager.
+    ///
+    /// - `base_query`: the query to page through, it must not have an `ORDER BY` or `LIMIT` clause.
+    /// - `key_column`: the name of the column ordering the rows, it must be unique and returned by the `base_query`.
+    /// - `page_size`: the maximum number of rows in a page.
+    pub fn new<Q: Into<String>, K: Into<String>>(base_query: Q, key_column: K, page_size: usize) -> Self {
+        Self { base_query: base_query.into(), key_column: key_column.into(), page_size, last_key: None, done: false }
+    }
+
+    /// Get the key of the last row of the last page fetched.
+    pub fn last_key(&self) -> Option<&Value> {
+        self.last_key.as_ref()
+    }
+
+    /// Check if all the pages have been fetched.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Get the statement and its parameters to fetch the next page.
+    pub fn next_query(&self) -> (String, Option<Parameters>) {
+        match &self.last_key {
+            None => (
+                format!(
+                    "SELECT * FROM ({}) AS keyset_page ORDER BY {} LIMIT {}",
+                    self.base_query, self.key_column, self.page_size
+                ),
+                None,
+            ),
+            Some(last_key) => (
+                format!(
+                    "SELECT * FROM ({}) AS keyset_page WHERE {} > ? ORDER BY {} LIMIT {}",
+                    self.base_query, self.key_column, self.key_column, self.page_size
+                ),
+                Some(Parameters::Positional(vec![last_key.clone()])),
+            ),
+        }
+    }
+
+    /// Fetch the next page.
+    ///
+    /// The `fetch` closure is called with the statement and parameters returned by [KeysetPager::next_query].
+    ///
+    /// Returns `Ok(None)` once all the pages have been fetched.
+    pub fn next_page<F>(&mut self, fetch: F) -> Result<Option<Vec<Row>>>
+    where
+        F: FnOnce(&str, Option<Parameters>) -> Result<Vec<Row>>,
+    {
+        if self.done {
+            return Ok(None);
+        }
+        let (statement, parameters) = self.next_query();
+        let rows = fetch(&statement, parameters)?;
+        if rows.len() < self.page_size {
+            // A partial page is the last one, this saves a round trip to get an empty page.
+            self.done = true;
+        }
+        match rows.last() {
+            Some(row) => {
+                self.last_key = Some(row.try_get::<_, Value>(self.key_column.as_str())?);
+                Ok(Some(rows))
+            }
+            None => {
+                self.done = true;
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, RecordBatch};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    fn rows(ids: &[i32]) -> Vec<Row> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let batch = Arc::new(RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(ids.to_vec()))]).unwrap());
+        (0..ids.len()).map(|index| Row::new(batch.clone(), index)).collect()
+    }
+
+    #[test]
+    fn test_next_query() {
+        let mut pager = KeysetPager::new("SELECT id FROM t", "id", 2);
+        assert_eq!(
+            pager.next_query(),
+            ("SELECT * FROM (SELECT id FROM t) AS keyset_page ORDER BY id LIMIT 2".into(), None)
+        );
+
+        assert_eq!(pager.next_page(|_, _| Ok(rows(&[1, 2]))).unwrap().unwrap().len(), 2);
+        assert_eq!(pager.last_key(), Some(&Value::Int32(2)));
+        assert_eq!(
+            pager.next_query(),
+            (
+                "SELECT * FROM (SELECT id FROM t) AS keyset_page WHERE id > ? ORDER BY id LIMIT 2".into(),
+                Some(Parameters::Positional(vec![Value::Int32(2)]))
+            )
+        );
+
+        // A partial page is the last one.
+        assert_eq!(pager.next_page(|_, _| Ok(rows(&[3]))).unwrap().unwrap().len(), 1);
+        assert!(pager.is_done());
+        assert!(pager.next_page(|_, _| panic!("should not be called")).unwrap().is_none());
+
+        // Errors are reported.
+        let mut pager = KeysetPager::new("SELECT id FROM t", "unknown", 2);
+        assert!(pager.next_page(|_, _| Ok(rows(&[1]))).is_err());
+    }
+}
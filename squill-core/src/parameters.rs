@@ -3,6 +3,12 @@ use crate::values::{ToValue, Value};
 #[derive(Debug, PartialEq, Clone)]
 pub enum Parameters {
     Positional(Vec<Value>),
+
+    /// Parameters bound by their name.
+    ///
+    /// The name can be given with or without the prefix used by the placeholder in the statement (i.e. `id` or `:id`
+    /// for a placeholder `:id`). Named parameters are only supported by some drivers (SQLite and MySQL).
+    Named(Vec<(String, Value)>),
 }
 
 impl Parameters {
@@ -13,18 +19,31 @@ impl Parameters {
     pub fn is_empty(&self) -> bool {
         match self {
             Parameters::Positional(values) => values.is_empty(),
+            Parameters::Named(values) => values.is_empty(),
         }
     }
 
     pub fn len(&self) -> usize {
         match self {
             Parameters::Positional(values) => values.len(),
+            Parameters::Named(values) => values.len(),
         }
     }
 
     pub fn get(&self, index: usize) -> Option<&Value> {
         match self {
             Parameters::Positional(values) => values.get(index),
+            Parameters::Named(values) => values.get(index).map(|(_, value)| value),
+        }
+    }
+
+    /// Get the value of a named parameter.
+    ///
+    /// Always returns `None` for positional parameters.
+    pub fn get_named(&self, name: &str) -> Option<&Value> {
+        match self {
+            Parameters::Positional(_) => None,
+            Parameters::Named(values) => values.iter().find(|(key, _)| key == name).map(|(_, value)| value),
         }
     }
 }
@@ -58,7 +77,7 @@ mod tests {
             &u128::MAX,
             &f32::MAX,
             &f64::MAX,
-            &vec![0xde, 0xad, 0xbe, 0xef],
+            &vec![0xde_u8, 0xad, 0xbe, 0xef],
         ]);
         assert_eq!(parameters.get(0), Some(&Value::Bool(false)));
         assert_eq!(parameters.get(1), Some(&Value::Bool(true)));
@@ -79,4 +98,16 @@ mod tests {
         assert_eq!(parameters.get(16), Some(&Value::Blob(vec![0xde, 0xad, 0xbe, 0xef])));
         assert!(Parameters::from_slice(&[]).is_empty());
     }
+
+    #[test]
+    fn test_named_parameters() {
+        let parameters =
+            Parameters::Named(vec![("id".to_string(), Value::Int32(1)), (":name".to_string(), "a".into())]);
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters.get(1), Some(&Value::String("a".to_string())));
+        assert_eq!(parameters.get_named("id"), Some(&Value::Int32(1)));
+        assert_eq!(parameters.get_named("name"), None);
+        assert_eq!(Parameters::Positional(vec![Value::Int32(1)]).get_named("id"), None);
+        assert!(Parameters::Named(vec![]).is_empty());
+    }
 }
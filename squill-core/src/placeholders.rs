@@ -0,0 +1,378 @@
+use crate::parameters::Parameters;
+use crate::values::Value;
+
+/// The style of placeholders expected by a driver to bind the parameters of a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// Anonymous positional placeholders: `?` (SQLite, DuckDB, MySQL).
+    QuestionMark,
+
+    /// Numbered positional placeholders: `$1`, `$2`, ... (PostgreSQL).
+    Dollar,
+}
+
+impl PlaceholderStyle {
+    /// Get the placeholder style expected by a driver given its name.
+    pub fn for_driver(driver_name: &str) -> Self {
+        match driver_name {
+            "postgres" => PlaceholderStyle::Dollar,
+            _ => PlaceholderStyle::QuestionMark,
+        }
+    }
+}
+
+/// Rewrite the `?` placeholders of a statement into the given placeholder style.
+///
+/// The `?` found inside quoted literals (`'...'`, `"..."`, `` `...` ``, `E'...'`, `$$...$$`) and comments (`-- ...`,
+/// `/* ... */`) are left untouched, and so are the PostgreSQL `jsonb` operators `?|` and `?&`. The `?` of a cast is a
+/// placeholder: `?::INT` is rewritten as `$1::INT` for PostgreSQL. The `jsonb` operator `?` cannot be told apart from a
+/// placeholder, it is rewritten as well.
+///
+/// ```rust
+/// use squill_core::placeholders::{rewrite, PlaceholderStyle};
+///
+/// assert_eq!(
+///     rewrite("SELECT * FROM t WHERE a = ? AND b = '?'", PlaceholderStyle::Dollar),
+///     "SELECT * FROM t WHERE a = $1 AND b = '?'"
+/// );
+/// ```
+pub fn rewrite(statement: &str, style: PlaceholderStyle) -> String {
+    match style {
+        PlaceholderStyle::QuestionMark => statement.to_string(),
+        PlaceholderStyle::Dollar => {
+            let mut result = String::with_capacity(statement.len() + 8);
+            let mut last = 0;
+            for (position, offset) in question_marks(statement).into_iter().enumerate() {
+                result.push_str(&statement[last..offset]);
+                result.push_str(&format!("${}", position + 1));
+                last = offset + 1;
+            }
+            result.push_str(&statement[last..]);
+            result
+        }
+    }
+}
+
+/// Expand the `?` placeholders bound to a [Value::List] into as many placeholders as there are values in the list.
+///
+/// This allows to bind a list of values to an `IN (?)` clause regardless of the support of arrays by the driver. The
+/// values of the list are still bound as parameters, there is no risk of SQL injection. An empty list is replaced by
+/// `NULL` so `IN (?)` becomes `IN (NULL)` which matches no rows.
+///
+/// The placeholders must be `?`, the expansion must be done before a [rewrite].
+///
+/// ```rust
+/// use squill_core::params;
+/// use squill_core::placeholders::expand_lists;
+///
+/// let (statement, parameters) = expand_lists("DELETE FROM t WHERE a = ? AND id IN (?)", params!(1, vec![2, 3, 4]));
+/// assert_eq!(statement, "DELETE FROM t WHERE a = ? AND id IN (?, ?, ?)");
+/// assert_eq!(parameters, params!(1, 2, 3, 4));
+/// ```
+pub fn expand_lists(statement: &str, parameters: Option<Parameters>) -> (String, Option<Parameters>) {
+    let values = match parameters {
+        Some(Parameters::Positional(values)) if values.iter().any(|value| matches!(value, Value::List(_))) => values,
+        _ => return (statement.to_string(), parameters),
+    };
+    let mut result = String::with_capacity(statement.len() + 16);
+    let mut expanded_values = Vec::with_capacity(values.len());
+    let mut last = 0;
+    // If the number of placeholders doesn't match the number of values we just leave the extra values unchanged and
+    // let the driver report the invalid parameter count.
+    let offsets = question_marks(statement);
+    for (position, value) in values.into_iter().enumerate() {
+        match (value, offsets.get(position)) {
+            (Value::List(list), Some(&offset)) => {
+                result.push_str(&statement[last..offset]);
+                if list.is_empty() {
+                    result.push_str("NULL");
+                } else {
+                    result.push_str(&vec!["?"; list.len()].join(", "));
+                }
+                last = offset + 1;
+                expanded_values.extend(list);
+            }
+            (value, _) => expanded_values.push(value),
+        }
+    }
+    result.push_str(&statement[last..]);
+    (result, Some(Parameters::Positional(expanded_values)))
+}
+
+/// Wrap the first `?` placeholders of a statement into casts to the given SQL types.
+///
+/// This gives the types of the parameters that cannot be inferred by a driver (i.e. `SELECT ? + ?`). If there are
+/// fewer types than placeholders, the remaining placeholders are left untouched.
+///
+/// ```rust
+/// use squill_core::placeholders::cast;
+///
+/// assert_eq!(cast("SELECT ? + ?, ?", &["BIGINT", "INTEGER"]), "SELECT CAST(? AS BIGINT) + CAST(? AS INTEGER), ?");
+/// ```
+pub fn cast<S: AsRef<str>>(statement: &str, types: &[S]) -> String {
+    let mut result = String::with_capacity(statement.len() + 16 * types.len());
+    let mut last = 0;
+    for (offset, type_name) in question_marks(statement).into_iter().zip(types) {
+        result.push_str(&statement[last..offset]);
+        result.push_str(&format!("CAST(? AS {})", type_name.as_ref()));
+        last = offset + 1;
+    }
+    result.push_str(&statement[last..]);
+    result
+}
+
+/// A placeholder found in a statement by [placeholders].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    /// The name of a `:name` placeholder, the number of a `$N` placeholder or `None` for a `?` placeholder.
+    pub name: Option<String>,
+
+    /// The offset in bytes of the placeholder in the statement.
+    pub byte_offset: usize,
+}
+
+/// Get the placeholders of a statement in the order they appear.
+///
+/// The placeholders can be anonymous (`?`), numbered (`$1`) or named (`:name`), regardless of the style supported by
+/// the driver. As for [rewrite], the placeholders inside quoted literals and comments are ignored, and so are the
+/// `jsonb` operators `?|` and `?&`. The `::` of a cast (i.e. `?::INT` or `:name::INT`) is not a named placeholder.
+///
+/// ```rust
+/// use squill_core::placeholders::{placeholders, Placeholder};
+///
+/// assert_eq!(
+///     placeholders("SELECT * FROM t WHERE a = ? AND b = :b AND c = '?'"),
+///     vec![
+///         Placeholder { name: None, byte_offset: 26 },
+///         Placeholder { name: Some("b".to_string()), byte_offset: 36 },
+///     ]
+/// );
+/// ```
+pub fn placeholders(statement: &str) -> Vec<Placeholder> {
+    let bytes = statement.as_bytes();
+    let is_identifier = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let mut placeholders = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_literal_or_comment(bytes, i) {
+            i = end + 1;
+            continue;
+        }
+        match bytes[i] {
+            b'?' if is_jsonb_operator(bytes, i) => i += 1,
+            b'?' => placeholders.push(Placeholder { name: None, byte_offset: i }),
+            // A cast such as `?::INT` or `:name::INT`.
+            b':' if bytes.get(i + 1) == Some(&b':') => i += 1,
+            prefix @ (b'$' | b':') if i == 0 || !is_identifier(bytes[i - 1]) => {
+                let end = (i + 1..bytes.len()).find(|&end| !is_identifier(bytes[end])).unwrap_or(bytes.len());
+                let name = &statement[i + 1..end];
+                let valid = match prefix {
+                    b'$' => !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_digit()),
+                    _ => name.bytes().next().is_some_and(|byte| byte.is_ascii_alphabetic() || byte == b'_'),
+                };
+                if valid {
+                    placeholders.push(Placeholder { name: Some(name.to_string()), byte_offset: i });
+                    i = end - 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    placeholders
+}
+
+/// Get the byte offsets of the `?` placeholders of a statement.
+pub(crate) fn question_marks(statement: &str) -> Vec<usize> {
+    let bytes = statement.as_bytes();
+    let mut offsets = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match skip_literal_or_comment(bytes, i) {
+            Some(end) => i = end,
+            None if bytes[i] == b'?' && !is_jsonb_operator(bytes, i) => offsets.push(i),
+            None => {}
+        }
+        i += 1;
+    }
+    offsets
+}
+
+/// Check if the `?` at the given offset is one of the PostgreSQL `jsonb` operators `?|` or `?&` rather than a
+/// placeholder (a placeholder can still be followed by the `||` and `&&` operators).
+fn is_jsonb_operator(bytes: &[u8], offset: usize) -> bool {
+    match bytes.get(offset + 1) {
+        Some(&operator @ (b'|' | b'&')) => bytes.get(offset + 2) != Some(&operator),
+        _ => false,
+    }
+}
+
+/// Get the offset of the last byte of the quoted literal or the comment starting at the given offset of a statement
+/// (`None` if there is none).
+///
+/// A doubled quote inside a literal (`'it''s'`) is treated as the end of the literal immediately followed by a new one
+/// which gives the same result as handling the escape. The PostgreSQL escape strings (`E'it\'s'`) and dollar-quoted
+/// strings (`$$...$$` or `$tag$...$tag$`) are supported as well. The offset is past the end of the statement if the
+/// literal or the comment is not terminated.
+///
+/// ```rust
+/// use squill_core::placeholders::skip_literal_or_comment;
+///
+/// let statement = "SELECT 'a?' -- b?";
+/// assert_eq!(skip_literal_or_comment(statement.as_bytes(), 7), Some(10));
+/// assert_eq!(skip_literal_or_comment(statement.as_bytes(), 12), Some(17));
+/// assert_eq!(skip_literal_or_comment(statement.as_bytes(), 0), None);
+/// ```
+pub fn skip_literal_or_comment(bytes: &[u8], start: usize) -> Option<usize> {
+    let is_identifier = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let after_identifier = start > 0 && is_identifier(bytes[start - 1]);
+    let mut i = start;
+    match bytes[i] {
+        b'E' | b'e' if bytes.get(i + 1) == Some(&b'\'') && !after_identifier => {
+            i += 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\\' => i += 2,
+                    b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 2,
+                    b'\'' => break,
+                    _ => i += 1,
+                }
+            }
+        }
+        // The tag of a dollar-quoted string cannot start with a digit (`$1` is a placeholder).
+        b'$' if !after_identifier && !bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+            let tag_end = (i + 1..bytes.len()).find(|&end| !is_identifier(bytes[end]))?;
+            if bytes[tag_end] != b'$' {
+                return None;
+            }
+            let delimiter = &bytes[i..=tag_end];
+            i = tag_end + 1;
+            while i < bytes.len() && !bytes[i..].starts_with(delimiter) {
+                i += 1;
+            }
+            i += delimiter.len() - 1;
+        }
+        quote @ (b'\'' | b'"' | b'`') => {
+            i += 1;
+            while i < bytes.len() && bytes[i] != quote {
+                i += 1;
+            }
+        }
+        b'-' if bytes.get(i + 1) == Some(&b'-') => {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        }
+        b'/' if bytes.get(i + 1) == Some(&b'*') => {
+            i += 2;
+            while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                i += 1;
+            }
+            i += 1;
+        }
+        _ => return None,
+    }
+    Some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite() {
+        let dollar = PlaceholderStyle::Dollar;
+        assert_eq!(rewrite("INSERT INTO t (a, b) VALUES (?, ?)", dollar), "INSERT INTO t (a, b) VALUES ($1, $2)");
+        assert_eq!(rewrite("SELECT ?::INT, ?::TEXT", dollar), "SELECT $1::INT, $2::TEXT");
+        assert_eq!(rewrite("SELECT '?', \"?\", ?", dollar), "SELECT '?', \"?\", $1");
+        assert_eq!(rewrite("SELECT 'it''s ?', ?", dollar), "SELECT 'it''s ?', $1");
+        assert_eq!(rewrite("SELECT ? -- why?\n, ?", dollar), "SELECT $1 -- why?\n, $2");
+        assert_eq!(rewrite("SELECT /* ? */ ?", dollar), "SELECT /* ? */ $1");
+        assert_eq!(rewrite("SELECT 'unterminated ?", dollar), "SELECT 'unterminated ?");
+        assert_eq!(rewrite("SELECT 1", dollar), "SELECT 1");
+        assert_eq!(rewrite("SELECT ?, 'é', ?", dollar), "SELECT $1, 'é', $2");
+
+        // PostgreSQL escape strings and dollar-quoted strings.
+        assert_eq!(rewrite(r"SELECT E'it\'s ?', ?", dollar), r"SELECT E'it\'s ?', $1");
+        assert_eq!(rewrite(r"SELECT e'\\', ?", dollar), r"SELECT e'\\', $1");
+        assert_eq!(rewrite("SELECT name'?', ?", dollar), "SELECT name'?', $1");
+        assert_eq!(rewrite("SELECT $$it's ?$$, ?", dollar), "SELECT $$it's ?$$, $1");
+        assert_eq!(rewrite("SELECT $fn$ $$?$$ $fn$, ?", dollar), "SELECT $fn$ $$?$$ $fn$, $1");
+        assert_eq!(rewrite("SELECT $$unterminated ?", dollar), "SELECT $$unterminated ?");
+        assert_eq!(rewrite("SELECT a$b$, ?, $x", dollar), "SELECT a$b$, $1, $x");
+
+        // The `jsonb` operators `?|` and `?&`, unlike a placeholder followed by `||`.
+        assert_eq!(rewrite("SELECT data ?| array['a'], ?", dollar), "SELECT data ?| array['a'], $1");
+        assert_eq!(rewrite("SELECT data ?& ?", dollar), "SELECT data ?& $1");
+        assert_eq!(rewrite("SELECT ?||'a', ?&&?", dollar), "SELECT $1||'a', $2&&$3");
+
+        // Drivers using `?` are left untouched.
+        assert_eq!(rewrite("SELECT ?, ?", PlaceholderStyle::QuestionMark), "SELECT ?, ?");
+    }
+
+    #[test]
+    fn test_expand_lists() {
+        use crate::params;
+
+        assert_eq!(
+            expand_lists("SELECT * FROM t WHERE id IN (?) AND a = ?", params!(vec![1, 2], "?")),
+            ("SELECT * FROM t WHERE id IN (?, ?) AND a = ?".to_string(), params!(1, 2, "?"))
+        );
+        assert_eq!(
+            expand_lists("SELECT '?' FROM t WHERE id IN (?)", params!(vec!["a"])),
+            ("SELECT '?' FROM t WHERE id IN (?)".to_string(), params!("a"))
+        );
+        assert_eq!(
+            expand_lists("SELECT * FROM t WHERE id IN (?)", params!(Vec::<i32>::new())),
+            ("SELECT * FROM t WHERE id IN (NULL)".to_string(), Some(Parameters::Positional(vec![])))
+        );
+
+        // Nothing to expand.
+        assert_eq!(expand_lists("SELECT ?", params!(1)), ("SELECT ?".to_string(), params!(1)));
+        assert_eq!(expand_lists("SELECT 1", None), ("SELECT 1".to_string(), None));
+    }
+
+    #[test]
+    fn test_cast() {
+        assert_eq!(cast("SELECT ? + ?", &["BIGINT", "BIGINT"]), "SELECT CAST(? AS BIGINT) + CAST(? AS BIGINT)");
+        // The placeholders in literals and comments are ignored, the extra types too.
+        assert_eq!(cast("SELECT '?', ? -- ?", &["VARCHAR", "INTEGER"]), "SELECT '?', CAST(? AS VARCHAR) -- ?");
+        assert_eq!(cast("SELECT ?", &[] as &[&str]), "SELECT ?");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let placeholder =
+            |name: Option<&str>, byte_offset: usize| Placeholder { name: name.map(String::from), byte_offset };
+        let statement = "SELECT ?, $2, :name, '?' || ':text' || '$1' -- ? :comment\n FROM t /* $3 */ WHERE a = :a_1";
+        assert_eq!(
+            placeholders(statement),
+            vec![
+                placeholder(None, 7),
+                placeholder(Some("2"), 10),
+                placeholder(Some("name"), 14),
+                placeholder(Some("a_1"), 85)
+            ]
+        );
+
+        // The casts, the identifiers and the escaped quotes are not placeholders.
+        assert_eq!(placeholders("SELECT ?::INT, :id::TEXT"), vec![placeholder(None, 7), placeholder(Some("id"), 15)]);
+        assert_eq!(placeholders("SELECT a$1, b:c, $, :, :1, $x FROM t"), vec![]);
+        assert_eq!(placeholders("SELECT 'it''s ?', \"?\", `?`, ?"), vec![placeholder(None, 28)]);
+        assert_eq!(placeholders("SELECT 'é', :é, ?"), vec![placeholder(None, 18)]);
+        assert_eq!(placeholders("SELECT 'unterminated ?"), vec![]);
+        assert_eq!(placeholders(":start"), vec![placeholder(Some("start"), 0)]);
+
+        // The bodies of the dollar-quoted strings (i.e. a function) and the `jsonb` operators are ignored.
+        let statement = "CREATE FUNCTION f() AS $$ SELECT ?, :a, $1 $$; SELECT $body$ :b $body$, data ?| :keys, $1";
+        assert_eq!(placeholders(statement), vec![placeholder(Some("keys"), 80), placeholder(Some("1"), 87)]);
+        assert_eq!(placeholders(r"SELECT E'\' :a', :b"), vec![placeholder(Some("b"), 17)]);
+    }
+
+    #[test]
+    fn test_for_driver() {
+        assert_eq!(PlaceholderStyle::for_driver("postgres"), PlaceholderStyle::Dollar);
+        assert_eq!(PlaceholderStyle::for_driver("sqlite"), PlaceholderStyle::QuestionMark);
+        assert_eq!(PlaceholderStyle::for_driver("mock"), PlaceholderStyle::QuestionMark);
+    }
+}
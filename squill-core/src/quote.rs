@@ -0,0 +1,130 @@
+use crate::{Error, Result};
+
+/// Quote an identifier (i.e. the name of a table or a column) so it can be embedded in a statement.
+///
+/// The identifier is enclosed in backticks for MySQL and in double quotes for the other drivers, the quote character
+/// itself is escaped by doubling it.
+///
+/// ```rust
+/// use squill_core::quote::quote_identifier;
+///
+/// assert_eq!(quote_identifier("sqlite", r#"my "table""#), r#""my ""table""""#);
+/// assert_eq!(quote_identifier("mysql", "my `table`"), "`my ``table```");
+/// ```
+pub fn quote_identifier(driver_name: &str, identifier: &str) -> String {
+    let quote = match driver_name {
+        "mysql" => '`',
+        _ => '"',
+    };
+    let mut quoted = String::with_capacity(identifier.len() + 2);
+    quoted.push(quote);
+    for c in identifier.chars() {
+        if c == quote {
+            quoted.push(quote);
+        }
+        quoted.push(c);
+    }
+    quoted.push(quote);
+    quoted
+}
+
+/// Quote a string literal so it can be embedded in a statement.
+///
+/// The string is enclosed in single quotes and the single quotes are escaped by doubling them. With MySQL, the
+/// backslashes are escaped as well since they are an escape character in a string literal by default.
+///
+/// Binding a parameter should always be preferred over embedding a literal, this is intended for the statements that
+/// don't support parameters (e.g. `ATTACH`, `COPY`).
+///
+/// ```rust
+/// use squill_core::quote::quote_literal;
+///
+/// assert_eq!(quote_literal("sqlite", "it's"), "'it''s'");
+/// assert_eq!(quote_literal("mysql", r"C:\temp"), r"'C:\\temp'");
+/// ```
+pub fn quote_literal(driver_name: &str, value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("''"),
+            '\\' if driver_name == "mysql" => quoted.push_str("\\\\"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Check that a name is a plain identifier so it can be embedded in a statement without being quoted.
+///
+/// Some names cannot be quoted (i.e. the name of a SQLite pragma or of a DuckDB setting), they are only accepted if
+/// they start with a letter or an underscore followed by letters, digits or underscores.
+///
+/// ```rust
+/// use squill_core::quote::check_identifier;
+///
+/// assert!(check_identifier("cache_size").is_ok());
+/// assert!(check_identifier("cache_size; DROP TABLE employee").is_err());
+/// ```
+pub fn check_identifier(name: &str) -> Result<()> {
+    let invalid = |reason: String, offset: usize| Error::InputError {
+        message: reason,
+        input: name.to_string(),
+        offset,
+        error: Box::new(Error::InvalidType { expected: "identifier".to_string(), actual: name.to_string() }),
+    };
+    if name.is_empty() {
+        return Err(invalid("Empty identifier".to_string(), 0));
+    }
+    for (offset, c) in name.char_indices() {
+        if offset == 0 && !(c.is_ascii_alphabetic() || c == '_') {
+            return Err(invalid(format!("The identifier must start with a letter or '_', found '{}'", c), offset));
+        }
+        if !(c.is_ascii_alphanumeric() || c == '_') {
+            return Err(invalid(format!("Invalid character '{}' in the identifier", c), offset));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("sqlite", "employee"), r#""employee""#);
+        assert_eq!(quote_identifier("sqlite", r#"a"b"#), r#""a""b""#);
+        assert_eq!(quote_identifier("sqlite", "a`b"), r#""a`b""#);
+        assert_eq!(quote_identifier("postgres", r#""""#), r#""""""""#);
+        assert_eq!(quote_identifier("mysql", "a`b"), "`a``b`");
+        assert_eq!(quote_identifier("mysql", r#"a"b"#), r#"`a"b`"#);
+    }
+
+    #[test]
+    fn test_quote_literal() {
+        assert_eq!(quote_literal("sqlite", ""), "''");
+        assert_eq!(quote_literal("sqlite", "'; DROP TABLE employee; --"), "'''; DROP TABLE employee; --'");
+        assert_eq!(quote_literal("postgres", r"a\b"), r"'a\b'");
+        assert_eq!(quote_literal("mysql", r"a\'b"), r"'a\\''b'");
+    }
+
+    #[test]
+    fn test_check_identifier() {
+        assert!(check_identifier("cache_size").is_ok());
+        assert!(check_identifier("_x1").is_ok());
+        assert!(matches!(check_identifier(""), Err(Error::InputError { offset: 0, .. })));
+        assert!(matches!(check_identifier("1x"), Err(Error::InputError { offset: 0, .. })));
+        assert!(matches!(check_identifier("threads=1"), Err(Error::InputError { offset: 7, .. })));
+        assert!(matches!(check_identifier("a b"), Err(Error::InputError { offset: 1, .. })));
+        match check_identifier("a;b") {
+            Err(Error::InputError { error, .. }) => {
+                assert!(
+                    matches!(error.downcast_ref::<Error>(), Some(Error::InvalidType { actual, .. }) if actual == "a;b")
+                )
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::decode;
+use crate::values::Value;
 use crate::{decode::Decode, Error, Result};
-use arrow_array::RecordBatch;
+use arrow_array::{Array, RecordBatch};
 use arrow_schema::SchemaRef;
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::sync::Arc;
 
 /// A row returned by a query.
@@ -24,6 +27,19 @@ impl Row {
         self.record_batch.schema()
     }
 
+    /// Get the record batch the row belongs to.
+    ///
+    /// This gives access to the Arrow arrays of the columns for vectorized processing or interop with other Arrow code
+    /// without copying the data.
+    pub fn record_batch(&self) -> &RecordBatch {
+        &self.record_batch
+    }
+
+    /// Get the index of the row in its record batch.
+    pub fn row_index(&self) -> usize {
+        self.index_in_batch
+    }
+
     /// Get the number of columns in the row.
     pub fn num_columns(&self) -> usize {
         self.record_batch.num_columns()
@@ -48,20 +64,15 @@ impl Row {
     /// Panics if the column index is out of bounds (`usize` index) or not found (`&str` index) or if the type is not
     /// the expected one.
     pub fn get<I: ColumnIndex, T: Decode>(&self, index: I) -> T {
-        match index.index(self.record_batch.schema()) {
-            Ok(index) => T::decode(self.record_batch.column(index), self.index_in_batch),
+        match self.try_get(index) {
+            Ok(value) => value,
             Err(e) => panic!("{}", e),
         }
     }
 
     pub fn get_nullable<I: ColumnIndex, T: Decode>(&self, index: I) -> Option<T> {
-        match index.index(self.record_batch.schema()) {
-            Ok(index) => {
-                if decode::is_null(self.record_batch.column(index), self.index_in_batch) {
-                    return None;
-                }
-                Some(T::decode(self.record_batch.column(index), self.index_in_batch))
-            }
+        match self.try_get_nullable(index) {
+            Ok(value) => value,
             Err(e) => panic!("{}", e),
         }
     }
@@ -71,9 +82,12 @@ impl Row {
     /// The index of the column can be either a 0-based index or the name of the column.
     /// This method returns an error if the column index is out of bounds, or if the type is not the expected one, or if
     /// the value is null.
+    ///
+    /// An error raised while decoding the value is wrapped into an [Error::Column] giving the name and the index of the
+    /// column.
     pub fn try_get<I: ColumnIndex, T: Decode>(&self, index: I) -> Result<T> {
         let index = index.index(self.record_batch.schema())?;
-        T::try_decode(self.record_batch.column(index), self.index_in_batch)
+        T::try_decode(self.record_batch.column(index), self.index_in_batch).map_err(|e| self.column_error(index, e))
     }
 
     pub fn try_get_nullable<I: ColumnIndex, T: Decode>(&self, index: I) -> Result<Option<T>> {
@@ -81,7 +95,81 @@ impl Row {
         if decode::is_null(self.record_batch.column(index), self.index_in_batch) {
             return Ok(None);
         }
-        Ok(Some(T::try_decode(self.record_batch.column(index), self.index_in_batch)?))
+        Ok(Some(
+            T::try_decode(self.record_batch.column(index), self.index_in_batch)
+                .map_err(|e| self.column_error(index, e))?,
+        ))
+    }
+
+    /// Get a value from a column using a custom decoding function.
+    ///
+    /// This allows to decode a column into a type that doesn't implement [Decode] (i.e. an Arrow extension type). The
+    /// function is given the array of the column and the index of the row in the array, it is not called if the
+    /// column is not found. As with [Row::try_get], the error returned by the function is wrapped into an
+    /// [Error::Column].
+    ///
+    /// ```rust,ignore
+    /// let len = row.get_with("name", |array, index| Ok(String::try_decode(array, index)?.len()))?;
+    /// ```
+    pub fn get_with<I: ColumnIndex, F, T>(&self, index: I, decode_fn: F) -> Result<T>
+    where
+        F: FnOnce(&dyn Array, usize) -> Result<T>,
+    {
+        let index = index.index(self.record_batch.schema())?;
+        decode_fn(self.record_batch.column(index).as_ref(), self.index_in_batch)
+            .map_err(|e| self.column_error(index, e))
+    }
+
+    /// Iterate over the columns of the row as `(name, value)` pairs, in the order of the columns.
+    ///
+    /// The null values are returned as [Value::Null].
+    ///
+    /// # Panics
+    /// Panics if a column has a type that cannot be decoded into a [Value].
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Value)> + '_ {
+        self.record_batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| (field.name().as_str(), self.get::<_, Value>(index)))
+    }
+
+    /// Convert the row into a JSON object using the names of the columns as keys.
+    ///
+    /// See [Value::to_json] for the representation of the values.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        let schema = self.record_batch.schema();
+        let mut object = serde_json::Map::with_capacity(schema.fields().len());
+        for (index, field) in schema.fields().iter().enumerate() {
+            object.insert(field.name().to_string(), self.try_get::<_, Value>(index)?.to_json());
+        }
+        Ok(serde_json::Value::Object(object))
+    }
+
+    /// Check if the values of the row are equal to the expected values.
+    ///
+    /// The row must have as many columns as there are expected values, and each column decoded into a [Value] must be
+    /// equal to the expected value of the same position (see [Value::eq], values of different types are never equal).
+    /// A column that cannot be decoded into a [Value] is never equal. This is mostly intended for tests:
+    ///
+    /// ```rust,ignore
+    /// assert!(row.values_eq(&[Value::Int32(1), Value::from("Alice")]));
+    /// ```
+    pub fn values_eq(&self, expected: &[Value]) -> bool {
+        self.num_columns() == expected.len()
+            && expected
+                .iter()
+                .enumerate()
+                .all(|(index, expected)| matches!(self.try_get::<_, Value>(index), Ok(value) if value == *expected))
+    }
+
+    fn column_error(&self, index: usize, error: Error) -> Error {
+        Error::Column {
+            name: self.record_batch.schema().field(index).name().to_string(),
+            index,
+            source: Box::new(error),
+        }
     }
 }
 
@@ -108,3 +196,236 @@ impl ColumnIndex for &str {
         }
     }
 }
+
+/// A trait to collect the rows of a key/value result set (i.e. `SELECT key, value FROM config`) into a [HashMap].
+///
+/// It is implemented by all the iterators over rows, such as the rows returned by a query.
+///
+/// ```rust,ignore
+/// use squill_core::row::IntoMap;
+///
+/// let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+/// let settings: HashMap<String, i64> = stmt.query_rows(None)?.into_map()?;
+/// ```
+pub trait IntoMap: Iterator<Item = Result<Row>> + Sized {
+    /// Collect the rows into a [HashMap] by decoding the keys from the first column and the values from the second one.
+    ///
+    /// If the same key is found in several rows, the value of the last row wins. An error is returned if a row cannot
+    /// be fetched or if a key or a value cannot be decoded (including when it is null).
+    fn into_map<K, V>(self) -> Result<HashMap<K, V>>
+    where
+        K: Decode + Eq + Hash,
+        V: Decode,
+    {
+        let mut map = HashMap::new();
+        for row in self {
+            let row = row?;
+            map.insert(try_get_non_null(&row, 0)?, try_get_non_null(&row, 1)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Get the value of a column that is not expected to be null.
+fn try_get_non_null<T: Decode>(row: &Row, index: usize) -> Result<T> {
+    match row.try_get_nullable(index)? {
+        Some(value) => Ok(value),
+        None => Err(row.column_error(
+            index,
+            Error::InvalidType { expected: "non-null value".to_string(), actual: "null".to_string() },
+        )),
+    }
+}
+
+impl<I: Iterator<Item = Result<Row>>> IntoMap for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::error::Error as _;
+
+    fn row() -> Row {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int32Array::from(vec![1])), Arc::new(StringArray::from(vec![Some("Alice")]))],
+        )
+        .unwrap();
+        Row::new(Arc::new(batch), 0)
+    }
+
+    #[test]
+    fn test_iter() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int32Array::from(vec![1, 2])), Arc::new(StringArray::from(vec![Some("Alice"), None]))],
+        )
+        .unwrap();
+        let batch = Arc::new(batch);
+        let columns = |row: Row| row.iter().map(|(name, value)| (name.to_string(), value)).collect::<Vec<_>>();
+        assert_eq!(
+            columns(Row::new(batch.clone(), 0)),
+            vec![("id".to_string(), Value::Int32(1)), ("name".to_string(), Value::String("Alice".to_string()))]
+        );
+        assert_eq!(
+            columns(Row::new(batch, 1)),
+            vec![("id".to_string(), Value::Int32(2)), ("name".to_string(), Value::Null)]
+        );
+    }
+
+    #[test]
+    fn test_values_eq() {
+        let row = row();
+        assert!(row.values_eq(&[Value::Int32(1), Value::from("Alice")]));
+        assert!(!row.values_eq(&[Value::Int32(2), Value::from("Alice")]));
+        assert!(!row.values_eq(&[Value::Int64(1), Value::from("Alice")]));
+        assert!(!row.values_eq(&[Value::Int32(1), Value::Null]));
+        assert!(!row.values_eq(&[Value::Int32(1)]));
+        assert!(!row.values_eq(&[Value::Int32(1), Value::from("Alice"), Value::Null]));
+
+        let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, true)]));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(StringArray::from(vec![None::<&str>]))]).unwrap();
+        let row = Row::new(Arc::new(batch), 0);
+        assert!(row.values_eq(&[Value::Null]));
+        assert!(!row.values_eq(&[Value::from("")]));
+    }
+
+    #[test]
+    fn test_decode_error_context() {
+        let row = row();
+        let error = row.try_get::<_, String>("id").unwrap_err();
+        assert!(
+            matches!(&error, Error::Column { name, index: 0, source } if name == "id" && matches!(**source, Error::InvalidType { .. }))
+        );
+        assert!(error.to_string().starts_with("Column 'id' (index 0): "));
+        assert!(error.source().is_some());
+
+        let error = row.try_get_nullable::<_, i64>(1).unwrap_err();
+        assert!(matches!(error, Error::Column { name, index: 1, .. } if name == "name"));
+
+        // Errors resolving the column are not wrapped.
+        assert!(matches!(row.try_get::<_, i32>(2), Err(Error::OutOfBounds { index: 2 })));
+        assert!(matches!(row.try_get::<_, i32>("unknown"), Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn test_get_with() {
+        let row = row();
+        let decode_len = |array: &dyn Array, index: usize| Ok(String::try_decode(array, index)?.len());
+        assert_eq!(row.get_with("name", decode_len).unwrap(), 5);
+        assert_eq!(row.get_with(0, |array, index| Ok(i32::try_decode(array, index)? * 10)).unwrap(), 10);
+
+        // The errors of the function are wrapped, the function is not called if the column is not found.
+        assert!(matches!(
+            row.get_with::<_, _, usize>(0, |_, _| Err(Error::NotFound)),
+            Err(Error::Column { index: 0, .. })
+        ));
+        assert!(matches!(
+            row.get_with::<_, _, usize>("unknown", |_, _| panic!("should not be called")),
+            Err(Error::NotFound)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 'name' (index 1)")]
+    fn test_get_panics_with_column_context() {
+        row().get::<_, i32>("name");
+    }
+
+    #[test]
+    fn test_into_map() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("key", DataType::Utf8, false),
+            Field::new("value", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b", "a", "c"])),
+                Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3), None])),
+            ],
+        )
+        .unwrap();
+        let batch = Arc::new(batch);
+        let rows = |len: usize| (0..len).map(|index| Ok(Row::new(batch.clone(), index))).collect::<Vec<_>>();
+
+        // The last value wins for a duplicate key.
+        let map: HashMap<String, i32> = rows(3).into_iter().into_map().unwrap();
+        assert_eq!(map, HashMap::from([("a".to_string(), 3), ("b".to_string(), 2)]));
+        let map: HashMap<String, i32> = rows(0).into_iter().into_map().unwrap();
+        assert!(map.is_empty());
+
+        // A null value or a key of the wrong type cannot be decoded.
+        assert!(matches!(rows(4).into_iter().into_map::<String, i32>(), Err(Error::Column { index: 1, .. })));
+        assert!(matches!(rows(1).into_iter().into_map::<i32, i32>(), Err(Error::Column { index: 0, .. })));
+
+        // The errors of the rows are propagated.
+        let error = std::iter::once(Err(Error::NotFound)).into_map::<String, i32>();
+        assert!(matches!(error, Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn test_nullable_hand_written_decoders() {
+        use arrow_array::{BooleanArray, Decimal128Array, FixedSizeBinaryArray, NullArray, TimestampMicrosecondArray};
+        use chrono::{DateTime, Utc};
+        use rust_decimal::Decimal;
+
+        // A typed column with a null value and a column of nulls (i.e. a SQLite column with only NULL values).
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("bool", DataType::Boolean, true),
+            Field::new("uuid", DataType::FixedSizeBinary(16), true),
+            Field::new("decimal", DataType::Decimal128(10, 2), true),
+            Field::new("datetime", DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, None), true),
+            Field::new("null", DataType::Null, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(BooleanArray::from(vec![None::<bool>])),
+                Arc::new(
+                    FixedSizeBinaryArray::try_from_sparse_iter_with_size(std::iter::once(None::<[u8; 16]>), 16)
+                        .unwrap(),
+                ),
+                Arc::new(Decimal128Array::from(vec![None::<i128>]).with_precision_and_scale(10, 2).unwrap()),
+                Arc::new(TimestampMicrosecondArray::from(vec![None::<i64>])),
+                Arc::new(NullArray::new(1)),
+            ],
+        )
+        .unwrap();
+        let row = Row::new(Arc::new(batch), 0);
+
+        for column in ["bool", "null"] {
+            assert_eq!(row.try_get_nullable::<_, bool>(column).unwrap(), None);
+            assert_eq!(row.get_nullable::<_, bool>(column), None);
+        }
+        for column in ["uuid", "null"] {
+            assert_eq!(row.try_get_nullable::<_, uuid::Uuid>(column).unwrap(), None);
+        }
+        for column in ["decimal", "null"] {
+            assert_eq!(row.try_get_nullable::<_, Decimal>(column).unwrap(), None);
+        }
+        for column in ["datetime", "null"] {
+            assert_eq!(row.try_get_nullable::<_, DateTime<Utc>>(column).unwrap(), None);
+        }
+
+        // Without the nullable accessors, the null values are reported as such.
+        let is_null_error = |error: Error| match error {
+            Error::Column { source, .. } => matches!(*source, Error::InvalidType { actual, .. } if actual == "null"),
+            _ => false,
+        };
+        assert!(is_null_error(row.try_get::<_, bool>("bool").unwrap_err()));
+        assert!(is_null_error(row.try_get::<_, bool>("null").unwrap_err()));
+        assert!(is_null_error(row.try_get::<_, uuid::Uuid>("uuid").unwrap_err()));
+        assert!(is_null_error(row.try_get::<_, Decimal>("decimal").unwrap_err()));
+        assert!(is_null_error(row.try_get::<_, DateTime<Utc>>("datetime").unwrap_err()));
+    }
+}
@@ -0,0 +1,183 @@
+//! The implementation of `Serialize` and `Deserialize` for [Value] (only available with the `serde` feature).
+//!
+//! The values are serialized into their natural representation, which is their JSON representation (see
+//! [Value::to_json]) so both give the same JSON document:
+//! - the booleans, numbers and strings are serialized as is (except the 128-bit integers that don't fit in 64 bits
+//!   which are serialized as strings),
+//! - the blobs are serialized as a base64 string,
+//! - the dates, times and timestamps are serialized as ISO-8601 strings,
+//! - the decimals, intervals and UUIDs are serialized as strings,
+//! - the JSON values are serialized as the JSON document they contain,
+//! - the lists are serialized as sequences.
+//!
+//! Because most formats (i.e. JSON) don't keep the type of the values, the deserialization is only symmetric for the
+//! types that have a natural representation: the integers are deserialized as [Value::Int64] (or [Value::UInt64],
+//! [Value::Int128] and [Value::UInt128] if they don't fit and the format supports them), the floats as
+//! [Value::Float64], the strings as [Value::String], the bytes as [Value::Blob], the sequences as [Value::List] and the
+//! maps as [Value::Json]. A [Value::Json] holding an array is therefore deserialized as a [Value::List].
+
+use crate::values::Value;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Int64(value))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Value, E> {
+        Ok(i64::try_from(value).map_or(Value::Int128(value), Value::Int64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Value, E> {
+        Ok(i64::try_from(value).map_or(Value::UInt64(value), Value::Int64))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<Value, E> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Int64(value)),
+            Err(_) => Ok(u64::try_from(value).map_or(Value::UInt128(value), Value::UInt64)),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Float64(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Value, E> {
+        Ok(Value::Blob(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::Blob(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::List(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Value, A::Error> {
+        let json = serde_json::Value::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(Value::Json(json.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::values::{TimeUnit, Value};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    /// Serialize a value into JSON and deserialize it back.
+    fn round_trip(value: &Value) -> (String, Value) {
+        let json = serde_json::to_string(value).unwrap();
+        let value = serde_json::from_str::<Value>(&json).unwrap();
+        (json, value)
+    }
+
+    #[test]
+    fn test_serde() {
+        let string = |value: &str| Value::String(value.to_string());
+        let uuid = Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap();
+        let cases = vec![
+            (Value::Null, "null", Value::Null),
+            (Value::Bool(true), "true", Value::Bool(true)),
+            (Value::Int8(-8), "-8", Value::Int64(-8)),
+            (Value::Int16(-16), "-16", Value::Int64(-16)),
+            (Value::Int32(-32), "-32", Value::Int64(-32)),
+            (Value::Int64(i64::MIN), "-9223372036854775808", Value::Int64(i64::MIN)),
+            (Value::Int128(-128), "-128", Value::Int64(-128)),
+            (Value::Int128(i128::MAX), r#""170141183460469231731687303715884105727""#, string(&i128::MAX.to_string())),
+            (Value::UInt8(8), "8", Value::Int64(8)),
+            (Value::UInt16(16), "16", Value::Int64(16)),
+            (Value::UInt32(32), "32", Value::Int64(32)),
+            (Value::UInt64(u64::MAX), "18446744073709551615", Value::UInt64(u64::MAX)),
+            (Value::UInt128(128), "128", Value::Int64(128)),
+            (Value::Float32(1.5), "1.5", Value::Float64(1.5)),
+            (Value::Float64(-2.25), "-2.25", Value::Float64(-2.25)),
+            (string("it's \"quoted\""), r#""it's \"quoted\"""#, string("it's \"quoted\"")),
+            (Value::Blob(vec![0xde, 0xad, 0xbe, 0xef]), r#""3q2+7w==""#, string("3q2+7w==")),
+            (Value::Date32(19_908), r#""2024-07-04""#, string("2024-07-04")),
+            (
+                Value::Timestamp(TimeUnit::Millisecond, 1720070496101),
+                r#""2024-07-04T05:21:36.101Z""#,
+                string("2024-07-04T05:21:36.101Z"),
+            ),
+            (
+                Value::TimestampTz(TimeUnit::Second, 1720070496, "+02:00".to_string()),
+                r#""2024-07-04T07:21:36+02:00""#,
+                string("2024-07-04T07:21:36+02:00"),
+            ),
+            (Value::Time64(TimeUnit::Microsecond, 3_661_000_001), r#""01:01:01.000001""#, string("01:01:01.000001")),
+            (Value::Interval { months: 1, days: 2, nanos: 0 }, r#""1 month 2 days""#, string("1 month 2 days")),
+            (Value::Decimal(Decimal::new(12345, 2)), r#""123.45""#, string("123.45")),
+            (
+                Value::Uuid(uuid),
+                r#""58cb5e1d-5104-49c7-a983-f1dc53c3da84""#,
+                string("58cb5e1d-5104-49c7-a983-f1dc53c3da84"),
+            ),
+            (Value::Json(r#"{"a": [1, 2]}"#.to_string()), r#"{"a":[1,2]}"#, Value::Json(r#"{"a":[1,2]}"#.to_string())),
+            (Value::Json(r#"[1, "a"]"#.to_string()), r#"[1,"a"]"#, Value::List(vec![Value::Int64(1), string("a")])),
+            (
+                Value::List(vec![Value::Int32(1), Value::Null, string("a")]),
+                r#"[1,null,"a"]"#,
+                Value::List(vec![Value::Int64(1), Value::Null, string("a")]),
+            ),
+        ];
+        for (value, expected_json, expected_value) in cases {
+            assert_eq!(round_trip(&value), (expected_json.to_string(), expected_value), "{:?}", value);
+            // The serialization gives the same JSON document as `Value::to_json`.
+            assert_eq!(serde_json::to_value(&value).unwrap(), value.to_json(), "{:?}", value);
+        }
+    }
+}
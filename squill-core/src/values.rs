@@ -1,6 +1,19 @@
-use chrono::{DateTime, Datelike, TimeZone};
+use crate::Error;
+use arrow_array::types::IntervalMonthDayNano;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array, Int16Array,
+    Int32Array, Int64Array, Int8Array, IntervalMonthDayNanoArray, ListArray, NullArray, StringArray,
+    Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray,
+    TimestampNanosecondArray, TimestampSecondArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{Field, DECIMAL128_MAX_PRECISION};
+use base64::Engine;
+use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike};
 use rust_decimal::Decimal;
+use std::cmp::Ordering;
 use std::fmt;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // The number of days between the UNIX epoch and the CE epoch.
@@ -27,6 +40,11 @@ impl TimeUnit {
 }
 
 // See {@link https://arrow.apache.org/docs/python/api/datatypes.html}
+/// A value bound to a statement or decoded from a record batch.
+///
+/// The values are totally ordered (see [Value::cmp]) and the equality is consistent with that order, so unlike the
+/// IEEE 754 comparison of `f32` and `f64`, `Float64(f64::NAN) == Float64(f64::NAN)` and `Float64(-0.0) !=
+/// Float64(0.0)`.
 #[derive(Debug, Clone)]
 pub enum Value {
     Null,
@@ -53,6 +71,13 @@ pub enum Value {
     /// The precision depends on the {TimeUnit} used.
     Timestamp(TimeUnit, i64),
 
+    /// A 64-bit timestamp with a time zone.
+    ///
+    /// Like [Value::Timestamp], the value is the elapsed time since UNIX epoch (UTC) in the unit of {TimeUnit}. The time
+    /// zone is either an offset (i.e. `+02:00`) or the name of a time zone from the IANA database (i.e.
+    /// `Europe/Paris`), as in the time zone of an Arrow `Timestamp` type.
+    TimestampTz(TimeUnit, i64, String),
+
     /// A 64-bit time type representing the elapsed time since midnight in the unit of {TimeUnit}.
     Time64(TimeUnit, i64),
 
@@ -68,51 +93,97 @@ pub enum Value {
 
     /// Decimal type with precision and scale and 128-bit width
     Decimal(Decimal),
+
+    /// A UUID, bound natively by the drivers supporting it or as its hyphenated string representation otherwise.
+    Uuid(Uuid),
+
+    /// A JSON document in its textual representation.
+    ///
+    /// Unlike a [Value::String], drivers with a native JSON type bind it as such (e.g. `JSON` or `JSONB` for
+    /// PostgreSQL).
+    Json(String),
+
+    /// A list of values, bound as an array by the drivers supporting it (e.g. `INTEGER[]` for PostgreSQL).
+    ///
+    /// DuckDB is an exception: duckdb-rs cannot bind a list parameter, so the list is bound as its text
+    /// representation (i.e. `[1, 2, 3]`) and the placeholder must be cast into a list by the statement (i.e.
+    /// `list_contains(?::INTEGER[], id)` instead of `id = ANY(?)`).
+    List(Vec<Value>),
 }
 
 impl PartialEq for Value {
+    /// Two values are equal if they have the same type and the same value (see [Value::cmp]).
     fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    /// Compare two values using a total order.
+    ///
+    /// The values are ordered by type first, in the order of declaration of the variants (so [Value::Null] comes
+    /// first), then by value:
+    /// - the floats are compared using `total_cmp` (i.e. `-0.0 < 0.0` and `NaN` is greater than any other number),
+    /// - the timestamps and times are compared by the instant they represent regardless of their unit, then by time
+    ///   zone,
+    /// - the intervals are compared by months, then by days and then by nanoseconds,
+    /// - the strings, blobs, JSON documents and lists are compared lexicographically.
+    ///
+    /// The values of different types are never equal, even if they have the same numeric value (i.e. `Int32(1)` and
+    /// `Int64(1)`).
+    fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            (Value::Null, Value::Null) => true,
-            (Value::Bool(a), Value::Bool(b)) => a == b,
-            (Value::Int8(a), Value::Int8(b)) => a == b,
-            (Value::Int16(a), Value::Int16(b)) => a == b,
-            (Value::Int32(a), Value::Int32(b)) => a == b,
-            (Value::Int64(a), Value::Int64(b)) => a == b,
-            (Value::Int128(a), Value::Int128(b)) => a == b,
-            (Value::UInt8(a), Value::UInt8(b)) => a == b,
-            (Value::UInt16(a), Value::UInt16(b)) => a == b,
-            (Value::UInt32(a), Value::UInt32(b)) => a == b,
-            (Value::UInt64(a), Value::UInt64(b)) => a == b,
-            (Value::UInt128(a), Value::UInt128(b)) => a == b,
-            (Value::Float32(a), Value::Float32(b)) => a == b,
-            (Value::Float64(a), Value::Float64(b)) => a == b,
-            (Value::String(a), Value::String(b)) => a == b,
-            (Value::Blob(a), Value::Blob(b)) => a == b,
-            (Value::Date32(a), Value::Date32(b)) => a == b,
-            (Value::Timestamp(unit_a, a), Value::Timestamp(unit_b, b)) => {
-                if unit_a == unit_b && a == b {
-                    true
-                } else {
-                    unit_a.to_nanos(*a) == unit_b.to_nanos(*b)
-                }
-            }
-            (Value::Time64(unit_a, a), Value::Time64(unit_b, b)) => {
-                if unit_a == unit_b && a == b {
-                    true
-                } else {
-                    unit_a.to_nanos(*a) == unit_b.to_nanos(*b)
-                }
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int8(a), Value::Int8(b)) => a.cmp(b),
+            (Value::Int16(a), Value::Int16(b)) => a.cmp(b),
+            (Value::Int32(a), Value::Int32(b)) => a.cmp(b),
+            (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
+            (Value::Int128(a), Value::Int128(b)) => a.cmp(b),
+            (Value::UInt8(a), Value::UInt8(b)) => a.cmp(b),
+            (Value::UInt16(a), Value::UInt16(b)) => a.cmp(b),
+            (Value::UInt32(a), Value::UInt32(b)) => a.cmp(b),
+            (Value::UInt64(a), Value::UInt64(b)) => a.cmp(b),
+            (Value::UInt128(a), Value::UInt128(b)) => a.cmp(b),
+            (Value::Float32(a), Value::Float32(b)) => a.total_cmp(b),
+            (Value::Float64(a), Value::Float64(b)) => a.total_cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Blob(a), Value::Blob(b)) => a.cmp(b),
+            (Value::Date32(a), Value::Date32(b)) => a.cmp(b),
+            (Value::Timestamp(unit_a, a), Value::Timestamp(unit_b, b))
+            | (Value::Time64(unit_a, a), Value::Time64(unit_b, b)) => cmp_instants(unit_a, *a, unit_b, *b),
+            (Value::TimestampTz(unit_a, a, tz_a), Value::TimestampTz(unit_b, b, tz_b)) => {
+                cmp_instants(unit_a, *a, unit_b, *b).then_with(|| tz_a.cmp(tz_b))
             }
             (Value::Interval { months, days, nanos }, Value::Interval { months: m, days: d, nanos: n }) => {
-                months == m && days == d && nanos == n
+                (months, days, nanos).cmp(&(m, d, n))
             }
-            (Value::Decimal(a), Value::Decimal(b)) => a == b,
-            _ => false,
+            (Value::Decimal(a), Value::Decimal(b)) => a.cmp(b),
+            (Value::Uuid(a), Value::Uuid(b)) => a.cmp(b),
+            (Value::Json(a), Value::Json(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => a.cmp(b),
+            _ => self.type_rank().cmp(&other.type_rank()),
         }
     }
 }
 
+/// Compare two instants expressed in possibly different units (without overflowing when converted to nanoseconds).
+fn cmp_instants(unit_a: &TimeUnit, a: i64, unit_b: &TimeUnit, b: i64) -> Ordering {
+    if unit_a == unit_b {
+        return a.cmp(&b);
+    }
+    let nanos = |unit: &TimeUnit, value: i64| unit.to_nanos(1) as i128 * value as i128;
+    nanos(unit_a, a).cmp(&nanos(unit_b, b))
+}
+
 impl From<chrono::NaiveDate> for Value {
     #[inline]
     fn from(value: chrono::NaiveDate) -> Self {
@@ -128,13 +199,14 @@ impl From<chrono::NaiveDateTime> for Value {
 }
 
 impl<T: TimeZone> From<DateTime<T>> for Value {
+    /// Convert a DateTime into a [Value::TimestampTz] keeping the offset of its time zone.
     fn from(value: DateTime<T>) -> Self {
-        Value::Timestamp(TimeUnit::Microsecond, value.timestamp_micros())
+        Value::TimestampTz(TimeUnit::Microsecond, value.timestamp_micros(), value.offset().fix().to_string())
     }
 }
 
 impl From<Uuid> for Value {
-    /// Convert a Uuid into a [Value::String] or [Value::Null] if the Uuid is nil.
+    /// Convert a Uuid into a [Value::Uuid] or [Value::Null] if the Uuid is nil.
     ///
     /// If you explicitly want to convert an Uuid into a u128, you can use [Uuid::as_u128()] when binding the value.
     /// If you want to store the nil UUID in database, you can bind the value as a `String`.
@@ -143,11 +215,45 @@ impl From<Uuid> for Value {
         if value.is_nil() {
             Value::Null
         } else {
-            Value::String(value.to_string())
+            Value::Uuid(value)
+        }
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    /// Convert a JSON document into a [Value::Json] or [Value::Null] if the document is the JSON `null`.
+    #[inline]
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            _ => Value::Json(value.to_string()),
         }
     }
 }
 
+impl From<chrono::NaiveTime> for Value {
+    #[inline]
+    fn from(value: chrono::NaiveTime) -> Self {
+        Value::Time64(
+            TimeUnit::Microsecond,
+            value.num_seconds_from_midnight() as i64 * 1_000_000 + value.nanosecond() as i64 / 1_000,
+        )
+    }
+}
+
+impl From<chrono::Duration> for Value {
+    /// Convert a duration into a [Value::Interval] expressed in nanoseconds only.
+    ///
+    /// A duration is an exact amount of time, it is therefore never converted into days or months which may have a
+    /// variable length. Durations exceeding the range of `i64` nanoseconds (about 292 years) are saturated.
+    #[inline]
+    fn from(value: chrono::Duration) -> Self {
+        let nanos =
+            value.num_nanoseconds().unwrap_or(if value < chrono::Duration::zero() { i64::MIN } else { i64::MAX });
+        Value::Interval { months: 0, days: 0, nanos }
+    }
+}
+
 impl<T> From<Option<T>> for Value
 where
     T: Into<Value>,
@@ -201,6 +307,406 @@ impl_from_for_value!(&str, String);
 impl_from_for_value!(Vec<u8>, Blob);
 impl_from_for_value!(Decimal, Decimal);
 
+// Lists of values.
+// `Vec<u8>` is not part of it because it's converted into a [Value::Blob]. Since there are several implementations for
+// vectors of integers, the type of an unsuffixed integer literal falls back to `i32`: `vec![0xde, 0xad]` is converted
+// into a [Value::List] and a blob must be written `vec![0xde_u8, 0xad]`.
+macro_rules! impl_from_vec_for_value {
+    ($($t:ty),*) => {
+        $(
+            impl From<Vec<$t>> for Value {
+                #[inline]
+                fn from(values: Vec<$t>) -> Self {
+                    Value::List(values.into_iter().map(Value::from).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_vec_for_value!(i8, i16, i32, i64, i128, u16, u32, u64, u128, bool, f32, f64, String, &str, Decimal, Uuid);
+
+impl Value {
+    /// Build an Arrow array of length 1 holding the value.
+    ///
+    /// The type of the array is the one the drivers are using for the value, so the array can be decoded as it would
+    /// be if it was returned by a query:
+    /// - [Value::Int128] is a `Decimal128(38, 0)` and [Value::Decimal] a `Decimal128(38, scale)`,
+    /// - [Value::Time64] is a `Time64(Microsecond)` unless its unit is the nanosecond,
+    /// - [Value::UInt128], [Value::Uuid] and [Value::Json] are using their string representation (`Utf8`),
+    /// - [Value::List] is a `List` of the type of its first non-null value. A list mixing different types of values
+    ///   is a `List` of `Utf8` using the string representation of the values.
+    ///
+    /// This is mostly useful for testing:
+    /// ```rust
+    /// use squill_core::decode::Decode;
+    /// use squill_core::values::Value;
+    ///
+    /// assert_eq!(i32::decode(&Value::Int32(42).to_array(), 0), 42);
+    /// ```
+    pub fn to_array(&self) -> ArrayRef {
+        values_to_array(std::slice::from_ref(self))
+    }
+
+    /// Check if the value is [Value::Null].
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Get the value as a boolean.
+    ///
+    /// Returns `None` if the value is not a [Value::Bool].
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a 64-bit signed integer.
+    ///
+    /// The smaller integer types are widened. [Value::UInt64] and [Value::Int128] are only returned if they fit into an
+    /// `i64`. Returns `None` for any other type.
+    ///
+    /// ```rust
+    /// use squill_core::values::Value;
+    ///
+    /// assert_eq!(Value::Int16(42).as_i64(), Some(42));
+    /// assert_eq!(Value::UInt64(u64::MAX).as_i64(), None);
+    /// assert_eq!(Value::String("42".into()).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int8(value) => Some(*value as i64),
+            Value::Int16(value) => Some(*value as i64),
+            Value::Int32(value) => Some(*value as i64),
+            Value::Int64(value) => Some(*value),
+            Value::Int128(value) => i64::try_from(*value).ok(),
+            Value::UInt8(value) => Some(*value as i64),
+            Value::UInt16(value) => Some(*value as i64),
+            Value::UInt32(value) => Some(*value as i64),
+            Value::UInt64(value) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a 64-bit floating point number.
+    ///
+    /// [Value::Float32] is widened. Returns `None` for any other type.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float32(value) => Some(*value as f64),
+            Value::Float64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a string slice.
+    ///
+    /// Returns `None` if the value is not a [Value::String].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a byte slice.
+    ///
+    /// Returns `None` if the value is not a [Value::Blob].
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match self {
+            Value::Blob(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Convert the value into a JSON value.
+    ///
+    /// - The integers and floats are JSON numbers, unless they cannot be represented as such (`Int128` or `UInt128`
+    ///   out of the range of 64-bit integers are strings, non-finite floats are `null`),
+    /// - [Value::Blob] is a base64 string,
+    /// - [Value::Date32] is a date (`YYYY-MM-DD`), [Value::Timestamp] and [Value::TimestampTz] are RFC 3339 datetimes
+    ///   and the other temporal types use their string representation,
+    /// - [Value::Decimal] is a string to preserve its precision,
+    /// - [Value::Json] is the parsed document (or a string if it is not a valid JSON document),
+    /// - [Value::List] is an array.
+    ///
+    /// ```rust
+    /// use squill_core::values::Value;
+    ///
+    /// assert_eq!(Value::Int32(42).to_json(), serde_json::json!(42));
+    /// assert_eq!(Value::Date32(19_000).to_json(), serde_json::json!("2022-01-08"));
+    /// assert_eq!(Value::Null.to_json(), serde_json::Value::Null);
+    /// ```
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Value::Null => Json::Null,
+            Value::Bool(value) => Json::from(*value),
+            Value::Int8(value) => Json::from(*value),
+            Value::Int16(value) => Json::from(*value),
+            Value::Int32(value) => Json::from(*value),
+            Value::Int64(value) => Json::from(*value),
+            Value::Int128(value) => match i64::try_from(*value) {
+                Ok(value) => Json::from(value),
+                Err(_) => Json::from(value.to_string()),
+            },
+            Value::UInt8(value) => Json::from(*value),
+            Value::UInt16(value) => Json::from(*value),
+            Value::UInt32(value) => Json::from(*value),
+            Value::UInt64(value) => Json::from(*value),
+            Value::UInt128(value) => match u64::try_from(*value) {
+                Ok(value) => Json::from(value),
+                Err(_) => Json::from(value.to_string()),
+            },
+            Value::Float32(value) => serde_json::Number::from_f64(*value as f64).map_or(Json::Null, Json::Number),
+            Value::Float64(value) => serde_json::Number::from_f64(*value).map_or(Json::Null, Json::Number),
+            Value::String(value) => Json::from(value.as_str()),
+            Value::Blob(value) => Json::from(base64::engine::general_purpose::STANDARD.encode(value)),
+            Value::Date32(days) => match days
+                .checked_add(UNIX_EPOCH_NUM_DAYS_FROM_CE)
+                .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+            {
+                Some(date) => Json::from(date.format("%Y-%m-%d").to_string()),
+                None => Json::Null,
+            },
+            Value::Json(value) => serde_json::from_str(value).unwrap_or_else(|_| Json::from(value.as_str())),
+            Value::List(values) => Json::Array(values.iter().map(Value::to_json).collect()),
+            // The other types are using their string representation (null if it cannot be formatted, i.e. a timestamp
+            // out of the range supported by chrono).
+            Value::Timestamp(_, _)
+            | Value::TimestampTz(_, _, _)
+            | Value::Time64(_, _)
+            | Value::Interval { .. }
+            | Value::Decimal(_)
+            | Value::Uuid(_) => {
+                let mut value = String::new();
+                match fmt::write(&mut value, format_args!("{}", self)) {
+                    Ok(()) => Json::from(value),
+                    Err(_) => Json::Null,
+                }
+            }
+        }
+    }
+
+    /// Get the name of the variant of the value (e.g. `Int32` for [Value::Int32]).
+    ///
+    /// This is the name reported by the drivers for a value they cannot bind (see [Error::UnsupportedDataType]).
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "Null",
+            Value::Bool(_) => "Bool",
+            Value::Int8(_) => "Int8",
+            Value::Int16(_) => "Int16",
+            Value::Int32(_) => "Int32",
+            Value::Int64(_) => "Int64",
+            Value::Int128(_) => "Int128",
+            Value::UInt8(_) => "UInt8",
+            Value::UInt16(_) => "UInt16",
+            Value::UInt32(_) => "UInt32",
+            Value::UInt64(_) => "UInt64",
+            Value::UInt128(_) => "UInt128",
+            Value::Float32(_) => "Float32",
+            Value::Float64(_) => "Float64",
+            Value::String(_) => "String",
+            Value::Blob(_) => "Blob",
+            Value::Date32(_) => "Date32",
+            Value::Timestamp(_, _) => "Timestamp",
+            Value::TimestampTz(_, _, _) => "TimestampTz",
+            Value::Time64(_, _) => "Time64",
+            Value::Interval { .. } => "Interval",
+            Value::Decimal(_) => "Decimal",
+            Value::Uuid(_) => "Uuid",
+            Value::Json(_) => "Json",
+            Value::List(_) => "List",
+        }
+    }
+
+    /// Get the rank of the variant of the value used to order the values of different types (see [Value::cmp]).
+    fn type_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Int8(_) => 2,
+            Value::Int16(_) => 3,
+            Value::Int32(_) => 4,
+            Value::Int64(_) => 5,
+            Value::Int128(_) => 6,
+            Value::UInt8(_) => 7,
+            Value::UInt16(_) => 8,
+            Value::UInt32(_) => 9,
+            Value::UInt64(_) => 10,
+            Value::UInt128(_) => 11,
+            Value::Float32(_) => 12,
+            Value::Float64(_) => 13,
+            Value::String(_) => 14,
+            Value::Blob(_) => 15,
+            Value::Date32(_) => 16,
+            Value::Timestamp(_, _) => 17,
+            Value::TimestampTz(_, _, _) => 18,
+            Value::Time64(_, _) => 19,
+            Value::Interval { .. } => 20,
+            Value::Decimal(_) => 21,
+            Value::Uuid(_) => 22,
+            Value::Json(_) => 23,
+            Value::List(_) => 24,
+        }
+    }
+
+    /// Build the error returned when the value cannot be converted into the type `expected`.
+    fn invalid_type(&self, expected: &str) -> Error {
+        Error::InvalidType { expected: expected.to_string(), actual: self.type_name().to_string() }
+    }
+
+    /// Check if two values would be stored in an array of the same type.
+    fn is_same_type(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Timestamp(unit, _), Value::Timestamp(other_unit, _)) => unit == other_unit,
+            (Value::TimestampTz(unit, _, tz), Value::TimestampTz(other_unit, _, other_tz)) => {
+                unit == other_unit && tz == other_tz
+            }
+            (Value::Time64(TimeUnit::Nanosecond, _), Value::Time64(other_unit, _)) => {
+                other_unit == &TimeUnit::Nanosecond
+            }
+            (Value::Time64(_, _), Value::Time64(other_unit, _)) => other_unit != &TimeUnit::Nanosecond,
+            (Value::List(_), Value::List(_)) => true,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Build an Arrow array from a list of values of the same type (or null).
+fn values_to_array(values: &[Value]) -> ArrayRef {
+    // The type of the array is given by the first non-null value.
+    let first = match values.iter().find(|value| !matches!(value, Value::Null)) {
+        Some(first) => first,
+        None => return Arc::new(NullArray::new(values.len())),
+    };
+    if !values.iter().all(|value| matches!(value, Value::Null) || value.is_same_type(first)) {
+        return Arc::new(StringArray::from_iter(values.iter().map(|value| match value {
+            Value::Null => None,
+            value => Some(value.to_string()),
+        })));
+    }
+
+    macro_rules! array_of {
+        ($array_type:ty, $pattern:pat => $value:expr) => {
+            Arc::new(<$array_type>::from_iter(values.iter().map(|value| match value {
+                $pattern => Some($value),
+                _ => None,
+            })))
+        };
+    }
+
+    match first {
+        Value::Null => unreachable!("the first non-null value cannot be null"),
+        Value::Bool(_) => array_of!(BooleanArray, Value::Bool(value) => *value),
+        Value::Int8(_) => array_of!(Int8Array, Value::Int8(value) => *value),
+        Value::Int16(_) => array_of!(Int16Array, Value::Int16(value) => *value),
+        Value::Int32(_) => array_of!(Int32Array, Value::Int32(value) => *value),
+        Value::Int64(_) => array_of!(Int64Array, Value::Int64(value) => *value),
+        Value::Int128(_) => Arc::new(
+            Decimal128Array::from_iter(values.iter().map(|value| match value {
+                Value::Int128(value) => Some(*value),
+                _ => None,
+            }))
+            .with_precision_and_scale(DECIMAL128_MAX_PRECISION, 0)
+            .unwrap(), // The precision and scale are valid.
+        ),
+        Value::UInt8(_) => array_of!(UInt8Array, Value::UInt8(value) => *value),
+        Value::UInt16(_) => array_of!(UInt16Array, Value::UInt16(value) => *value),
+        Value::UInt32(_) => array_of!(UInt32Array, Value::UInt32(value) => *value),
+        Value::UInt64(_) => array_of!(UInt64Array, Value::UInt64(value) => *value),
+        Value::Float32(_) => array_of!(Float32Array, Value::Float32(value) => *value),
+        Value::Float64(_) => array_of!(Float64Array, Value::Float64(value) => *value),
+        Value::String(_) => array_of!(StringArray, Value::String(value) => value),
+        Value::Blob(_) => array_of!(BinaryArray, Value::Blob(value) => value),
+        Value::Date32(_) => array_of!(Date32Array, Value::Date32(value) => *value),
+        Value::Timestamp(TimeUnit::Second, _) => array_of!(TimestampSecondArray, Value::Timestamp(_, value) => *value),
+        Value::Timestamp(TimeUnit::Millisecond, _) => {
+            array_of!(TimestampMillisecondArray, Value::Timestamp(_, value) => *value)
+        }
+        Value::Timestamp(TimeUnit::Microsecond, _) => {
+            array_of!(TimestampMicrosecondArray, Value::Timestamp(_, value) => *value)
+        }
+        Value::Timestamp(TimeUnit::Nanosecond, _) => {
+            array_of!(TimestampNanosecondArray, Value::Timestamp(_, value) => *value)
+        }
+        Value::TimestampTz(unit, _, tz) => {
+            macro_rules! timestamp_tz_array_of {
+                ($array_type:ty) => {
+                    Arc::new(
+                        <$array_type>::from_iter(values.iter().map(|value| match value {
+                            Value::TimestampTz(_, value, _) => Some(*value),
+                            _ => None,
+                        }))
+                        .with_timezone(tz.as_str()),
+                    )
+                };
+            }
+            match unit {
+                TimeUnit::Second => timestamp_tz_array_of!(TimestampSecondArray),
+                TimeUnit::Millisecond => timestamp_tz_array_of!(TimestampMillisecondArray),
+                TimeUnit::Microsecond => timestamp_tz_array_of!(TimestampMicrosecondArray),
+                TimeUnit::Nanosecond => timestamp_tz_array_of!(TimestampNanosecondArray),
+            }
+        }
+        Value::Time64(TimeUnit::Nanosecond, _) => array_of!(Time64NanosecondArray, Value::Time64(_, value) => *value),
+        Value::Time64(_, _) => {
+            array_of!(Time64MicrosecondArray, Value::Time64(unit, value) => unit.to_nanos(*value) / 1_000)
+        }
+        Value::Interval { .. } => array_of!(
+            IntervalMonthDayNanoArray,
+            Value::Interval { months, days, nanos } => IntervalMonthDayNano::new(*months, *days, *nanos)
+        ),
+        Value::Decimal(_) => {
+            // All the decimals of the array must have the same scale.
+            let scale = values
+                .iter()
+                .filter_map(|value| match value {
+                    Value::Decimal(value) => Some(value.scale()),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or_default();
+            Arc::new(
+                Decimal128Array::from_iter(values.iter().map(|value| match value {
+                    Value::Decimal(value) => {
+                        let mut value = *value;
+                        value.rescale(scale);
+                        Some(value.mantissa())
+                    }
+                    _ => None,
+                }))
+                .with_precision_and_scale(DECIMAL128_MAX_PRECISION, scale as i8)
+                .unwrap(), // The scale of a Decimal is at most 28.
+            )
+        }
+        Value::UInt128(_) | Value::Uuid(_) | Value::Json(_) => {
+            array_of!(StringArray, value @ (Value::UInt128(_) | Value::Uuid(_) | Value::Json(_)) => value.to_string())
+        }
+        Value::List(_) => {
+            let lengths = values.iter().map(|value| match value {
+                Value::List(list) => list.len(),
+                _ => 0,
+            });
+            let offsets = OffsetBuffer::from_lengths(lengths);
+            let nulls = NullBuffer::from_iter(values.iter().map(|value| !matches!(value, Value::Null)));
+            let items: Vec<Value> = values
+                .iter()
+                .flat_map(|value| match value {
+                    Value::List(list) => list.clone(),
+                    _ => vec![],
+                })
+                .collect();
+            let items = values_to_array(&items);
+            let field = Arc::new(Field::new_list_field(items.data_type().clone(), true));
+            Arc::new(ListArray::new(field, offsets, items, Some(nulls)))
+        }
+    }
+}
+
 /// Display implementation for Value.
 ///
 /// This is also use to cast a Value into a string when a driver would not support the Value type. For instance DuckDB
@@ -230,20 +736,20 @@ impl fmt::Display for Value {
             Value::Date32(value) => write!(f, "{}", value),
 
             // Timestamp
-            Value::Timestamp(TimeUnit::Second, value) => match DateTime::from_timestamp(*value, 0) {
-                Some(datetime) => datetime.to_rfc3339_opts(chrono::SecondsFormat::Secs, true).fmt(f),
-                None => Err(fmt::Error),
-            },
-            Value::Timestamp(TimeUnit::Millisecond, value) => match DateTime::from_timestamp_millis(*value) {
-                Some(datetime) => datetime.to_rfc3339_opts(chrono::SecondsFormat::Millis, true).fmt(f),
-                None => Err(fmt::Error),
-            },
-            Value::Timestamp(TimeUnit::Microsecond, value) => match DateTime::from_timestamp_micros(*value) {
-                Some(datetime) => datetime.to_rfc3339_opts(chrono::SecondsFormat::Micros, true).fmt(f),
+            Value::Timestamp(unit, value) => match timestamp_to_datetime(unit, *value) {
+                Some((datetime, format)) => datetime.to_rfc3339_opts(format, true).fmt(f),
                 None => Err(fmt::Error),
             },
-            Value::Timestamp(TimeUnit::Nanosecond, value) => {
-                DateTime::from_timestamp_nanos(*value).to_rfc3339_opts(chrono::SecondsFormat::Nanos, true).fmt(f)
+
+            // TimestampTz
+            // The timestamp is displayed using the offset of its time zone at that instant.
+            Value::TimestampTz(unit, value, tz) => {
+                match (timestamp_to_datetime(unit, *value), tz.parse::<arrow_array::timezone::Tz>()) {
+                    (Some((datetime, format)), Ok(tz)) => {
+                        datetime.with_timezone(&tz).fixed_offset().to_rfc3339_opts(format, true).fmt(f)
+                    }
+                    _ => Err(fmt::Error),
+                }
             }
 
             // Time64
@@ -304,6 +810,28 @@ impl fmt::Display for Value {
 
             // Decimal
             Value::Decimal(value) => write!(f, "{}", value),
+
+            // Uuid
+            Value::Uuid(value) => write!(f, "{}", value.hyphenated()),
+
+            // Json
+            Value::Json(value) => write!(f, "{}", value),
+
+            // List
+            // Strings are double quoted so the result can be cast into a list/array by most databases.
+            Value::List(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match value {
+                        Value::String(value) => write!(f, "\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))?,
+                        _ => write!(f, "{}", value)?,
+                    }
+                }
+                write!(f, "]")
+            }
         }
     }
 }
@@ -321,6 +849,89 @@ where
     }
 }
 
+// Conversions from a value into a Rust type.
+//
+// The integers are converted from any integer value as long as the conversion is lossless (i.e. an `Int8` can be
+// converted into an `i64`, and an `Int64` into an `i8` only if it fits), otherwise an `Error::InvalidType` is
+// returned.
+macro_rules! impl_try_from_value_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<Value> for $t {
+                type Error = Error;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    let converted = match &value {
+                        Value::Int8(v) => <$t>::try_from(*v).ok(),
+                        Value::Int16(v) => <$t>::try_from(*v).ok(),
+                        Value::Int32(v) => <$t>::try_from(*v).ok(),
+                        Value::Int64(v) => <$t>::try_from(*v).ok(),
+                        Value::Int128(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt8(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt16(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt32(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt64(v) => <$t>::try_from(*v).ok(),
+                        Value::UInt128(v) => <$t>::try_from(*v).ok(),
+                        _ => None,
+                    };
+                    converted.ok_or_else(|| value.invalid_type(stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_value_for_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+// Conversions from a value into a Rust type for the variants holding exactly that type.
+macro_rules! impl_try_from_value {
+    ($t:ty, $variant:ident) => {
+        impl TryFrom<Value> for $t {
+            type Error = Error;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok(v),
+                    _ => Err(value.invalid_type(stringify!($t))),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_value!(bool, Bool);
+impl_try_from_value!(f32, Float32);
+impl_try_from_value!(String, String);
+impl_try_from_value!(Vec<u8>, Blob);
+impl_try_from_value!(Decimal, Decimal);
+impl_try_from_value!(Uuid, Uuid);
+impl_try_from_value!(Vec<Value>, List);
+
+impl TryFrom<Value> for f64 {
+    type Error = Error;
+
+    /// [Value::Float32] is widened, see [Value::as_f64].
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value.as_f64().ok_or_else(|| value.invalid_type("f64"))
+    }
+}
+
+// Helper function to get the UTC datetime of a timestamp along with the precision used to format it.
+//
+// Returns `None` if the timestamp is out of the range supported by chrono.
+fn timestamp_to_datetime(unit: &TimeUnit, value: i64) -> Option<(DateTime<chrono::Utc>, chrono::SecondsFormat)> {
+    match unit {
+        TimeUnit::Second => DateTime::from_timestamp(value, 0).map(|datetime| (datetime, chrono::SecondsFormat::Secs)),
+        TimeUnit::Millisecond => {
+            DateTime::from_timestamp_millis(value).map(|datetime| (datetime, chrono::SecondsFormat::Millis))
+        }
+        TimeUnit::Microsecond => {
+            DateTime::from_timestamp_micros(value).map(|datetime| (datetime, chrono::SecondsFormat::Micros))
+        }
+        TimeUnit::Nanosecond => Some((DateTime::from_timestamp_nanos(value), chrono::SecondsFormat::Nanos)),
+    }
+}
+
 // Helper function to format a unit value with its singular and plural form.
 //
 // - The value is only printed if it is greater than 0.
@@ -370,6 +981,18 @@ mod tests {
         assert_eq!(Value::Blob(vec![0xde, 0xad, 0xbe, 0xef]).to_string(), "[222, 173, 190, 239]");
         assert_eq!(Value::Date32(18628).to_string(), "18628");
         assert_eq!(Value::Timestamp(TimeUnit::Second, 1720070496).to_string(), "2024-07-04T05:21:36Z");
+        assert_eq!(
+            Value::TimestampTz(TimeUnit::Millisecond, 1720070496101, "+02:00".to_string()).to_string(),
+            "2024-07-04T07:21:36.101+02:00"
+        );
+        assert_eq!(
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "America/New_York".to_string()).to_string(),
+            "2024-07-04T01:21:36-04:00"
+        );
+        assert_eq!(
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "UTC".to_string()).to_string(),
+            "2024-07-04T05:21:36Z"
+        );
         assert_eq!(Value::Timestamp(TimeUnit::Millisecond, 1720070496101).to_string(), "2024-07-04T05:21:36.101Z");
         assert_eq!(
             Value::Timestamp(TimeUnit::Microsecond, 1720070496101102).to_string(),
@@ -393,6 +1016,14 @@ mod tests {
             "13:20:10.101202303"
         );
         assert_eq!(Value::Decimal(Decimal::new(1299, 2)).to_string(), "12.99");
+        assert_eq!(
+            Value::Uuid(Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap()).to_string(),
+            "58cb5e1d-5104-49c7-a983-f1dc53c3da84"
+        );
+        assert_eq!(Value::Json(r#"{"a":1}"#.to_string()).to_string(), r#"{"a":1}"#);
+        assert_eq!(Value::List(vec![Value::Int32(1), Value::Null, Value::Int32(3)]).to_string(), "[1, null, 3]");
+        assert_eq!(Value::from(vec!["a", "b \"c\""]).to_string(), r#"["a", "b \"c\""]"#);
+        assert_eq!(Value::List(vec![]).to_string(), "[]");
 
         // INTERVAL
         assert_eq!(Value::Interval { months: 1, days: 1, nanos: 0 }.to_string(), "1 month 1 day");
@@ -421,16 +1052,296 @@ mod tests {
         assert_eq!(Value::from(u128::MAX), Value::UInt128(u128::MAX));
         assert_eq!(Value::from(f32::MAX), Value::Float32(f32::MAX));
         assert_eq!(Value::from(f64::MAX), Value::Float64(f64::MAX));
-        assert_eq!(Value::from(vec![0xde, 0xad, 0xbe, 0xef]), Value::Blob(vec![0xde, 0xad, 0xbe, 0xef]));
+        // The `u8` suffix is needed, a vector of unsuffixed integers is a list of `Int32`.
+        assert_eq!(Value::from(vec![0xde_u8, 0xad, 0xbe, 0xef]), Value::Blob(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(Value::from(vec![0xde, 0xad]), Value::List(vec![Value::Int32(0xde), Value::Int32(0xad)]));
         assert_eq!(Value::from(chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()), Value::Date32(18628));
         assert_eq!(
             Value::from(chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()),
             Value::Timestamp(TimeUnit::Millisecond, 1609459200000)
         );
+        assert_eq!(
+            Value::from(chrono::DateTime::parse_from_rfc3339("2024-07-04T07:21:36.101202+02:00").unwrap()),
+            Value::TimestampTz(TimeUnit::Microsecond, 1720070496101202, "+02:00".to_string())
+        );
+        assert_eq!(
+            Value::from(chrono::DateTime::from_timestamp(1720070496, 0).unwrap()),
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "+00:00".to_string())
+        );
+        assert_ne!(
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "+00:00".to_string()),
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "UTC".to_string())
+        );
         assert_eq!(Value::from(Uuid::nil()), Value::Null);
         assert_eq!(
+            Value::from(Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap()),
+            Value::Uuid(Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap())
+        );
+        assert_ne!(
             Value::from(Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap()),
             Value::String("58cb5e1d-5104-49c7-a983-f1dc53c3da84".to_string())
         );
+        assert_eq!(Value::from(serde_json::json!({ "a": [1, 2] })), Value::Json(r#"{"a":[1,2]}"#.to_string()));
+        assert_eq!(Value::from(serde_json::json!("hello")), Value::Json(r#""hello""#.to_string()));
+        assert_eq!(Value::from(serde_json::Value::Null), Value::Null);
+        assert_eq!(
+            Value::from(chrono::NaiveTime::from_hms_micro_opt(13, 20, 10, 101_202).unwrap()),
+            Value::Time64(TimeUnit::Microsecond, (13 * 3600 + 20 * 60 + 10) * 1_000_000 + 101_202)
+        );
+        assert_eq!(
+            Value::from(chrono::Duration::milliseconds(1500)),
+            Value::Interval { months: 0, days: 0, nanos: 1_500_000_000 }
+        );
+        assert_eq!(Value::from(chrono::Duration::MAX), Value::Interval { months: 0, days: 0, nanos: i64::MAX });
+        assert_eq!(Value::from(vec![1, 2, 3]), Value::List(vec![Value::Int32(1), Value::Int32(2), Value::Int32(3)]));
+        assert_eq!(Value::from(vec!["a".to_string()]), Value::List(vec![Value::String("a".to_string())]));
+        assert_eq!(Value::from(vec![0u8, 1u8]), Value::Blob(vec![0, 1]));
+        assert_ne!(Value::from(serde_json::json!("hello")), Value::String("hello".to_string()));
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert!(Value::Null.is_null());
+        assert!(!Value::Int32(0).is_null());
+
+        assert_eq!(Value::Bool(true).as_bool(), Some(true));
+        assert_eq!(Value::Int64(1).as_bool(), None);
+
+        assert_eq!(Value::Int8(-8).as_i64(), Some(-8));
+        assert_eq!(Value::Int16(-16).as_i64(), Some(-16));
+        assert_eq!(Value::Int32(-32).as_i64(), Some(-32));
+        assert_eq!(Value::Int64(i64::MIN).as_i64(), Some(i64::MIN));
+        assert_eq!(Value::Int128(128).as_i64(), Some(128));
+        assert_eq!(Value::Int128(i128::MAX).as_i64(), None);
+        assert_eq!(Value::UInt8(8).as_i64(), Some(8));
+        assert_eq!(Value::UInt16(16).as_i64(), Some(16));
+        assert_eq!(Value::UInt32(u32::MAX).as_i64(), Some(u32::MAX as i64));
+        assert_eq!(Value::UInt64(64).as_i64(), Some(64));
+        assert_eq!(Value::UInt64(u64::MAX).as_i64(), None);
+        assert_eq!(Value::Float64(1.0).as_i64(), None);
+        assert_eq!(Value::Null.as_i64(), None);
+
+        assert_eq!(Value::Float32(1.5).as_f64(), Some(1.5));
+        assert_eq!(Value::Float64(2.5).as_f64(), Some(2.5));
+        assert_eq!(Value::Int32(1).as_f64(), None);
+
+        assert_eq!(Value::String("hello".into()).as_str(), Some("hello"));
+        assert_eq!(Value::Json("{}".into()).as_str(), None);
+        assert_eq!(Value::Blob(vec![1, 2]).as_str(), None);
+
+        assert_eq!(Value::Blob(vec![1, 2]).as_blob(), Some(&[1u8, 2][..]));
+        assert_eq!(Value::String("hello".into()).as_blob(), None);
+    }
+
+    #[test]
+    fn test_to_array() {
+        use crate::decode::Decode;
+        use arrow_array::Array;
+        use arrow_schema::DataType;
+
+        // Values decoded as themselves.
+        let values = vec![
+            Value::Null,
+            Value::Bool(true),
+            Value::Int8(i8::MIN),
+            Value::Int16(i16::MIN),
+            Value::Int32(i32::MIN),
+            Value::Int64(i64::MIN),
+            Value::UInt8(u8::MAX),
+            Value::UInt16(u16::MAX),
+            Value::UInt32(u32::MAX),
+            Value::UInt64(u64::MAX),
+            Value::Float32(1.5),
+            Value::Float64(-2.5),
+            Value::String("hello".to_string()),
+            Value::Blob(vec![0xde, 0xad]),
+            Value::Date32(18628),
+            Value::Timestamp(TimeUnit::Second, 1720070496),
+            Value::Timestamp(TimeUnit::Millisecond, 1720070496101),
+            Value::Timestamp(TimeUnit::Microsecond, 1720070496101202),
+            Value::Timestamp(TimeUnit::Nanosecond, 1720070496101202303),
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "+02:00".to_string()),
+            Value::TimestampTz(TimeUnit::Microsecond, 1720070496101202, "Europe/Paris".to_string()),
+            Value::Time64(TimeUnit::Microsecond, 48_010_101_202),
+            Value::Time64(TimeUnit::Nanosecond, 48_010_101_202_303),
+            Value::Interval { months: 1, days: 2, nanos: 3 },
+            Value::Decimal(Decimal::from_str("-123.45").unwrap()),
+        ];
+        for value in values {
+            let array = value.to_array();
+            assert_eq!(array.len(), 1);
+            assert_eq!(Value::decode(&array, 0), value);
+        }
+
+        // Values decoded using their equivalent type.
+        let array = Value::TimestampTz(TimeUnit::Microsecond, 1720070496101202, "Europe/Paris".to_string()).to_array();
+        assert_eq!(
+            array.data_type(),
+            &DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, Some("Europe/Paris".into()))
+        );
+        assert_eq!(
+            chrono::DateTime::<chrono::FixedOffset>::decode(&array, 0),
+            chrono::DateTime::parse_from_rfc3339("2024-07-04T07:21:36.101202+02:00").unwrap()
+        );
+        assert_eq!(
+            chrono::DateTime::<chrono::FixedOffset>::decode(
+                &Value::Timestamp(TimeUnit::Second, 1720070496).to_array(),
+                0
+            ),
+            chrono::DateTime::parse_from_rfc3339("2024-07-04T05:21:36Z").unwrap()
+        );
+        assert_eq!(Value::Int128(i128::MIN).to_array().data_type(), &DataType::Decimal128(38, 0));
+        assert_eq!(i128::decode(&Value::Int128(i128::MIN).to_array(), 0), i128::MIN);
+        assert_eq!(
+            Value::decode(&Value::Time64(TimeUnit::Second, 48_010).to_array(), 0),
+            Value::Time64(TimeUnit::Microsecond, 48_010_000_000)
+        );
+        assert_eq!(String::decode(&Value::UInt128(u128::MAX).to_array(), 0), u128::MAX.to_string());
+        let uuid = Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap();
+        assert_eq!(Uuid::decode(&Value::Uuid(uuid).to_array(), 0), uuid);
+        assert_eq!(String::decode(&Value::Json(r#"{"a":1}"#.to_string()).to_array(), 0), r#"{"a":1}"#);
+
+        // Lists
+        let array = Value::List(vec![Value::Int32(1), Value::Null, Value::Int32(3)]).to_array();
+        assert_eq!(array.data_type(), &DataType::new_list(DataType::Int32, true));
+        let list = array.as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        assert_eq!(list.len(), 3);
+        assert_eq!(i32::decode(&list, 0), 1);
+        assert!(list.is_null(1));
+        assert_eq!(i32::decode(&list, 2), 3);
+        let array =
+            Value::List(vec![Value::Decimal(Decimal::new(15, 1)), Value::Decimal(Decimal::new(125, 2))]).to_array();
+        let list = array.as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        assert_eq!(Decimal::decode(&list, 0), Decimal::from_str("1.50").unwrap());
+        assert_eq!(Decimal::decode(&list, 1), Decimal::from_str("1.25").unwrap());
+        let array = Value::List(vec![Value::Int32(1), Value::String("a".to_string())]).to_array();
+        let list = array.as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        assert_eq!(String::decode(&list, 0), "1");
+        assert_eq!(String::decode(&list, 1), "a");
+        let array = Value::List(vec![]).to_array();
+        assert_eq!(array.as_any().downcast_ref::<ListArray>().unwrap().value(0).len(), 0);
+    }
+
+    #[test]
+    fn test_try_from_value() {
+        // Integers
+        assert_eq!(i64::try_from(Value::Int8(-42)).unwrap(), -42);
+        assert_eq!(i64::try_from(Value::UInt32(u32::MAX)).unwrap(), u32::MAX as i64);
+        assert_eq!(i8::try_from(Value::Int64(42)).unwrap(), 42);
+        assert_eq!(u8::try_from(Value::Int16(255)).unwrap(), 255);
+        assert_eq!(u128::try_from(Value::UInt128(u128::MAX)).unwrap(), u128::MAX);
+        assert_eq!(i128::try_from(Value::UInt64(u64::MAX)).unwrap(), u64::MAX as i128);
+
+        // Floats
+        assert_eq!(f64::try_from(Value::Float64(1.5)).unwrap(), 1.5);
+        assert_eq!(f64::try_from(Value::Float32(1.5)).unwrap(), 1.5);
+        assert_eq!(f32::try_from(Value::Float32(1.5)).unwrap(), 1.5);
+
+        // Others
+        assert!(bool::try_from(Value::Bool(true)).unwrap());
+        assert_eq!(String::try_from(Value::String("hello".to_string())).unwrap(), "hello");
+        assert_eq!(Vec::<u8>::try_from(Value::Blob(vec![0xde, 0xad])).unwrap(), vec![0xde, 0xad]);
+        assert_eq!(Decimal::try_from(Value::Decimal(Decimal::new(125, 2))).unwrap(), Decimal::new(125, 2));
+        let uuid = Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap();
+        assert_eq!(Uuid::try_from(Value::Uuid(uuid)).unwrap(), uuid);
+        assert_eq!(Vec::<Value>::try_from(Value::List(vec![Value::Int32(1)])).unwrap(), vec![Value::Int32(1)]);
+
+        // Errors
+        assert!(matches!(
+            i64::try_from(Value::String("42".to_string())),
+            Err(Error::InvalidType { expected, actual }) if expected == "i64" && actual == "String"
+        ));
+        assert!(matches!(
+            i8::try_from(Value::Int32(128)),
+            Err(Error::InvalidType { expected, actual }) if expected == "i8" && actual == "Int32"
+        ));
+        assert!(matches!(u64::try_from(Value::Int64(-1)), Err(Error::InvalidType { .. })));
+        assert!(matches!(i64::try_from(Value::Float64(1.0)), Err(Error::InvalidType { .. })));
+        assert!(matches!(f32::try_from(Value::Float64(1.5)), Err(Error::InvalidType { .. })));
+        assert!(matches!(bool::try_from(Value::Int8(1)), Err(Error::InvalidType { .. })));
+        assert!(matches!(String::try_from(Value::Null), Err(Error::InvalidType { actual, .. }) if actual == "Null"));
+        assert!(matches!(Vec::<u8>::try_from(Value::String("a".to_string())), Err(Error::InvalidType { .. })));
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut values = vec![
+            Value::List(vec![Value::Int32(1), Value::Int32(2)]),
+            Value::String("b".to_string()),
+            Value::Float64(f64::NAN),
+            Value::Int32(2),
+            Value::Timestamp(TimeUnit::Second, 2),
+            Value::Float64(0.0),
+            Value::Null,
+            Value::Int64(-1),
+            Value::Timestamp(TimeUnit::Millisecond, 1_500),
+            Value::Float64(-0.0),
+            Value::String("a".to_string()),
+            Value::Bool(true),
+            Value::Float64(f64::NEG_INFINITY),
+            Value::List(vec![Value::Int32(1)]),
+            Value::Int32(-3),
+            Value::Bool(false),
+        ];
+        values.sort();
+        assert_eq!(
+            values.iter().map(|value| value.to_string()).collect::<Vec<_>>(),
+            vec![
+                "null",
+                "false",
+                "true",
+                "-3",
+                "2",
+                "-1",
+                "-inf",
+                "-0",
+                "0",
+                "NaN",
+                "a",
+                "b",
+                "1970-01-01T00:00:01.500Z",
+                "1970-01-01T00:00:02Z",
+                "[1]",
+                "[1, 2]",
+            ]
+        );
+
+        // The values of different types are never equal, the instants are compared regardless of their unit.
+        assert_ne!(Value::Int32(1), Value::Int64(1));
+        assert_eq!(Value::Timestamp(TimeUnit::Second, 1), Value::Timestamp(TimeUnit::Nanosecond, 1_000_000_000));
+        assert!(Value::Timestamp(TimeUnit::Second, i64::MAX) > Value::Timestamp(TimeUnit::Nanosecond, i64::MAX));
+        assert_eq!(Value::Float64(f64::NAN), Value::Float64(f64::NAN));
+        assert!(Value::Null < Value::Bool(false));
+    }
+
+    #[test]
+    fn test_to_json() {
+        use serde_json::json;
+
+        assert_eq!(Value::Null.to_json(), json!(null));
+        assert_eq!(Value::Bool(true).to_json(), json!(true));
+        assert_eq!(Value::Int8(-8).to_json(), json!(-8));
+        assert_eq!(Value::UInt64(u64::MAX).to_json(), json!(u64::MAX));
+        assert_eq!(Value::Int128(42).to_json(), json!(42));
+        assert_eq!(Value::Int128(i128::MAX).to_json(), json!(i128::MAX.to_string()));
+        assert_eq!(Value::UInt128(u128::MAX).to_json(), json!(u128::MAX.to_string()));
+        assert_eq!(Value::Float32(1.5).to_json(), json!(1.5));
+        assert_eq!(Value::Float64(f64::NAN).to_json(), json!(null));
+        assert_eq!(Value::String("hello".to_string()).to_json(), json!("hello"));
+        assert_eq!(Value::Blob(vec![0xde, 0xad]).to_json(), json!("3q0="));
+        assert_eq!(Value::Date32(19_908).to_json(), json!("2024-07-04"));
+        assert_eq!(Value::Timestamp(TimeUnit::Second, 1720070496).to_json(), json!("2024-07-04T05:21:36Z"));
+        assert_eq!(Value::Timestamp(TimeUnit::Second, i64::MAX).to_json(), json!(null));
+        assert_eq!(
+            Value::TimestampTz(TimeUnit::Second, 1720070496, "+02:00".to_string()).to_json(),
+            json!("2024-07-04T07:21:36+02:00")
+        );
+        assert_eq!(Value::Time64(TimeUnit::Second, 3661).to_json(), json!("01:01:01"));
+        assert_eq!(Value::Decimal(Decimal::new(12345, 2)).to_json(), json!("123.45"));
+        let uuid = Uuid::from_str("58cb5e1d-5104-49c7-a983-f1dc53c3da84").unwrap();
+        assert_eq!(Value::Uuid(uuid).to_json(), json!("58cb5e1d-5104-49c7-a983-f1dc53c3da84"));
+        assert_eq!(Value::Json(r#"{"a":[1,2]}"#.to_string()).to_json(), json!({ "a": [1, 2] }));
+        assert_eq!(Value::Json("not json".to_string()).to_json(), json!("not json"));
+        assert_eq!(Value::List(vec![Value::Int32(1), Value::Null]).to_json(), json!([1, null]));
     }
 }
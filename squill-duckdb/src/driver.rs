@@ -1,17 +1,205 @@
+use crate::errors::driver_error;
 use crate::statement::DuckDBStatement;
-use squill_core::driver::{DriverConnection, DriverStatement, Result};
+use arrow_array::RecordBatch;
+use arrow_schema::DataType;
+use duckdb::vtab::arrow_recordbatch_to_query_params;
+use squill_core::decode::Decode;
+use squill_core::driver::{Described, DriverConnection, DriverStatement, Result};
+use squill_core::placeholders;
+use squill_core::quote::{check_identifier, quote_identifier, quote_literal};
+use squill_core::values::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::{DuckDB, DRIVER_NAME};
 
+/// The name of the table function used to read Arrow data.
+pub(crate) const ARROW_TABLE_FUNCTION: &str = "arrow";
+
+impl DuckDB {
+    /// Register a record batch as a table that can be queried by its name.
+    ///
+    /// The record batch is loaded into a temporary table, replacing any previously registered table of the same name.
+    /// The table is only visible from this connection and is dropped when the connection is closed or when calling
+    /// [DuckDB::unregister].
+    ///
+    /// ```rust,ignore
+    /// duckdb.register_arrow("my_table", batch)?;
+    /// let mut stmt = conn.prepare("SELECT * FROM my_table")?;
+    /// ```
+    pub fn register_arrow(&mut self, name: &str, batch: RecordBatch) -> Result<()> {
+        let params = arrow_recordbatch_to_query_params(batch);
+        self.conn.execute(
+            &format!(
+                "CREATE OR REPLACE TEMPORARY TABLE {} AS SELECT * FROM {}(?, ?)",
+                quote_identifier(DRIVER_NAME, name),
+                ARROW_TABLE_FUNCTION
+            ),
+            params,
+        )?;
+        Ok(())
+    }
+
+    /// Unregister a table previously registered with [DuckDB::register_arrow].
+    ///
+    /// Unregistering a table that does not exist is not an error.
+    pub fn unregister(&mut self, name: &str) -> Result<()> {
+        self.conn.execute(&format!("DROP TABLE IF EXISTS temp.main.{}", quote_identifier(DRIVER_NAME, name)), [])?;
+        Ok(())
+    }
+
+    /// Prepare a statement reading a CSV file.
+    ///
+    /// The `path` can be anything supported by the `read_csv` function of DuckDB (i.e. a glob pattern or an URL). The
+    /// options that are not set are detected by DuckDB from the content of the file.
+    ///
+    /// ```rust,ignore
+    /// let options = CsvOptions { delimiter: Some(';'), header: Some(true) };
+    /// let mut stmt = duckdb.query_csv("/tmp/employees.csv", &options)?;
+    /// let batches = stmt.query(None)?;
+    /// ```
+    pub fn query_csv<'c: 's, 's>(
+        &'c mut self,
+        path: &str,
+        options: &CsvOptions,
+    ) -> Result<Box<dyn DriverStatement + 's>> {
+        let mut args = vec![quote_literal(DRIVER_NAME, path)];
+        if let Some(delimiter) = options.delimiter {
+            args.push(format!("delim = {}", quote_literal(DRIVER_NAME, &delimiter.to_string())));
+        }
+        if let Some(header) = options.header {
+            args.push(format!("header = {}", header));
+        }
+        self.prepare(&format!("SELECT * FROM read_csv({})", args.join(", ")))
+    }
+
+    /// Prepare a statement reading a Parquet file.
+    ///
+    /// The `path` can be anything supported by the `read_parquet` function of DuckDB (i.e. a glob pattern or an URL).
+    pub fn query_parquet<'c: 's, 's>(&'c mut self, path: &str) -> Result<Box<dyn DriverStatement + 's>> {
+        self.prepare(&format!("SELECT * FROM read_parquet({})", quote_literal(DRIVER_NAME, path)))
+    }
+
+    /// Prepare a statement streaming the results of a query.
+    ///
+    /// By default (see [DriverConnection::prepare]) DuckDB materializes the whole result of a query in memory before
+    /// returning the first record batch. A streaming statement instead fetches the record batches one at a time while
+    /// iterating, trading some latency for a bounded memory usage on huge results.
+    ///
+    /// Unlike the other statements, the parameters must be given each time the statement is queried (the parameters
+    /// bound by a previous call are not reused).
+    ///
+    /// ```rust,ignore
+    /// let mut stmt = duckdb.query_streaming("SELECT * FROM range(100000000)")?;
+    /// for batch in stmt.query(None)? { ... }
+    /// ```
+    pub fn query_streaming<'c: 's, 's>(&'c mut self, statement: &str) -> Result<Box<dyn DriverStatement + 's>> {
+        // The schema is needed to read the record batches from the stream but DuckDB only knows it once the statement
+        // has been executed.
+        let schema = self.describe(statement)?.schema;
+        Ok(Box::new(DuckDBStatement {
+            inner: Rc::new(RefCell::new(self.conn.prepare(statement)?)),
+            streaming: Some(schema),
+        }))
+    }
+
+    /// Attach a SQLite database file so its tables can be queried as `alias.table`.
+    ///
+    /// The `sqlite` extension of DuckDB is installed (downloaded the first time) and loaded before attaching the
+    /// database, the file is created if it doesn't exist.
+    ///
+    /// ```rust,ignore
+    /// duckdb.attach_sqlite("legacy", "/tmp/legacy.db")?;
+    /// let mut stmt = conn.prepare("SELECT * FROM legacy.employees")?;
+    /// ```
+    pub fn attach_sqlite(&mut self, alias: &str, path: &str) -> Result<()> {
+        self.attach("sqlite", alias, path)
+    }
+
+    /// Attach a PostgreSQL database so its tables can be queried as `alias.schema.table`.
+    ///
+    /// The `uri` is either a URI (i.e. `postgresql://user@localhost/db`) or a libpq connection string (i.e.
+    /// `host=localhost dbname=db`). The `postgres` extension of DuckDB is installed (downloaded the first time) and
+    /// loaded before attaching the database.
+    pub fn attach_postgres(&mut self, alias: &str, uri: &str) -> Result<()> {
+        self.attach("postgres", alias, uri)
+    }
+
+    /// Detach a database previously attached with [DuckDB::attach_sqlite] or [DuckDB::attach_postgres].
+    pub fn detach(&mut self, alias: &str) -> Result<()> {
+        self.conn.execute_batch(&format!("DETACH DATABASE {}", quote_identifier(DRIVER_NAME, alias)))?;
+        Ok(())
+    }
+
+    /// Change the value of a setting (i.e. `threads` or `memory_limit`) for this connection.
+    ///
+    /// The name of the setting must be a plain identifier (see [squill_core::quote::check_identifier]) since it
+    /// cannot be given as a parameter of the statement, the value is given as a string and converted by DuckDB.
+    ///
+    /// ```rust,ignore
+    /// duckdb.set_setting("memory_limit", "1GB")?;
+    /// ```
+    pub fn set_setting(&mut self, name: &str, value: &str) -> Result<()> {
+        check_identifier(name)?;
+        self.conn.execute_batch(&format!("SET {} = {}", name, quote_literal(DRIVER_NAME, value)))?;
+        Ok(())
+    }
+
+    /// Get the current value of a setting.
+    ///
+    /// Returns `None` if the value of the setting is null, an error is returned if the setting doesn't exist.
+    pub fn current_setting(&self, name: &str) -> Result<Option<Value>> {
+        check_identifier(name)?;
+        let mut stmt = self.conn.prepare("SELECT current_setting(?)")?;
+        let batch = match stmt.query_arrow([name])?.next() {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+        match Value::try_decode(batch.column(0).as_ref(), 0)? {
+            Value::Null => Ok(None),
+            value => Ok(Some(value)),
+        }
+    }
+
+    /// Install and load an extension then attach a database using it.
+    fn attach(&mut self, extension: &str, alias: &str, target: &str) -> Result<()> {
+        self.conn.execute_batch(&format!(
+            "INSTALL {extension}; LOAD {extension}; ATTACH {} AS {} (TYPE {extension})",
+            quote_literal(DRIVER_NAME, target),
+            quote_identifier(DRIVER_NAME, alias),
+        ))?;
+        Ok(())
+    }
+}
+
+/// The options to read a CSV file with [DuckDB::query_csv].
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    /// The character separating the columns (detected if not set).
+    pub delimiter: Option<char>,
+
+    /// Whether the first line of the file contains the names of the columns (detected if not set).
+    pub header: Option<bool>,
+}
+
 impl DriverConnection for DuckDB {
     fn driver_name(&self) -> &str {
         DRIVER_NAME
     }
 
     fn prepare<'c: 's, 's>(&'c mut self, statement: &str) -> Result<Box<dyn DriverStatement + 's>> {
-        Ok(Box::new(DuckDBStatement { inner: Rc::new(RefCell::new(self.conn.prepare(statement)?)) }))
+        let inner = self.conn.prepare(statement).map_err(driver_error)?;
+        Ok(Box::new(DuckDBStatement { inner: Rc::new(RefCell::new(inner)), streaming: None }))
+    }
+
+    /// The types are given by casting the placeholders of the statement (i.e. `CAST(? AS BIGINT)`).
+    fn prepare_typed<'c: 's, 's>(
+        &'c mut self,
+        statement: &str,
+        types: &[DataType],
+    ) -> Result<Box<dyn DriverStatement + 's>> {
+        let types = types.iter().map(type_name).collect::<Result<Vec<String>>>()?;
+        self.prepare(&placeholders::cast(statement, &types))
     }
 
     /// Check if the connection is alive.
@@ -27,6 +215,19 @@ impl DriverConnection for DuckDB {
         }
     }
 
+    /// DuckDB only knows the schema of a statement once it has been executed, so the statement is wrapped into a query
+    /// returning no row which is executed with `NULL` bound to all the parameters. Only queries can be described.
+    fn describe(&mut self, statement: &str) -> Result<Described> {
+        let param_count = self.conn.prepare(statement)?.parameter_count();
+        let mut stmt =
+            self.conn.prepare(&format!("SELECT * FROM ({}) LIMIT 0", statement.trim().trim_end_matches(';')))?;
+        for index in 1..=param_count {
+            stmt.raw_bind_parameter(index, crate::values::Adapter(&Value::Null))?;
+        }
+        stmt.raw_execute()?;
+        Ok(Described { schema: stmt.schema(), param_count })
+    }
+
     fn close(self: std::boxed::Box<DuckDB>) -> Result<()> {
         let result = self.conn.close();
         match result {
@@ -36,11 +237,44 @@ impl DriverConnection for DuckDB {
     }
 }
 
+/// Get the name of the DuckDB type of the values of an Arrow data type.
+fn type_name(data_type: &DataType) -> Result<String> {
+    let name = match data_type {
+        DataType::Boolean => "BOOLEAN",
+        DataType::Int8 => "TINYINT",
+        DataType::Int16 => "SMALLINT",
+        DataType::Int32 => "INTEGER",
+        DataType::Int64 => "BIGINT",
+        DataType::UInt8 => "UTINYINT",
+        DataType::UInt16 => "USMALLINT",
+        DataType::UInt32 => "UINTEGER",
+        DataType::UInt64 => "UBIGINT",
+        DataType::Float32 => "FLOAT",
+        DataType::Float64 => "DOUBLE",
+        DataType::Utf8 | DataType::LargeUtf8 => "VARCHAR",
+        DataType::Binary | DataType::LargeBinary => "BLOB",
+        DataType::Date32 => "DATE",
+        DataType::Time64(_) => "TIME",
+        DataType::Timestamp(_, None) => "TIMESTAMP",
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMPTZ",
+        DataType::Interval(_) => "INTERVAL",
+        DataType::Decimal128(precision, scale) => return Ok(format!("DECIMAL({}, {})", precision, scale)),
+        _ => return Err(squill_core::Error::UnsupportedDataType { data_type: data_type.to_string() }.into()),
+    };
+    Ok(name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::IN_MEMORY_URI;
+    use crate::{CsvOptions, DuckDB, DuckDbConnectOptions, IN_MEMORY_URI};
+    use arrow_array::{Int64Array, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
     use ctor::ctor;
-    use squill_core::{assert_execute_eq, assert_ok, factory::Factory};
+    use squill_core::decode::Decode;
+    use squill_core::driver::AFFECTED_ROWS_COLUMN;
+    use squill_core::values::Value;
+    use squill_core::{assert_execute_eq, assert_ok, assert_some, assert_some_ok, factory::Factory, params};
+    use std::sync::Arc;
     use url::Url;
 
     #[ctor]
@@ -53,6 +287,19 @@ mod tests {
         assert_ok!(Factory::open(IN_MEMORY_URI));
     }
 
+    #[test]
+    fn test_open_shared_memory() {
+        let mut conn1 = assert_ok!(squill_blocking::Connection::open_shared_memory("duckdb", "shared db"));
+        let mut conn2 = assert_ok!(squill_blocking::Connection::open_shared_memory("duckdb", "shared db"));
+        assert_ok!(conn1.execute("CREATE TABLE employees (id BIGINT)", None));
+        assert_ok!(conn1.execute("INSERT INTO employees (id) VALUES (1), (2)", None));
+        assert_eq!(assert_ok!(conn2.query_scalar::<_, i64>("SELECT COUNT(*) FROM employees", None)), Some(2));
+
+        // Another name is another database.
+        let mut conn3 = assert_ok!(squill_blocking::Connection::open_shared_memory("duckdb", "other db"));
+        assert!(conn3.query_row("SELECT COUNT(*) FROM employees", None).is_err());
+    }
+
     #[test]
     fn test_ping() {
         assert_ok!(assert_ok!(Factory::open(IN_MEMORY_URI)).ping());
@@ -69,11 +316,98 @@ mod tests {
         // assert!(Factory::open(path_to_duckdb_uri("./invalid-path/invalid.db".into()).as_str()).is_err());
     }
 
+    #[test]
+    fn test_connect_options() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("my data #1?.db");
+        let mut conn = assert_ok!(DuckDbConnectOptions::new(&file_path).config("threads", "2").connect());
+        assert_execute_eq!(conn, "CREATE TABLE employee (id BIGINT)", 0);
+        assert_ok!(conn.close());
+        assert!(file_path.exists());
+
+        let mut conn = assert_ok!(DuckDbConnectOptions::new(&file_path).config("access_mode", "READ_ONLY").connect());
+        assert!(conn.prepare("INSERT INTO employee (id) VALUES (1)").and_then(|mut stmt| stmt.execute(None)).is_err());
+
+        assert_ok!(DuckDbConnectOptions::in_memory().config("max_memory", "1GB").connect());
+        assert!(DuckDbConnectOptions::in_memory().config("unknown_option", "1").connect().is_err());
+    }
+
+    #[test]
+    fn test_open_read_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("read_only.db");
+        let uri = format!("duckdb://{}", Factory::to_uri_path(&file_path));
+        let mut conn = assert_ok!(Factory::open(&uri));
+        assert_execute_eq!(conn, "CREATE TABLE employee (id BIGINT)", 0);
+        assert_ok!(conn.close());
+
+        for params in ["access_mode=read_only", "mode=ro"] {
+            let mut conn = assert_ok!(squill_blocking::Connection::open(&format!("{}?{}", uri, params)));
+            assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(0));
+            let error = conn.execute("INSERT INTO employee (id) VALUES (1)", None).unwrap_err();
+            assert!(matches!(error, squill_core::Error::ReadOnly { .. }), "unexpected error: {:?}", error);
+            assert!(error.to_string().contains("read-only"), "unexpected error: {}", error);
+        }
+
+        assert_ok!(Factory::open(&format!("{}?mode=rw", uri)));
+        assert!(matches!(Factory::open(&format!("{}?mode=rwc", uri)), Err(squill_core::Error::InvalidUri { .. })));
+    }
+
+    // Attaching a database requires DuckDB to download the extension the first time.
+    #[cfg(feature = "extensions-tests")]
+    #[test]
+    fn test_attach_sqlite() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("legacy.db");
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let duckdb = conn.downcast_mut::<DuckDB>().unwrap();
+        assert_ok!(duckdb.attach_sqlite("legacy", file_path.to_str().unwrap()));
+        assert_execute_eq!(conn, "CREATE TABLE legacy.employees (id BIGINT, name VARCHAR)", 0);
+        assert_execute_eq!(conn, "INSERT INTO legacy.employees VALUES (1, 'Alice'), (2, 'Bob')", 2);
+        assert_ok!(conn.downcast_mut::<DuckDB>().unwrap().detach("legacy"));
+        assert!(file_path.exists());
+
+        // The tables of the SQLite database are queried through DuckDB.
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.downcast_mut::<DuckDB>().unwrap().attach_sqlite("my db", file_path.to_str().unwrap()));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>(r#"SELECT name FROM "my db".employees WHERE id = 2"#, None)),
+            Some("Bob".to_string())
+        );
+        assert!(conn.downcast_mut::<DuckDB>().unwrap().attach_sqlite("other", "/missing/dir/db.sqlite").is_err());
+    }
+
     #[test]
     fn test_open_with_config() {
         let mut uri = Url::parse(IN_MEMORY_URI).unwrap();
         uri.query_pairs_mut().append_pair("max_memory", "2GB").append_pair("threads", "4");
-        assert_ok!(Factory::open(uri.as_str()));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(uri.as_str()));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT current_setting('threads')::BIGINT", None)), Some(4));
+
+        // Invalid settings
+        for (key, value) in [("max_memory", "lots"), ("threads", "0"), ("unknown_setting", "1")] {
+            let mut uri = Url::parse(IN_MEMORY_URI).unwrap();
+            uri.query_pairs_mut().append_pair(key, value);
+            assert!(matches!(
+                Factory::open(uri.as_str()),
+                Err(squill_core::Error::InvalidUri { reason, .. }) if reason.contains(key)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_statement_timeout() {
+        // DuckDB cannot abort a statement after a given time.
+        let uri = format!("{}?statement_timeout=100", IN_MEMORY_URI);
+        assert!(matches!(Factory::open(&uri), Err(squill_core::Error::InvalidUri { .. })));
+    }
+
+    #[test]
+    fn test_server_version() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let version = assert_ok!(conn.server_version());
+        assert!(!version.is_empty());
+        assert_eq!(assert_ok!(conn.server_version()), version);
     }
 
     #[test]
@@ -115,4 +449,293 @@ mod tests {
         }
         assert_eq!(num_rows, 5000);
     }
+
+    #[test]
+    fn test_fetch_all() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employees (id BIGINT, name VARCHAR)", None));
+
+        // The schema is returned even if there is no row.
+        let (schema, batches) = assert_ok!(conn.fetch_all("SELECT id, name FROM employees", None));
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert!(batches.is_empty());
+
+        assert_ok!(conn.execute(
+            "INSERT INTO employees SELECT id, 'Employee ' || id FROM generate_series(1, 5000) AS series(id)",
+            None
+        ));
+        let (schema, batches) = assert_ok!(conn.fetch_all("SELECT id, name FROM employees", None));
+        assert_eq!(schema.field(1).name(), "name");
+        assert!(batches.len() > 1);
+        assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 5000);
+    }
+
+    #[test]
+    fn test_query_dml() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employees (id BIGINT)", None));
+        let row = assert_some!(assert_ok!(conn.query_row("INSERT INTO employees (id) VALUES (?), (?)", params!(1, 2))));
+        assert_eq!(row.schema().field(0).name(), AFFECTED_ROWS_COLUMN);
+        assert_eq!(row.get::<_, i64>(AFFECTED_ROWS_COLUMN), 2);
+    }
+
+    #[test]
+    fn test_parameter_count() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_eq!(assert_ok!(conn.prepare("SELECT 1")).parameter_count(), 0);
+        let stmt = assert_ok!(conn.prepare("SELECT ?, ?"));
+        assert_eq!(stmt.parameter_count(), 2);
+        assert_eq!(stmt.parameter_types(), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employees (id BIGINT, name VARCHAR)", None));
+
+        let described = assert_ok!(conn.describe("SELECT id, name, ?::INTEGER AS age FROM employees WHERE id > ?"));
+        assert_eq!(described.param_count, 2);
+        assert_eq!(
+            described.schema.fields().iter().map(|field| field.data_type().clone()).collect::<Vec<_>>(),
+            vec![DataType::Int64, DataType::Utf8, DataType::Int32]
+        );
+        assert_eq!(described.schema.field(2).name(), "age");
+
+        assert!(conn.describe("SELECT * FROM unknown_table").is_err());
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employees AS SELECT range AS id FROM range(100)", None));
+        assert_ok!(conn.vacuum());
+        assert_ok!(conn.analyze(Some("main.employees")));
+        assert_ok!(conn.analyze(None));
+        assert_ok!(conn.optimize());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employees", None)), Some(100));
+    }
+
+    #[test]
+    fn test_register_arrow() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from(vec![2, 4, 6])), Arc::new(StringArray::from(vec!["two", "four", "six"]))],
+        )
+        .unwrap();
+
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        assert_ok!(assert_some!(conn.downcast_mut::<DuckDB>()).register_arrow("my_table", batch));
+        let mut stmt = assert_ok!(conn.prepare(
+            "SELECT series.id, my_table.name FROM generate_series(1, 5) AS series(id) \
+               JOIN my_table ON my_table.id = series.id ORDER BY series.id"
+        ));
+        let record_batch = assert_some_ok!(assert_ok!(stmt.query(None)).next());
+        assert_eq!(record_batch.num_rows(), 2);
+        assert_eq!(i64::decode(record_batch.column(0), 0), 2);
+        assert_eq!(String::decode(record_batch.column(1), 0), "two");
+        assert_eq!(i64::decode(record_batch.column(0), 1), 4);
+        assert_eq!(String::decode(record_batch.column(1), 1), "four");
+        drop(stmt);
+
+        assert_ok!(assert_some!(conn.downcast_mut::<DuckDB>()).unregister("my_table"));
+        assert!(conn.prepare("SELECT * FROM my_table").is_err());
+        // Unregistering a table that does not exist is not an error.
+        assert_ok!(assert_some!(conn.downcast_mut::<DuckDB>()).unregister("my_table"));
+    }
+
+    #[test]
+    fn test_query_csv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("employee's.csv");
+        std::fs::write(&file_path, "id;name\n1;Alice\n2;Bob\n").unwrap();
+        let path = file_path.to_str().unwrap();
+
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let duckdb = assert_some!(conn.downcast_mut::<DuckDB>());
+        let options = CsvOptions { delimiter: Some(';'), header: Some(true) };
+        let mut stmt = assert_ok!(duckdb.query_csv(path, &options));
+        let record_batch = assert_some_ok!(assert_ok!(stmt.query(None)).next());
+        assert_eq!(record_batch.num_rows(), 2);
+        assert_eq!(record_batch.schema().field(1).name(), "name");
+        assert_eq!(i64::decode(record_batch.column(0), 1), 2);
+        assert_eq!(String::decode(record_batch.column(1), 1), "Bob");
+        drop(stmt);
+
+        // Without a header, the first line is read as a row.
+        let options = CsvOptions { delimiter: Some(';'), header: Some(false) };
+        let mut stmt = assert_ok!(assert_some!(conn.downcast_mut::<DuckDB>()).query_csv(path, &options));
+        let record_batch = assert_some_ok!(assert_ok!(stmt.query(None)).next());
+        assert_eq!(record_batch.num_rows(), 3);
+        assert_eq!(String::decode(record_batch.column(1), 0), "name");
+    }
+
+    #[test]
+    fn test_query_parquet() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("employees.parquet");
+        let path = file_path.to_str().unwrap();
+
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        assert_execute_eq!(
+            conn,
+            &format!(
+                "COPY (SELECT id::BIGINT AS id, 'Employee ' || id AS name FROM generate_series(1, 10) AS series(id)) \
+                   TO '{}' (FORMAT PARQUET)",
+                path
+            ),
+            10
+        );
+        let mut stmt = assert_ok!(assert_some!(conn.downcast_mut::<DuckDB>()).query_parquet(path));
+        let record_batch = assert_some_ok!(assert_ok!(stmt.query(None)).next());
+        assert_eq!(record_batch.num_rows(), 10);
+        assert_eq!(i64::decode(record_batch.column(0), 9), 10);
+        assert_eq!(String::decode(record_batch.column(1), 0), "Employee 1");
+        drop(stmt);
+
+        // Reading a file that does not exist is an error.
+        let missing = temp_dir.path().join("missing.parquet");
+        assert!(assert_some!(conn.downcast_mut::<DuckDB>()).query_parquet(missing.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_query_streaming() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let duckdb = assert_some!(conn.downcast_mut::<DuckDB>());
+        let mut stmt = assert_ok!(duckdb.query_streaming("SELECT id FROM generate_series(1, ?) AS series(id)"));
+        assert_eq!(stmt.schema().field(0).name(), "id");
+
+        // The rows are fetched in many small record batches instead of being materialized all at once.
+        for _ in 0..2 {
+            let (mut batches, mut rows, mut max_rows) = (0, 0, 0);
+            for batch in assert_ok!(stmt.query(params!(1_000_000_i64))) {
+                let batch = assert_ok!(batch);
+                batches += 1;
+                rows += batch.num_rows();
+                max_rows = max_rows.max(batch.num_rows());
+            }
+            assert_eq!(rows, 1_000_000);
+            assert!(batches > 1);
+            assert!(max_rows < rows);
+        }
+
+        // A stream dropped before the end doesn't prevent the statement from being queried again.
+        let mut batches = assert_ok!(stmt.query(params!(1_000_000_i64)));
+        assert_eq!(i64::decode(assert_some_ok!(batches.next()).column(0), 0), 1);
+        drop(batches);
+        let rows: usize = assert_ok!(stmt.query(params!(10_i64))).map(|batch| assert_ok!(batch).num_rows()).sum();
+        assert_eq!(rows, 10);
+
+        // Without parameters, `NULL` is bound to the placeholders as for a statement that is not streaming.
+        let rows: usize = assert_ok!(stmt.query(None)).map(|batch| assert_ok!(batch).num_rows()).sum();
+        assert_eq!(rows, 0);
+        drop(stmt);
+
+        // The first record batch is returned before the query reaches the rows failing at the end of the series,
+        // something a statement that is not streaming cannot do since it produces all the rows before returning.
+        let failing = "SELECT CASE WHEN id < 900000 THEN id ELSE error('too far') END AS id \
+                       FROM generate_series(1, 1000000) AS series(id)";
+        let mut stmt = assert_ok!(duckdb.query_streaming(failing));
+        let mut batches = assert_ok!(stmt.query(None));
+        assert_eq!(i64::decode(assert_some_ok!(batches.next()).column(0), 0), 1);
+        assert!(batches.any(|batch| batch.is_err()));
+        drop(batches);
+        drop(stmt);
+
+        let mut stmt = assert_ok!(conn.prepare(failing));
+        if let Ok(mut batches) = stmt.query(None) {
+            assert!(matches!(batches.next(), Some(Err(_))));
+        }
+    }
+
+    #[test]
+    fn test_prepare_typed() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        // The placeholders are cast to the given types.
+        let mut stmt = assert_ok!(conn.prepare_typed("SELECT ? + ?", &[DataType::Int64, DataType::Int64]));
+        let batch = assert_some_ok!(assert_ok!(stmt.query(params!(40_i64, 2_i64))).next());
+        assert_eq!(i64::decode(batch.column(0), 0), 42);
+        drop(stmt);
+
+        assert!(matches!(
+            conn.prepare_typed("SELECT ?", &[DataType::Float16]),
+            Err(squill_core::Error::UnsupportedDataType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_on_slow_query() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let reported = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let reported_clone = reported.clone();
+        conn.on_slow_query(std::time::Duration::from_millis(1), move |statement, elapsed| {
+            reported_clone.borrow_mut().push((statement.to_string(), elapsed));
+        });
+
+        // A cross join of 25 million rows takes well over a millisecond.
+        let count = assert_some!(assert_ok!(conn.query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM range(5000) AS a\n  CROSS JOIN range(5000) AS b WHERE (a.range * b.range) % 7 = 3",
+            None
+        )));
+        assert!(count > 0);
+        assert_eq!(reported.borrow().len(), 1);
+        assert_eq!(
+            reported.borrow()[0].0,
+            "SELECT COUNT(*) FROM range(5000) AS a  CROSS JOIN range(5000) AS b WHERE (a.range * b.range) % 7 = 3"
+        );
+        assert!(reported.borrow()[0].1 > std::time::Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_query_with_schema() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT id, 'name' || id AS name FROM range(?) AS t(id)"));
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(params!(3_i64)));
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(schema.field(1).name(), "name");
+        assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+        assert_eq!(batches.map(|batch| assert_ok!(batch).num_rows()).sum::<usize>(), 3);
+
+        // The schema is known before iterating, even if the query returns no rows.
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(params!(0_i64)));
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(batches.map(|batch| assert_ok!(batch).num_rows()).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let duckdb = conn.downcast_mut::<DuckDB>().unwrap();
+        assert_ok!(duckdb.set_setting("threads", "2"));
+        assert_eq!(assert_ok!(duckdb.current_setting("threads")), Some(Value::Int64(2)));
+
+        assert!(duckdb.current_setting("unknown_setting").is_err());
+        assert!(duckdb.set_setting("threads = 1; DROP TABLE t; SET threads", "2").is_err());
+        assert!(duckdb.current_setting("threads;").is_err());
+    }
+
+    #[test]
+    fn test_binding_list() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        assert_execute_eq!(conn, "CREATE TABLE employee (id BIGINT, team_ids INTEGER[])", 0);
+        assert_execute_eq!(conn, "INSERT INTO employee VALUES (1, [10, 20]), (2, [30]), (3, [10]), (4, [])", 4);
+
+        // The list is bound as text, so the placeholder is cast into a list.
+        let mut stmt = assert_ok!(conn.prepare("SELECT COUNT(*) FROM employee WHERE list_contains(?::BIGINT[], id)"));
+        let batch = assert_some_ok!(assert_ok!(stmt.query(params!(vec![1_i64, 3, 5]))).next());
+        assert_eq!(i64::decode(batch.column(0), 0), 2);
+        drop(stmt);
+
+        // A list bound to an array column.
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO employee VALUES (5, ?::INTEGER[])"));
+        assert_eq!(assert_ok!(stmt.execute(params!(vec![10, 40]))), 1);
+        drop(stmt);
+        let mut stmt = assert_ok!(conn.prepare("SELECT COUNT(*) FROM employee WHERE list_contains(team_ids, ?)"));
+        let batch = assert_some_ok!(assert_ok!(stmt.query(params!(10))).next());
+        assert_eq!(i64::decode(batch.column(0), 0), 3);
+    }
 }
@@ -0,0 +1,15 @@
+use squill_core::driver::DriverError;
+use squill_core::error::Error;
+
+/// Convert a `duckdb::Error` into a [DriverError].
+///
+/// DuckDB doesn't give a specific code to its errors, a write on a read-only database is recognized by its message
+/// and reported as [Error::ReadOnly]. The other errors are returned as is.
+pub(crate) fn driver_error(duckdb_error: duckdb::Error) -> DriverError {
+    match &duckdb_error {
+        duckdb::Error::DuckDBFailure(_, Some(message)) if message.contains("read-only mode") => {
+            Box::new(Error::ReadOnly { error: Box::new(duckdb_error) })
+        }
+        _ => Box::new(duckdb_error),
+    }
+}
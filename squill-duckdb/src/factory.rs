@@ -1,8 +1,23 @@
+use crate::driver::ARROW_TABLE_FUNCTION;
 use crate::DuckDB;
 use crate::IN_MEMORY_URI_PATH;
+use duckdb::vtab::ArrowVTab;
 use squill_core::driver::Result;
 use squill_core::driver::{DriverConnection, DriverFactory, DriverOptionsRef};
+use squill_core::uri::{decode_component, redact_uri, STATEMENT_TIMEOUT_PARAM};
 use squill_core::Error;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// The named in-memory databases (`duckdb:///:memory:<name>`), each one kept open by a connection that is cloned for
+/// every connection opened with the same name.
+static SHARED_MEMORY_DATABASES: Mutex<BTreeMap<String, duckdb::Connection>> = Mutex::new(BTreeMap::new());
+
+/// The URI parameter giving the access mode of the database (`ro` for read-only or `rw` for read-write).
+const MODE_PARAM: &str = "mode";
+
+/// The DuckDB setting giving the access mode of the database (`automatic`, `read_only` or `read_write`).
+const ACCESS_MODE_SETTING: &str = "access_mode";
 
 pub(crate) struct DuckDBFactory {}
 
@@ -14,15 +29,56 @@ impl DriverFactory for DuckDBFactory {
     /// Open a connection to a DuckDB database.
     ///
     /// The `options` parameter is not used because the DuckDB build the record batch by itself.
+    ///
+    /// DuckDB doesn't provide a way to abort a statement after a given time, the `statement_timeout` parameter is
+    /// therefore rejected rather than silently ignored.
+    ///
+    /// A database file can be opened read-only with `access_mode=read_only` (or `mode=ro`), any statement trying to
+    /// write to the database then fails.
+    ///
+    /// A named in-memory database (i.e. `duckdb:///:memory:my_db`) is shared by all the connections opened with the
+    /// same name, it's kept in memory until the end of the process and the settings are only applied by the first
+    /// connection.
     fn open(&self, uri: &str, _options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>> {
         let parsed_uri =
-            url::Url::parse(uri).map_err(|e| Error::InvalidUri { uri: uri.to_string(), reason: e.to_string() })?;
-        let mut path = parsed_uri.path();
-        // Initialization of the configuration from the URI query parameters
+            url::Url::parse(uri).map_err(|e| Error::InvalidUri { uri: redact_uri(uri), reason: e.to_string() })?;
+        // The path is percent-encoded in the URI.
+        let decoded_path = decode_component(parsed_uri.path());
+        let mut path = decoded_path.as_str();
+        // Initialization of the configuration from the URI query parameters, the settings are applied when the database
+        // is opened and unknown settings are rejected by DuckDB.
         // See: https://duckdb.org/docs/configuration/overview.html#configuration-reference
         let mut config = duckdb::Config::default();
         for (key, value) in parsed_uri.query_pairs() {
-            config = config.with(key.as_ref(), value.as_ref())?;
+            if key == STATEMENT_TIMEOUT_PARAM {
+                return Err(Error::InvalidUri {
+                    uri: redact_uri(uri),
+                    reason: format!("{} is not supported by DuckDB", STATEMENT_TIMEOUT_PARAM),
+                }
+                .into());
+            }
+            // `mode=ro` and `mode=rw` are accepted as shorthands for the `access_mode` setting, like for SQLite.
+            let (key, value) = match (key.as_ref(), value.as_ref()) {
+                (MODE_PARAM, "ro") => (ACCESS_MODE_SETTING.into(), "read_only".into()),
+                (MODE_PARAM, "rw") => (ACCESS_MODE_SETTING.into(), "read_write".into()),
+                (MODE_PARAM, _) => {
+                    return Err(Error::InvalidUri {
+                        uri: redact_uri(uri),
+                        reason: format!("Invalid value for {}: expecting 'ro' or 'rw', got '{}'", MODE_PARAM, value),
+                    }
+                    .into())
+                }
+                _ => (key, value),
+            };
+            config = validate_setting(key.as_ref(), value.as_ref())
+                .and_then(|_| config.with(key.as_ref(), value.as_ref()).map_err(|e| e.to_string()))
+                .map_err(|reason| Error::InvalidUri {
+                    uri: redact_uri(uri),
+                    reason: format!("Invalid DuckDB setting '{}': {}", key, reason),
+                })?;
+        }
+        if let Some(name) = decoded_path.strip_prefix(IN_MEMORY_URI_PATH).filter(|name| !name.is_empty()) {
+            return Ok(Box::new(DuckDB { conn: open_shared_memory(name, config)? }));
         }
         if parsed_uri.path() == IN_MEMORY_URI_PATH {
             // The path is the URI starts with a `/` but the duckdb::Connection::open_with_flags expects just ":memory:"
@@ -34,6 +90,77 @@ impl DriverFactory for DuckDBFactory {
             path = path.char_indices().nth(1).map_or("", |(i, _)| &path[i..]);
         }
         let conn = duckdb::Connection::open_with_flags(path, config)?;
+        // The `arrow` table function is used to register Arrow data (see `DuckDB::register_arrow`).
+        conn.register_table_function::<ArrowVTab>(ARROW_TABLE_FUNCTION)?;
         Ok(Box::new(DuckDB { conn }))
     }
 }
+
+/// Open a connection to a named in-memory database, creating the database if it doesn't exist yet.
+fn open_shared_memory(name: &str, config: duckdb::Config) -> Result<duckdb::Connection> {
+    let mut databases =
+        SHARED_MEMORY_DATABASES.lock().map_err(|e| Error::InternalError { error: e.to_string().into() })?;
+    if let Some(conn) = databases.get(name) {
+        return Ok(conn.try_clone()?);
+    }
+    let conn = duckdb::Connection::open_with_flags(":memory:", config)?;
+    // The table functions are registered in the catalog of the database, so only once for all the connections.
+    conn.register_table_function::<ArrowVTab>(ARROW_TABLE_FUNCTION)?;
+    let shared_conn = conn.try_clone()?;
+    databases.insert(name.to_string(), conn);
+    Ok(shared_conn)
+}
+
+/// Validate the value of the well-known settings before they are given to DuckDB.
+///
+/// DuckDB only reports an invalid value when the database is opened, with an error that doesn't always tell which
+/// setting is invalid.
+fn validate_setting(key: &str, value: &str) -> std::result::Result<(), String> {
+    match key.to_ascii_lowercase().as_str() {
+        "threads" | "worker_threads" => match value.trim().parse::<u64>() {
+            Ok(threads) if threads > 0 => Ok(()),
+            _ => Err(format!("expecting a positive number of threads, got '{}'", value)),
+        },
+        "memory_limit" | "max_memory" => {
+            // A size such as `2GB`, `512 MiB` or `-1` for no limit.
+            let value = value.trim();
+            let unit_start = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+            let (number, unit) = value.split_at(unit_start);
+            let valid_unit = matches!(
+                unit.trim().to_ascii_lowercase().as_str(),
+                "" | "b" | "bytes" | "kb" | "mb" | "gb" | "tb" | "kib" | "mib" | "gib" | "tib" | "%"
+            );
+            if value == "-1" || (number.parse::<f64>().is_ok() && valid_unit) {
+                Ok(())
+            } else {
+                Err(format!("expecting a memory size (e.g. '2GB'), got '{}'", value))
+            }
+        }
+        ACCESS_MODE_SETTING => match value.to_ascii_lowercase().as_str() {
+            "automatic" | "read_only" | "read_write" => Ok(()),
+            _ => Err(format!("expecting AUTOMATIC, READ_ONLY or READ_WRITE, got '{}'", value)),
+        },
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_setting;
+
+    #[test]
+    fn test_validate_setting() {
+        assert!(validate_setting("threads", "4").is_ok());
+        assert!(validate_setting("threads", "0").is_err());
+        assert!(validate_setting("worker_threads", "four").is_err());
+        assert!(validate_setting("max_memory", "2GB").is_ok());
+        assert!(validate_setting("memory_limit", "512 MiB").is_ok());
+        assert!(validate_setting("memory_limit", "1.5gb").is_ok());
+        assert!(validate_setting("memory_limit", "-1").is_ok());
+        assert!(validate_setting("memory_limit", "lots").is_err());
+        assert!(validate_setting("memory_limit", "2XB").is_err());
+        assert!(validate_setting("access_mode", "read_only").is_ok());
+        assert!(validate_setting("access_mode", "write").is_err());
+        assert!(validate_setting("default_order", "DESC").is_ok());
+    }
+}
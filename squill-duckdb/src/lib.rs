@@ -2,10 +2,15 @@
 use squill_core::factory::Factory;
 
 mod driver;
+mod errors;
 mod factory;
+mod options;
 mod statement;
 mod values;
 
+pub use driver::CsvOptions;
+pub use options::DuckDbConnectOptions;
+
 /// The name of the driver for DuckDB.
 pub const DRIVER_NAME: &str = "duckdb";
 
@@ -21,7 +26,16 @@ pub const IN_MEMORY_URI_PATH: &str = "/:memory:";
 /// ```
 pub const IN_MEMORY_URI: &str = "duckdb:///:memory:";
 
-pub(crate) struct DuckDB {
+/// A connection to a DuckDB database.
+///
+/// This type is not intended to be used directly but it gives access to the features specific to DuckDB when
+/// downcasting a connection (see [squill_core::driver::DriverConnection]).
+///
+/// ```rust,ignore
+/// let mut conn = Factory::open(IN_MEMORY_URI)?;
+/// conn.downcast_mut::<DuckDB>().unwrap().register_arrow("my_table", batch)?;
+/// ```
+pub struct DuckDB {
     conn: duckdb::Connection,
 }
 
@@ -0,0 +1,62 @@
+use crate::{register_driver, DRIVER_NAME, IN_MEMORY_URI_PATH};
+use squill_core::driver::DriverConnection;
+use squill_core::factory::Factory;
+use squill_core::uri::{encode_path, encode_query};
+use squill_core::Result;
+use std::path::PathBuf;
+
+/// A builder for the URI of a DuckDB database.
+///
+/// The path and the configuration options are encoded as expected in an URI, so any file name can be used. A relative
+/// path is made absolute using the current directory.
+///
+/// ```rust
+/// use squill_duckdb::DuckDbConnectOptions;
+///
+/// let options = DuckDbConnectOptions::new("/tmp/my data.db").config("threads", "4");
+/// assert_eq!(options.to_uri(), "duckdb:///tmp/my%20data.db?threads=4");
+/// assert_eq!(DuckDbConnectOptions::in_memory().to_uri(), "duckdb:///:memory:");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DuckDbConnectOptions {
+    path: String,
+    config: Vec<(String, String)>,
+}
+
+impl DuckDbConnectOptions {
+    /// Create the options to open the database file at the given path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let path = std::path::absolute(&path).unwrap_or(path);
+        Self { path: Factory::to_uri_path(&path), config: Vec::new() }
+    }
+
+    /// Create the options to open an in-memory database.
+    pub fn in_memory() -> Self {
+        Self { path: IN_MEMORY_URI_PATH.to_string(), config: Vec::new() }
+    }
+
+    /// Set a configuration option (i.e. `threads`, `max_memory`, `access_mode`), replacing any previous value.
+    ///
+    /// See [https://duckdb.org/docs/configuration/overview.html](https://duckdb.org/docs/configuration/overview.html)
+    /// for the options supported by DuckDB.
+    pub fn config<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let key = key.into();
+        self.config.retain(|(k, _)| *k != key);
+        self.config.push((key, value.into()));
+        self
+    }
+
+    /// Get the URI of the database.
+    pub fn to_uri(&self) -> String {
+        format!("{}://{}{}", DRIVER_NAME, encode_path(&self.path), encode_query(&self.config))
+    }
+
+    /// Open a connection to the database.
+    ///
+    /// The DuckDB driver is registered if it was not already.
+    pub fn connect(&self) -> Result<Box<dyn DriverConnection>> {
+        register_driver();
+        Factory::open(&self.to_uri())
+    }
+}
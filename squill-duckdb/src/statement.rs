@@ -1,17 +1,30 @@
+use crate::errors::driver_error;
 use arrow_array::RecordBatch;
 use arrow_schema::SchemaRef;
 use squill_core::driver::{DriverStatement, Result};
 use squill_core::parameters::Parameters;
+use squill_core::values::Value;
 use squill_core::Error;
 use std::{cell::RefCell, rc::Rc};
 
 #[derive(Clone)]
 pub(crate) struct DuckDBStatement<'c> {
     pub(crate) inner: Rc<RefCell<duckdb::Statement<'c>>>,
+
+    /// The schema of the results if the statement is streaming them (see [crate::DuckDB::query_streaming]).
+    pub(crate) streaming: Option<SchemaRef>,
 }
 
 impl DuckDBStatement<'_> {
-    fn bind(&mut self, parameters: Parameters) -> Result<()> {
+    /// Bind the parameters of an execution.
+    ///
+    /// The number of parameters must match the number of placeholders, so every parameter is bound again and no value
+    /// bound by a previous execution can leak into this one. When an execution is given no parameters at all, the
+    /// previous bindings are cleared (see [DriverStatement::reset]) so `NULL` is bound to every placeholder.
+    fn bind(&mut self, parameters: Option<Parameters>) -> Result<()> {
+        let Some(parameters) = parameters else {
+            return self.reset();
+        };
         let mut inner = self.inner.borrow_mut();
         let expected = inner.parameter_count();
         match parameters {
@@ -26,18 +39,44 @@ impl DuckDBStatement<'_> {
                 }
                 Ok(())
             }
+            Parameters::Named(_) => {
+                Err(Error::DriverError { error: "Named parameters are not supported by DuckDB".into() }.into())
+            }
         }
     }
+
+    /// Execute the statement in streaming mode, the record batches being fetched while iterating.
+    fn query_streaming<'s>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+        schema: SchemaRef,
+    ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>> {
+        let values = match parameters {
+            Some(Parameters::Positional(values)) => values,
+            Some(Parameters::Named(_)) => {
+                return Err(Error::DriverError { error: "Named parameters are not supported by DuckDB".into() }.into())
+            }
+            // Same as `bind`, `NULL` is bound to every placeholder.
+            None => vec![Value::Null; self.inner.borrow().parameter_count()],
+        };
+        // The iterators of the previous queries borrowed the statement mutably, so they are dropped by now and the
+        // statement is no longer shared.
+        let inner = Rc::get_mut(&mut self.inner)
+            .ok_or_else(|| Error::InternalError { error: "The statement is still in use.".into() })?
+            .get_mut();
+        let stream = inner
+            .stream_arrow(duckdb::params_from_iter(values.iter().map(crate::values::Adapter)), schema)
+            .map_err(driver_error)?;
+        Ok(Box::new(stream.map(Ok)))
+    }
 }
 
 impl DriverStatement for DuckDBStatement<'_> {
     fn execute(&mut self, parameters: Option<Parameters>) -> Result<u64> {
-        if let Some(parameters) = parameters {
-            self.bind(parameters)?;
-        }
+        self.bind(parameters)?;
         match self.inner.borrow_mut().raw_execute() {
             Ok(affected_rows) => Ok(affected_rows as u64),
-            Err(error) => Err(error.into()),
+            Err(error) => Err(driver_error(error)),
         }
     }
 
@@ -45,19 +84,43 @@ impl DriverStatement for DuckDBStatement<'_> {
         &'s mut self,
         parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>> {
-        if let Some(parameters) = parameters {
-            self.bind(parameters)?;
+        Ok(self.query_with_schema(parameters)?.1)
+    }
+
+    /// The schema of a statement is only known by DuckDB once it has been executed.
+    fn query_with_schema<'s>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+    ) -> Result<(SchemaRef, Box<dyn Iterator<Item = Result<RecordBatch>> + 's>)> {
+        if let Some(schema) = self.streaming.clone() {
+            return Ok((schema.clone(), self.query_streaming(parameters, schema)?));
         }
-        match self.inner.borrow_mut().raw_execute() {
-            Ok(_affected_rows) => Ok(Box::new(self.clone())),
-            Err(error) => Err(error.into()),
+        self.bind(parameters)?;
+        self.inner.borrow_mut().raw_execute().map_err(driver_error)?;
+        let schema = self.inner.borrow().schema();
+        Ok((schema, Box::new(self.clone())))
+    }
+
+    /// Clear the bindings by binding `NULL` to all the parameters.
+    fn reset(&mut self) -> Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        for index in 1..=inner.parameter_count() {
+            inner.raw_bind_parameter(index, crate::values::Adapter(&Value::Null))?;
         }
+        Ok(())
     }
 
     fn schema(&self) -> SchemaRef {
+        if let Some(schema) = &self.streaming {
+            return schema.clone();
+        }
         let schema = self.inner.borrow().schema();
         schema
     }
+
+    fn parameter_count(&self) -> usize {
+        self.inner.borrow().parameter_count()
+    }
 }
 
 /// Iterator over the record batches.
@@ -66,6 +129,9 @@ impl DriverStatement for DuckDBStatement<'_> {
 /// Because with DuckDB we are directly accessing the record batches from the statement execution, we don't need to
 /// expose the Row level as we would do with other drivers. This is why this iterator is also the statement itself and
 /// why {@link DuckDBStatement::query} returns a clone of itself as the iterator.
+///
+/// The iterator can be dropped before being exhausted, the remaining record batches are discarded by the next
+/// execution of the statement.
 impl<'conn> Iterator for DuckDBStatement<'conn> {
     type Item = Result<arrow_array::RecordBatch>;
 
@@ -310,6 +376,51 @@ mod tests {
         assert_eq!(Uuid::decode(batch.column(4), 0), Uuid::parse_str("0e089c07-8654-4aab-9c25-4f3c44590251").unwrap())
     }
 
+    #[test]
+    fn test_binding_uuid() {
+        let uuid = Uuid::parse_str("0e089c07-8654-4aab-9c25-4f3c44590251").unwrap();
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        assert_execute_eq!(conn, "CREATE TABLE test_uuid (id UUID)", 0);
+        assert_execute_eq!(conn, "INSERT INTO test_uuid (id) VALUES (?)", &[&uuid], 1);
+        assert_execute_eq!(conn, "INSERT INTO test_uuid (id) VALUES (?)", &[&Uuid::nil()], 1);
+        let mut stmt = assert_ok!(conn.prepare("SELECT id FROM test_uuid ORDER BY id NULLS LAST"));
+        let mut iter = assert_ok!(stmt.query(None));
+        let batch = assert_some_ok!(iter.next());
+        assert_eq!(Uuid::decode(batch.column(0), 0), uuid);
+        assert!(batch.column(0).is_null(1));
+    }
+
+    #[test]
+    fn test_binding_json() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT json_extract_string(?::JSON, '$.a')"));
+        let mut iter = assert_ok!(stmt.query(params!(Value::Json(r#"{"a": "hello"}"#.to_string()))));
+        let batch = assert_some_ok!(iter.next());
+        assert_eq!(String::decode(batch.column(0), 0), "hello");
+    }
+
+    #[test]
+    fn test_binding_time_and_list() {
+        let time = NaiveTime::from_hms_micro_opt(13, 20, 10, 101_202).unwrap();
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT ?::TIME, len(?::INTEGER[]), (?::INTEGER[])[2]"));
+        let mut iter = assert_ok!(stmt.query(params!(time, vec![1, 2, 3], vec![4, 5, 6])));
+        let batch = assert_some_ok!(iter.next());
+        assert_eq!(NaiveTime::decode(batch.column(0), 0), time);
+        assert_eq!(i64::decode(batch.column(1), 0), 3);
+        assert_eq!(i32::decode(batch.column(2), 0), 5);
+    }
+
+    #[test]
+    fn test_struct() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT {'a': 1, 'b': 'x'} AS s"));
+        let mut iter = assert_ok!(stmt.query(None));
+        let batch = assert_some_ok!(iter.next());
+        assert!(matches!(batch.column(0).data_type(), arrow_schema::DataType::Struct(_)));
+        assert_eq!(<(i32, String)>::decode(batch.column(0), 0), (1, "x".to_string()));
+    }
+
     #[test]
     fn test_binding_null() {
         let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
@@ -333,6 +444,7 @@ mod tests {
         assert!(batch.column(2).is_null(0));
     }
 
+    // Each query binds all the parameters again, the bindings of the previous query are replaced.
     #[test]
     fn test_rebinding_statement() {
         let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
@@ -343,6 +455,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rebinding_replaces_all_parameters() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT ?::INTEGER, ?::VARCHAR"));
+        let mut rows = assert_ok!(stmt.query(params!(1, "A")));
+        let batch = assert_some_ok!(rows.next());
+        assert_eq!((i32::decode(batch.column(0), 0), String::decode(batch.column(1), 0)), (1, "A".to_string()));
+        drop(rows);
+
+        let mut rows = assert_ok!(stmt.query(params!(2, "B")));
+        let batch = assert_some_ok!(rows.next());
+        assert_eq!((i32::decode(batch.column(0), 0), String::decode(batch.column(1), 0)), (2, "B".to_string()));
+        drop(rows);
+
+        // A partial binding is rejected instead of being completed by the previous bindings.
+        assert!(stmt.query(params!(3)).is_err());
+
+        // Without parameters, the bindings of the last query are cleared.
+        let mut rows = assert_ok!(stmt.query(None));
+        let batch = assert_some_ok!(rows.next());
+        assert!(batch.column(0).is_null(0) && batch.column(1).is_null(0));
+        drop(rows);
+        assert_eq!(assert_ok!(stmt.execute(None)), 1);
+    }
+
+    #[test]
+    fn test_drop_partially_consumed_rows() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        // The results are returned by chunks of 2048 rows.
+        let mut stmt = assert_ok!(conn.prepare("SELECT * FROM range(10000) AS t(value) WHERE value >= ?"));
+        let mut rows = assert_ok!(stmt.query(params!(0)));
+        assert_eq!(i64::decode(assert_some_ok!(rows.next()).column(0), 0), 0);
+        drop(rows);
+
+        let rows = assert_ok!(stmt.query(params!(5000)));
+        let batches = rows.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(i64::decode(batches[0].column(0), 0), 5000);
+        assert_eq!(batches.iter().map(|batch| batch.num_rows()).sum::<usize>(), 5000);
+        drop(stmt);
+        assert_execute_eq!(conn, "CREATE TABLE employee (id BIGINT)", 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare("SELECT * FROM range(10) AS t(value) WHERE value > ?"));
+        let mut rows = assert_ok!(stmt.query(params!(5)));
+        assert_eq!(i64::decode(assert_some_ok!(rows.next()).column(0), 0), 6);
+        drop(rows);
+
+        // The parameters are cleared by the reset.
+        assert_ok!(stmt.reset());
+        assert_eq!(assert_ok!(stmt.query(None)).map(|batch| batch.unwrap().num_rows()).sum::<usize>(), 0);
+        let mut rows = assert_ok!(stmt.query(params!(7)));
+        assert_eq!(i64::decode(assert_some_ok!(rows.next()).column(0), 0), 8);
+    }
+
     #[test]
     fn test_schema() {
         let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
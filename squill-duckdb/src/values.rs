@@ -44,18 +44,31 @@ impl<'a> duckdb::ToSql for Adapter<'a> {
             }
 
             // Timestamp
-            Value::Timestamp(TimeUnit::Second, value) => Ok(duckdb::types::ToSqlOutput::Owned(
-                duckdb::types::Value::Timestamp(duckdb::types::TimeUnit::Second, *value),
-            )),
-            Value::Timestamp(TimeUnit::Millisecond, value) => Ok(duckdb::types::ToSqlOutput::Owned(
-                duckdb::types::Value::Timestamp(duckdb::types::TimeUnit::Millisecond, *value),
-            )),
-            Value::Timestamp(TimeUnit::Microsecond, value) => Ok(duckdb::types::ToSqlOutput::Owned(
-                duckdb::types::Value::Timestamp(duckdb::types::TimeUnit::Microsecond, *value),
-            )),
-            Value::Timestamp(TimeUnit::Nanosecond, value) => Ok(duckdb::types::ToSqlOutput::Owned(
-                duckdb::types::Value::Timestamp(duckdb::types::TimeUnit::Nanosecond, *value),
-            )),
+            // The value of a timestamp with a time zone is the instant in UTC, the time zone itself is not bound.
+            Value::Timestamp(TimeUnit::Second, value) | Value::TimestampTz(TimeUnit::Second, value, _) => {
+                Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Timestamp(
+                    duckdb::types::TimeUnit::Second,
+                    *value,
+                )))
+            }
+            Value::Timestamp(TimeUnit::Millisecond, value) | Value::TimestampTz(TimeUnit::Millisecond, value, _) => {
+                Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Timestamp(
+                    duckdb::types::TimeUnit::Millisecond,
+                    *value,
+                )))
+            }
+            Value::Timestamp(TimeUnit::Microsecond, value) | Value::TimestampTz(TimeUnit::Microsecond, value, _) => {
+                Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Timestamp(
+                    duckdb::types::TimeUnit::Microsecond,
+                    *value,
+                )))
+            }
+            Value::Timestamp(TimeUnit::Nanosecond, value) | Value::TimestampTz(TimeUnit::Nanosecond, value, _) => {
+                Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Timestamp(
+                    duckdb::types::TimeUnit::Nanosecond,
+                    *value,
+                )))
+            }
 
             // Time64
             // duckdb::types::Value::Time64 is not supported by duckdb-rs 0.10.2 for binding parameters but we can use a
@@ -79,6 +92,24 @@ impl<'a> duckdb::ToSql for Adapter<'a> {
             Value::Decimal(_value) => {
                 Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Text(self.0.to_string())))
             }
+
+            // Uuid
+            // Bound as text, DuckDB will cast it when the type of the parameter is known to be an UUID.
+            Value::Uuid(value) => Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Text(value.to_string()))),
+
+            // Json
+            // DuckDB's JSON type is a VARCHAR holding a JSON document so binding the text is enough.
+            Value::Json(value) => Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Text(value.clone()))),
+
+            // List
+            // duckdb::types::Value::List is not supported by duckdb-rs for binding parameters (and the raw statement
+            // needed to bind an Arrow list is not exposed) but we can use a duckdb::types::Value::Text (i.e.
+            // `[1, 2, 3]`) that DuckDB will cast into a list (i.e. `?::INTEGER[]`). Rewriting the placeholder into
+            // `list_value(?, ...)` is not an option since the statement would have to be prepared again for each
+            // length of the list.
+            Value::List(_values) => {
+                Ok(duckdb::types::ToSqlOutput::Owned(duckdb::types::Value::Text(self.0.to_string())))
+            }
         }
     }
 }
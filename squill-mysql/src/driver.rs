@@ -1,3 +1,4 @@
+use crate::values::into_params;
 use crate::{errors::driver_error, DRIVER_NAME};
 use arrow_array::builder::ArrayBuilder;
 use arrow_array::RecordBatch;
@@ -5,7 +6,11 @@ use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use mysql::prelude::Queryable;
 use mysql::Binary;
 use squill_core::arrow::array_builder::ArrayBuilderAppender;
-use squill_core::driver::{DriverConnection, DriverOptionsRef, DriverStatement, Result};
+use squill_core::arrow::DATASOURCE_TYPE_METADATA;
+use squill_core::driver::{
+    affected_rows_batch, affected_rows_schema, DriverConnection, DriverOptionsRef, DriverStatement, ExecuteOutcome,
+    Result,
+};
 use squill_core::parameters::Parameters;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -39,6 +44,7 @@ impl DriverConnection for MySql {
             inner: inner_stmt,
             client: &mut self.conn,
             options: self.options.clone(),
+            max_batch_rows: None,
             schema: None,
         }))
     }
@@ -48,6 +54,8 @@ pub(crate) struct MySqlStatement<'c> {
     client: &'c mut mysql::Conn,
     inner: mysql::Statement,
     options: DriverOptionsRef,
+    /// The maximum number of rows of the record batches overriding the one of the options.
+    max_batch_rows: Option<usize>,
     schema: Option<SchemaRef>,
 }
 
@@ -98,25 +106,71 @@ impl MySqlStatement<'_> {
         };
 
         let mut metadata: HashMap<String, String> = HashMap::new();
-        metadata.insert("datasource_type".to_string(), mysql_type.to_lowercase());
+        metadata.insert(DATASOURCE_TYPE_METADATA.to_string(), mysql_type.to_lowercase());
         Field::new(column.name_str(), arrow_type, !column.flags().contains(mysql::consts::ColumnFlags::NOT_NULL_FLAG))
             .with_metadata(metadata)
     }
 }
 
 impl DriverStatement for MySqlStatement<'_> {
-    fn execute(&mut self, _parameters: Option<Parameters>) -> Result<u64> {
-        match self.client.exec_drop(&self.inner, mysql::Params::Empty).map_err(driver_error) {
+    fn execute(&mut self, parameters: Option<Parameters>) -> Result<u64> {
+        match self.client.exec_drop(&self.inner, into_params(parameters)?).map_err(driver_error) {
             Ok(_) => Ok(self.client.affected_rows()),
             Err(err) => Err(err.into()),
         }
     }
 
+    fn execute_detailed(&mut self, parameters: Option<Parameters>) -> Result<ExecuteOutcome> {
+        let rows_affected = self.execute(parameters)?;
+        let last_insert_id = match self.client.last_insert_id() {
+            0 => None,
+            id => Some(id as i64),
+        };
+        // The warnings are only fetched when the server reported some, saving a round trip otherwise.
+        let warnings = match self.client.warnings() {
+            0 => Vec::new(),
+            _ => self
+                .client
+                .query_map("SHOW WARNINGS", |(level, code, message): (String, u32, String)| {
+                    format!("{} ({}): {}", level, code, message)
+                })
+                .map_err(driver_error)?,
+        };
+        Ok(ExecuteOutcome { rows_affected, last_insert_id, warnings })
+    }
+
+    /// The MySQL protocol has no bulk execution (`Queryable::exec_batch` executes the statement for each set of
+    /// parameters as well but doesn't report the number of rows affected), so the statement is still executed once per
+    /// set of parameters. All the parameters are converted beforehand so an invalid value doesn't leave the batch
+    /// partially executed.
+    fn execute_many(&mut self, parameters: Vec<Parameters>) -> Result<u64> {
+        let params =
+            parameters.into_iter().map(|parameters| into_params(Some(parameters))).collect::<Result<Vec<_>>>()?;
+        let mut affected_rows = 0;
+        for params in params {
+            self.client.exec_drop(&self.inner, params).map_err(driver_error)?;
+            affected_rows += self.client.affected_rows();
+        }
+        Ok(affected_rows)
+    }
+
     fn query<'s>(
         &'s mut self,
-        _parameters: Option<Parameters>,
+        parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>> {
-        match self.client.exec_iter(&self.inner, mysql::Params::Empty).map_err(driver_error) {
+        Ok(self.query_with_schema(parameters)?.1)
+    }
+
+    /// The schema is built from the columns of the result rather than from the prepared statement.
+    fn query_with_schema<'s>(
+        &'s mut self,
+        parameters: Option<Parameters>,
+    ) -> Result<(SchemaRef, Box<dyn Iterator<Item = Result<RecordBatch>> + 's>)> {
+        if self.inner.columns().is_empty() {
+            let affected_rows = self.execute(parameters)?;
+            return Ok((affected_rows_schema(), Box::new(std::iter::once(affected_rows_batch(affected_rows)))));
+        }
+        match self.client.exec_iter(&self.inner, into_params(parameters)?).map_err(driver_error) {
             Ok(query_result) => {
                 // build the schema
                 let mut fields: Vec<Field> = vec![];
@@ -124,16 +178,37 @@ impl DriverStatement for MySqlStatement<'_> {
                     fields.push(Self::column_into_field(column));
                 }
                 let schema = Arc::new(Schema::new(fields));
-                let rows = MySqlRows { inner: query_result, schema: schema.clone(), options: self.options.clone() };
-                self.schema = Some(schema);
-                Ok(Box::new(rows))
+                let max_batch_rows = self.max_batch_rows.unwrap_or(self.options.max_batch_rows);
+                let rows = MySqlRows { inner: query_result, schema: schema.clone(), max_batch_rows };
+                self.schema = Some(schema.clone());
+                Ok((schema, Box::new(rows)))
             }
             Err(err) => Err(err.into()),
         }
     }
 
+    /// Before the first query, the schema is built from the columns reported by the server when the statement was
+    /// prepared.
     fn schema(&self) -> SchemaRef {
-        self.schema.clone().unwrap()
+        match &self.schema {
+            Some(schema) => schema.clone(),
+            None => {
+                Arc::new(Schema::new(self.inner.columns().iter().map(Self::column_into_field).collect::<Vec<Field>>()))
+            }
+        }
+    }
+
+    /// The columns are reported by the server when the statement is prepared.
+    fn prepared_schema(&self) -> Option<SchemaRef> {
+        Some(self.schema())
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.inner.num_params() as usize
+    }
+
+    fn set_max_batch_rows(&mut self, max_batch_rows: Option<usize>) {
+        self.max_batch_rows = max_batch_rows;
     }
 }
 
@@ -147,7 +222,7 @@ impl Drop for MySqlStatement<'_> {
 
 struct MySqlRows<'c, 't, 'tc> {
     inner: mysql::QueryResult<'c, 't, 'tc, Binary>,
-    options: DriverOptionsRef,
+    max_batch_rows: usize,
     schema: SchemaRef,
 }
 
@@ -209,7 +284,7 @@ impl Iterator for MySqlRows<'_, '_, '_> {
             .map(|field| arrow_array::builder::make_builder(field.data_type(), 0))
             .collect::<Vec<_>>();
 
-        let max_batch_rows = self.options.max_batch_rows;
+        let max_batch_rows = self.max_batch_rows;
         let mut row_num = 0;
         let inner = &mut self.inner;
         loop {
@@ -225,7 +300,7 @@ impl Iterator for MySqlRows<'_, '_, '_> {
                     Err(e) => return Some(Err(e)),
                 },
                 None => break,
-                Some(Err(e)) => return Some(Err(e.into())),
+                Some(Err(e)) => return Some(Err(driver_error(e).into())),
             };
         }
         match row_num {
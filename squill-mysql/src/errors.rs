@@ -12,6 +12,21 @@ pub(crate) fn driver_error(mysql_error: mysql::Error) -> Error {
             }
             _ => Error::DriverError { error: Box::new(driver_error) },
         },
+        // ER_QUERY_TIMEOUT: raised when the statement is aborted because of the `max_execution_time`.
+        mysql::Error::MySqlError(ref e) if e.code == 3024 => Error::Timeout,
+        // ER_LOCK_WAIT_TIMEOUT: a lock was not granted within `innodb_lock_wait_timeout`, only the statement is rolled
+        // back (unless `innodb_rollback_on_timeout` is set).
+        mysql::Error::MySqlError(ref e) if e.code == 1205 => Error::Timeout,
+        // ER_LOCK_DEADLOCK: the transaction was rolled back to resolve a deadlock.
+        mysql::Error::MySqlError(ref e) if e.code == 1213 => Error::Deadlock { error: Box::new(mysql_error) },
+        // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION: a write in a transaction started with `START TRANSACTION READ ONLY`.
+        mysql::Error::MySqlError(ref e) if e.code == 1792 => Error::ReadOnly { error: Box::new(mysql_error) },
+        // ER_OPTION_PREVENTS_STATEMENT: a write on a server started with `--read-only` (among other options).
+        mysql::Error::MySqlError(ref e) if e.code == 1290 && e.message.contains("read-only") => {
+            Error::ReadOnly { error: Box::new(mysql_error) }
+        }
+        // The connection to the server was lost.
+        mysql::Error::IoError(_) => Error::ConnectionFailed { message: mysql_error.to_string() },
         _ => Error::DriverError { error: Box::new(mysql_error) },
     }
 }
@@ -2,19 +2,154 @@ use crate::driver::MySql;
 use crate::errors::driver_error;
 use crate::DRIVER_NAME;
 use squill_core::driver::{DriverConnection, DriverFactory, DriverOptionsRef, Result};
+use squill_core::uri::{
+    parse_millis, redact_uri, take_query_param, APPLICATION_NAME_PARAM, DEFAULT_APPLICATION_NAME,
+    STATEMENT_TIMEOUT_PARAM,
+};
 use squill_core::Error;
+use std::collections::HashMap;
+
+/// The parameter of the URI setting the character set of the connection.
+const CHARSET_PARAM: &str = "charset";
+
+/// The character set used when the URI doesn't have a `charset` parameter.
+///
+/// Depending on the configuration of the server, the default character set of a connection may be `latin1` which
+/// cannot represent most of the Unicode characters.
+const DEFAULT_CHARSET: &str = "utf8mb4";
+
+/// The parameter of the URI enabling TLS (`true` or `false`), it is set when using the `mysql+tls` scheme.
+const TLS_PARAM: &str = "tls";
+
+/// The parameter of the URI setting the host of the server, a path being the Unix domain socket of the server.
+const HOST_PARAM: &str = "host";
+
+/// The connection attribute giving the name of the program connected to the server.
+const PROGRAM_NAME_ATTR: &str = "program_name";
+
+/// An alias of the `mysql` scheme used by some tools.
+const MYSQL2_SCHEME: &str = "mysql2";
 
 pub(crate) struct MySqlFactory {}
 
 impl DriverFactory for MySqlFactory {
     fn schemes(&self) -> &'static [&'static str] {
-        &[DRIVER_NAME]
+        &[DRIVER_NAME, MYSQL2_SCHEME]
+    }
+
+    /// The `tls` (or `ssl`) suffix enables TLS (i.e. `mysql+tls://localhost/db`).
+    fn apply_scheme_suffix(&self, uri: &str, suffix: &str) -> Result<String> {
+        match suffix {
+            "tls" | "ssl" => {
+                let mut parsed_uri = url::Url::parse(uri)
+                    .map_err(|e| Error::InvalidUri { uri: redact_uri(uri), reason: e.to_string() })?;
+                parsed_uri.query_pairs_mut().append_pair(TLS_PARAM, "true");
+                Ok(parsed_uri.to_string())
+            }
+            _ => Err(Error::InvalidUri {
+                uri: redact_uri(uri),
+                reason: format!("Unsupported scheme suffix: {}", suffix),
+            }
+            .into()),
+        }
     }
 
     /// Open a connection to a MySQL database.
+    ///
+    /// In addition to the parameters supported by the `mysql` crate, the URI can have a `statement_timeout` parameter
+    /// (in milliseconds) that is applied to the session using `max_execution_time`. Be aware that MySQL only enforces
+    /// this timeout for read-only `SELECT` statements.
+    ///
+    /// The character set of the connection is `utf8mb4` unless the URI has a `charset` parameter (i.e.
+    /// `charset=latin1`), it is set using `SET NAMES` once connected. TLS is enabled by the `tls=true` parameter, the
+    /// certificate of the server is verified.
+    ///
+    /// The `application_name` parameter is sent as the `program_name` connection attribute (visible in the
+    /// `performance_schema.session_connect_attrs` table), it defaults to `squill`.
+    ///
+    /// A `host` parameter starting with a `/` is the Unix domain socket of the server (i.e.
+    /// `mysql:///db?host=/var/run/mysqld/mysqld.sock`), the socket must exist.
     fn open(&self, uri: &str, options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>> {
-        let opts = mysql::Opts::from_url(uri)
-            .map_err(|url_error| Error::InvalidUri { uri: uri.to_string(), reason: url_error.to_string() })?;
+        // The `mysql` crate only accepts the `mysql` scheme.
+        let mysql_uri = match uri.strip_prefix(MYSQL2_SCHEME) {
+            Some(rest) => format!("{}{}", DRIVER_NAME, rest),
+            None => uri.to_string(),
+        };
+        // The `statement_timeout`, `charset`, `tls`, `host` and `application_name` parameters are not recognized by the
+        // `mysql` crate and must be removed.
+        let (mysql_uri, statement_timeout) = take_query_param(&mysql_uri, STATEMENT_TIMEOUT_PARAM)?;
+        let statement_timeout =
+            statement_timeout.map(|value| parse_millis(uri, STATEMENT_TIMEOUT_PARAM, &value)).transpose()?;
+        let (mysql_uri, charset) = take_query_param(&mysql_uri, CHARSET_PARAM)?;
+        let charset = charset.unwrap_or_else(|| DEFAULT_CHARSET.to_string());
+        // The character set cannot be bound as a parameter of `SET NAMES`.
+        if charset.is_empty() || !charset.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(Error::InvalidUri {
+                uri: redact_uri(uri),
+                reason: format!("Invalid value for {}", CHARSET_PARAM),
+            }
+            .into());
+        }
+        let (mysql_uri, tls) = take_query_param(&mysql_uri, TLS_PARAM)?;
+        let ssl_opts = match tls.as_deref() {
+            Some("true") => Some(mysql::SslOpts::default()),
+            Some("false") | None => None,
+            Some(_) => {
+                return Err(Error::InvalidUri {
+                    uri: redact_uri(uri),
+                    reason: format!("Invalid value for {} (expecting true or false)", TLS_PARAM),
+                }
+                .into())
+            }
+        };
+        let (mysql_uri, application_name) = take_query_param(&mysql_uri, APPLICATION_NAME_PARAM)?;
+        let application_name = application_name.unwrap_or_else(|| DEFAULT_APPLICATION_NAME.to_string());
+        let (mut mysql_uri, host) = take_query_param(&mysql_uri, HOST_PARAM)?;
+        let socket = match host {
+            Some(host) if host.starts_with('/') => {
+                // The `mysql` crate requires a host even if it is not used to connect through the socket.
+                let mut parsed_uri = url::Url::parse(&mysql_uri)
+                    .map_err(|e| Error::InvalidUri { uri: redact_uri(uri), reason: e.to_string() })?;
+                if parsed_uri.host_str().unwrap_or_default().is_empty() {
+                    parsed_uri
+                        .set_host(Some("localhost"))
+                        .map_err(|e| Error::InvalidUri { uri: redact_uri(uri), reason: e.to_string() })?;
+                    mysql_uri = parsed_uri.to_string();
+                }
+                Some(host)
+            }
+            Some(_) => {
+                return Err(Error::InvalidUri {
+                    uri: redact_uri(uri),
+                    reason: format!("Invalid value for {} (expecting the path of a socket)", HOST_PARAM),
+                }
+                .into())
+            }
+            None => None,
+        };
+        let opts = mysql::Opts::from_url(&mysql_uri)
+            .map_err(|url_error| Error::InvalidUri { uri: redact_uri(uri), reason: url_error.to_string() })?;
+        let mut init = vec![format!("SET NAMES {}", charset)];
+        if let Some(statement_timeout) = statement_timeout {
+            init.push(format!("SET SESSION max_execution_time = {}", statement_timeout.as_millis()));
+        }
+        let mut opts = mysql::OptsBuilder::from_opts(opts)
+            .init(init)
+            .ssl_opts(ssl_opts)
+            .connect_attrs(Some(HashMap::from([(PROGRAM_NAME_ATTR, application_name)])));
+        if socket.is_some() {
+            opts = opts.socket(socket);
+        }
+        // The socket may also be set by the `socket` parameter of the `mysql` crate.
+        if let Some(socket) = mysql::Opts::from(opts.clone()).get_socket() {
+            if !std::path::Path::new(socket).exists() {
+                return Err(Error::InvalidUri {
+                    uri: redact_uri(uri),
+                    reason: format!("Socket not found: {}", socket),
+                }
+                .into());
+            }
+        }
         let conn: mysql::Conn = mysql::Conn::new(opts).map_err(driver_error)?;
         Ok(Box::new(MySql { conn, options }))
     }
@@ -6,6 +6,7 @@ pub const DRIVER_NAME: &str = "mysql";
 mod driver;
 mod errors;
 mod factory;
+mod values;
 
 pub fn register_driver() {
     static INIT: std::sync::Once = std::sync::Once::new();
@@ -18,8 +19,11 @@ pub fn register_driver() {
 mod mysql_tests {
     use ctor::ctor;
     use squill_core::decode::Decode;
+    use squill_core::driver::{affected_rows_schema, ExecuteOutcome, AFFECTED_ROWS_COLUMN};
     use squill_core::factory::Factory;
-    use squill_core::{assert_execute_eq, assert_ok, assert_some_ok};
+    use squill_core::parameters::Parameters;
+    use squill_core::values::Value;
+    use squill_core::{assert_execute_eq, assert_ok, assert_some, assert_some_ok, params};
 
     #[ctor]
     fn before_all() {
@@ -33,6 +37,17 @@ mod mysql_tests {
         assert!(Factory::open("mysql://").is_err());
     }
 
+    #[test]
+    fn test_scheme_aliases() {
+        let uri = env!("CI_MYSQL_URI");
+        assert_ok!(Factory::open(&uri.replacen("mysql:", "mysql2:", 1)));
+        assert!(matches!(
+            Factory::open(&uri.replacen("mysql:", "mysql+unknown:", 1)),
+            Err(squill_core::Error::InvalidUri { .. })
+        ));
+        assert!(matches!(Factory::open(&format!("{}?tls=maybe", uri)), Err(squill_core::Error::InvalidUri { .. })));
+    }
+
     #[test]
     fn test_basics() {
         let conn = assert_ok!(Factory::open(env!("CI_MYSQL_URI")));
@@ -49,6 +64,171 @@ mod mysql_tests {
         assert_execute_eq!(conn, "DELETE FROM ci_test WHERE id IN (1, 2)", 2);
     }
 
+    #[test]
+    fn test_bind_json() {
+        let mut conn = assert_ok!(Factory::open(env!("CI_MYSQL_URI")));
+        assert_execute_eq!(conn, "CREATE TEMPORARY TABLE ci_json (id INTEGER PRIMARY KEY, doc JSON)", 0);
+        assert_execute_eq!(
+            conn,
+            "INSERT INTO ci_json (id, doc) VALUES (?, ?)",
+            &[&1, &Value::Json(r#"{"a": 1}"#.into())],
+            1
+        );
+        let mut stmt = assert_ok!(conn.prepare("SELECT COUNT(*) FROM ci_json WHERE JSON_EXTRACT(doc, '$.a') = 1"));
+        let mut rows = assert_ok!(stmt.query(None));
+        assert_eq!(i64::decode(assert_some_ok!(rows.next()).column(0), 0), 1);
+    }
+
+    #[test]
+    fn test_bind_unsupported_data_type() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert!(matches!(
+            conn.execute("SELECT ?", Some(Parameters::Positional(vec![Value::Interval { months: 1, days: 2, nanos: 0 }]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "Interval"
+        ));
+        assert!(matches!(
+            conn.execute("SELECT ?", Some(Parameters::Positional(vec![Value::List(vec![Value::Int32(1)])]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "List"
+        ));
+    }
+
+    #[test]
+    fn test_insert_returning_id() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_ok!(conn
+            .execute("CREATE TEMPORARY TABLE ci_returning (id INTEGER AUTO_INCREMENT PRIMARY KEY, name TEXT)", None));
+        assert_eq!(assert_ok!(conn.insert_returning_id("INSERT INTO ci_returning (name) VALUES (?)", params!("a"))), 1);
+        assert_eq!(assert_ok!(conn.insert_returning_id("INSERT INTO ci_returning (name) VALUES (?)", params!("b"))), 2);
+    }
+
+    #[test]
+    fn test_query_dml() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_query_dml (id INTEGER)", None));
+        let row =
+            assert_some!(assert_ok!(conn.query_row("INSERT INTO ci_query_dml (id) VALUES (?), (?)", params!(1, 2))));
+        assert_eq!(row.schema(), affected_rows_schema());
+        assert_eq!(row.get::<_, i64>(AFFECTED_ROWS_COLUMN), 2);
+    }
+
+    #[test]
+    fn test_execute_detailed() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_ok!(
+            conn.execute("CREATE TEMPORARY TABLE ci_detailed (id INTEGER AUTO_INCREMENT PRIMARY KEY, name TEXT)", None)
+        );
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO ci_detailed (name) VALUES (?)"));
+        let outcome = assert_ok!(stmt.execute_detailed(params!("a")));
+        assert_eq!(outcome, ExecuteOutcome { rows_affected: 1, last_insert_id: Some(1), warnings: vec![] });
+        assert_eq!(assert_ok!(stmt.execute_detailed(params!("b"))).last_insert_id, Some(2));
+        drop(stmt);
+
+        // Dropping an unknown table with `IF EXISTS` raises a note.
+        let mut stmt = assert_ok!(conn.prepare("DROP TEMPORARY TABLE IF EXISTS ci_unknown"));
+        let outcome = assert_ok!(stmt.execute_detailed(None));
+        assert_eq!(outcome.last_insert_id, None);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].contains("ci_unknown"));
+    }
+
+    #[test]
+    fn test_quote() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        let table = conn.quote_identifier("ci_`quote`");
+        assert_eq!(table, "`ci_``quote```");
+        assert_ok!(conn.execute(format!("CREATE TEMPORARY TABLE {} (value TEXT)", table), None));
+        let literal = conn.quote_literal(r"it's C:\temp");
+        assert_ok!(conn.execute(format!("INSERT INTO {} (value) VALUES ({})", table, literal), None));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>(format!("SELECT value FROM {}", table), None)),
+            Some(r"it's C:\temp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_statement_timeout() {
+        // `SLEEP()` is not aborted by `max_execution_time` (it just returns 1) so we need a query that is actually slow.
+        let slow_query = "SELECT COUNT(*) FROM information_schema.columns a, information_schema.columns b, \
+                          information_schema.columns c";
+        let uri = format!("{}?statement_timeout=100", env!("CI_MYSQL_URI"));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert!(matches!(conn.query_row(slow_query, None), Err(squill_core::Error::Timeout)));
+        assert_ok!(conn.query_row("SELECT 1", None));
+    }
+
+    #[test]
+    fn test_lock_wait_timeout() {
+        let mut locker = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_ok!(locker.execute("CREATE TABLE IF NOT EXISTS ci_lock_wait (id INTEGER PRIMARY KEY)", None));
+        assert_ok!(locker.execute("INSERT IGNORE INTO ci_lock_wait (id) VALUES (1)", None));
+        assert_ok!(locker.execute("SET autocommit = 0", None));
+        assert_ok!(locker.query_row("SELECT id FROM ci_lock_wait WHERE id = 1 FOR UPDATE", None));
+
+        // The row is locked by the transaction of the other connection.
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_ok!(conn.execute("SET innodb_lock_wait_timeout = 1", None));
+        let error = conn.execute("UPDATE ci_lock_wait SET id = 1 WHERE id = 1", None).unwrap_err();
+        assert!(matches!(error, squill_core::Error::Timeout), "unexpected error: {:?}", error);
+        assert!(error.is_retryable());
+
+        drop(locker);
+        assert_ok!(conn.execute("DROP TABLE ci_lock_wait", None));
+    }
+
+    #[test]
+    fn test_application_name() {
+        let query = "SELECT ATTR_VALUE FROM performance_schema.session_connect_attrs \
+                     WHERE PROCESSLIST_ID = CONNECTION_ID() AND ATTR_NAME = 'program_name'";
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>(query, None)), Some("squill".to_string()));
+
+        let uri = format!("{}?application_name=my_app", env!("CI_MYSQL_URI"));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>(query, None)), Some("my_app".to_string()));
+    }
+
+    #[test]
+    fn test_charset() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_MYSQL_URI")));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT @@character_set_client", None)),
+            Some("utf8mb4".to_string())
+        );
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_charset (greeting VARCHAR(32)) CHARACTER SET utf8mb4", None));
+        assert_ok!(conn.execute("INSERT INTO ci_charset (greeting) VALUES (?)", params!("いらっしゃいませ 👋")));
+        assert_ok!(conn.execute("INSERT INTO ci_charset (greeting) VALUES ('いらっしゃいませ 👋')", None));
+        let greetings: Vec<String> = assert_ok!(conn.query_map_rows("SELECT greeting FROM ci_charset", None, |row| {
+            row.try_get::<_, String>(0).map_err(Into::into)
+        }));
+        assert_eq!(greetings, vec!["いらっしゃいませ 👋", "いらっしゃいませ 👋"]);
+
+        let uri = format!("{}?charset=latin1", env!("CI_MYSQL_URI"));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT @@character_set_client", None)),
+            Some("latin1".to_string())
+        );
+        let uri = format!("{}?charset=utf8mb4;DROP", env!("CI_MYSQL_URI"));
+        assert!(matches!(squill_blocking::Connection::open(&uri), Err(squill_core::Error::InvalidUri { .. })));
+    }
+
+    #[test]
+    fn test_unix_socket() {
+        let uri = format!("{}?host=/no/such/mysqld.sock", env!("CI_MYSQL_URI"));
+        assert!(
+            matches!(Factory::open(&uri), Err(squill_core::Error::InvalidUri { reason, .. }) if reason.contains("Socket"))
+        );
+
+        // The tests through a socket are only run if its path is known.
+        let Some(socket) = option_env!("CI_MYSQL_SOCKET") else {
+            return;
+        };
+        let mut ci_database_uri = url::Url::parse(env!("CI_MYSQL_URI")).unwrap();
+        ci_database_uri.query_pairs_mut().append_pair("host", socket);
+        let mut conn = assert_ok!(squill_blocking::Connection::open(ci_database_uri.as_str()));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT 1", None)), Some(1));
+    }
+
     #[test]
     fn test_ping() {
         let mut conn = assert_ok!(Factory::open(env!("CI_MYSQL_URI")));
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Datelike, Timelike};
+use squill_core::driver::Result;
+use squill_core::parameters::Parameters;
+use squill_core::values::Value;
+use squill_core::Error;
+use std::collections::HashMap;
+
+/// Convert the parameters of a statement into `mysql::Params`.
+pub(crate) fn into_params(parameters: Option<Parameters>) -> Result<mysql::Params> {
+    match parameters {
+        None => Ok(mysql::Params::Empty),
+        Some(Parameters::Positional(values)) if values.is_empty() => Ok(mysql::Params::Empty),
+        Some(Parameters::Positional(values)) => {
+            Ok(mysql::Params::Positional(values.iter().map(into_mysql_value).collect::<Result<Vec<_>>>()?))
+        }
+        Some(Parameters::Named(values)) if values.is_empty() => Ok(mysql::Params::Empty),
+        Some(Parameters::Named(values)) => Ok(mysql::Params::Named(
+            values
+                .iter()
+                .map(|(name, value)| Ok((name.trim_start_matches(':').as_bytes().to_vec(), into_mysql_value(value)?)))
+                .collect::<Result<HashMap<_, _>>>()?,
+        )),
+    }
+}
+
+/// Convert a `Value` into a `mysql::Value`.
+///
+/// Values without an equivalent in the MySQL protocol are bound using their string representation and MySQL will
+/// cast them to the type of the parameter (i.e. `DECIMAL`, `JSON`).
+pub(crate) fn into_mysql_value(value: &Value) -> Result<mysql::Value> {
+    match value {
+        Value::Null => Ok(mysql::Value::NULL),
+        Value::Bool(value) => Ok(mysql::Value::Int(*value as i64)),
+        Value::Int8(value) => Ok(mysql::Value::Int(*value as i64)),
+        Value::Int16(value) => Ok(mysql::Value::Int(*value as i64)),
+        Value::Int32(value) => Ok(mysql::Value::Int(*value as i64)),
+        Value::Int64(value) => Ok(mysql::Value::Int(*value)),
+        Value::UInt8(value) => Ok(mysql::Value::UInt(*value as u64)),
+        Value::UInt16(value) => Ok(mysql::Value::UInt(*value as u64)),
+        Value::UInt32(value) => Ok(mysql::Value::UInt(*value as u64)),
+        Value::UInt64(value) => Ok(mysql::Value::UInt(*value)),
+        Value::Float32(value) => Ok(mysql::Value::Float(*value)),
+        Value::Float64(value) => Ok(mysql::Value::Double(*value)),
+        Value::Blob(value) => Ok(mysql::Value::Bytes(value.clone())),
+        Value::Int128(_)
+        | Value::UInt128(_)
+        | Value::String(_)
+        | Value::Decimal(_)
+        | Value::Uuid(_)
+        | Value::Json(_) => Ok(mysql::Value::Bytes(value.to_string().into_bytes())),
+        Value::Date32(days) => {
+            let date = DateTime::from_timestamp(*days as i64 * 86_400, 0)
+                .ok_or_else(|| Error::InternalError { error: format!("Date out of range: {}", days).into() })?;
+            Ok(mysql::Value::Date(date.year() as u16, date.month() as u8, date.day() as u8, 0, 0, 0, 0))
+        }
+        // MySQL doesn't store the time zone, a timestamp with a time zone is bound as its UTC date and time.
+        Value::Timestamp(unit, value) | Value::TimestampTz(unit, value, _) => {
+            let datetime = DateTime::from_timestamp_nanos(unit.to_nanos(*value));
+            Ok(mysql::Value::Date(
+                datetime.year() as u16,
+                datetime.month() as u8,
+                datetime.day() as u8,
+                datetime.hour() as u8,
+                datetime.minute() as u8,
+                datetime.second() as u8,
+                datetime.timestamp_subsec_micros(),
+            ))
+        }
+        Value::Time64(unit, value) => {
+            let micros = unit.to_nanos(*value) / 1_000;
+            let secs = micros / 1_000_000;
+            Ok(mysql::Value::Time(
+                false,
+                (secs / 86_400) as u32,
+                (secs / 3600 % 24) as u8,
+                (secs / 60 % 60) as u8,
+                (secs % 60) as u8,
+                (micros % 1_000_000) as u32,
+            ))
+        }
+        Value::Interval { .. } | Value::List(_) => {
+            Err(Error::UnsupportedDataType { data_type: value.type_name().to_string() }.into())
+        }
+    }
+}
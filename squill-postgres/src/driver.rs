@@ -1,5 +1,5 @@
-use crate::errors::into_driver_error;
-use crate::values::ParametersIterator;
+use crate::errors::{into_driver_error, StatementClock};
+use crate::values::{check_positional, Adapter, ParametersIterator};
 use crate::DRIVER_NAME;
 use arrow_array::builder::ArrayBuilder;
 use arrow_array::types::IntervalMonthDayNano;
@@ -7,16 +7,75 @@ use arrow_array::RecordBatch;
 use arrow_schema::{DataType, Field, Schema, SchemaRef};
 use byteorder::{BigEndian, ReadBytesExt};
 use postgres::fallible_iterator::FallibleIterator;
-use postgres_types::{accepts, FromSql, Type};
+use postgres_types::{accepts, FromSql, ToSql, Type};
 use squill_core::arrow::array_builder::ArrayBuilderAppender;
-use squill_core::driver::{DriverConnection, DriverOptionsRef, DriverStatement, Result};
+use squill_core::arrow::DATASOURCE_TYPE_METADATA;
+use squill_core::driver::{affected_rows_batch, DriverConnection, DriverOptionsRef, DriverStatement, Result};
 use squill_core::parameters::Parameters;
+use squill_core::quote::quote_identifier;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::io::Read;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
-pub(crate) struct Postgres {
+/// The time zone of the `TIMESTAMPTZ` columns.
+const TIMESTAMPTZ_TIME_ZONE: &str = "+00:00";
+
+/// A connection to PostgreSQL.
+///
+/// This type is not intended to be used directly but it gives access to the features specific to PostgreSQL when
+/// downcasting a connection (see [squill_core::driver::DriverConnection]).
+///
+/// ```rust,ignore
+/// let mut conn = Factory::open("postgres://localhost/db")?;
+/// let mut reader = conn.downcast_mut::<Postgres>().unwrap().copy_out("employee", CopyFormat::Csv { header: true })?;
+/// std::io::copy(&mut reader, &mut std::io::stdout())?;
+/// ```
+pub struct Postgres {
     pub(crate) client: postgres::Client,
     pub(crate) options: DriverOptionsRef,
+    /// The `statement_timeout` of the session set when opening the connection.
+    pub(crate) statement_timeout: Option<Duration>,
+}
+
+impl Postgres {
+    /// Stream the rows of a table or a query using `COPY ... TO STDOUT`.
+    ///
+    /// The `query_or_table` is either a query (i.e. `SELECT * FROM employee WHERE id > 10`) or the name of a table,
+    /// optionally qualified by its schema (i.e. `public.employee`). A table name is quoted, so its case must match the
+    /// name of the table in the catalog. Nothing is materialized, the data is read from the server while the returned
+    /// reader is consumed.
+    pub fn copy_out(&mut self, query_or_table: &str, format: CopyFormat) -> Result<impl Read + '_> {
+        let query_or_table = query_or_table.trim();
+        let source = match query_or_table.contains(char::is_whitespace) {
+            true => format!("({})", query_or_table.trim_end_matches(';')),
+            false => {
+                query_or_table.split('.').map(|part| quote_identifier(DRIVER_NAME, part)).collect::<Vec<_>>().join(".")
+            }
+        };
+        let options = match format {
+            CopyFormat::Text => "FORMAT text",
+            CopyFormat::Csv { header: false } => "FORMAT csv",
+            CopyFormat::Csv { header: true } => "FORMAT csv, HEADER",
+            CopyFormat::Binary => "FORMAT binary",
+        };
+        Ok(self.client.copy_out(&format!("COPY {} TO STDOUT ({})", source, options)).map_err(into_driver_error)?)
+    }
+}
+
+/// The format of the data streamed by [Postgres::copy_out].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// The text format of PostgreSQL: one line per row, the columns separated by tabs.
+    Text,
+
+    /// Comma separated values, the first line containing the names of the columns if `header` is `true`.
+    Csv { header: bool },
+
+    /// The binary format of PostgreSQL.
+    Binary,
 }
 
 impl DriverConnection for Postgres {
@@ -41,6 +100,26 @@ impl DriverConnection for Postgres {
             inner: self.client.prepare(statement).map_err(into_driver_error)?,
             client: &mut self.client,
             options: self.options.clone(),
+            statement_timeout: self.statement_timeout,
+            max_batch_rows: None,
+            row_count: Rc::new(Cell::new(None)),
+        }))
+    }
+
+    /// The types are given to PostgreSQL when preparing the statement (see `postgres::Client::prepare_typed`).
+    fn prepare_typed<'c: 's, 's>(
+        &'c mut self,
+        statement: &str,
+        types: &[DataType],
+    ) -> Result<Box<dyn DriverStatement + 's>> {
+        let types = types.iter().map(PostgresStatement::data_type_into_type).collect::<Result<Vec<Type>>>()?;
+        Ok(Box::new(PostgresStatement {
+            inner: self.client.prepare_typed(statement, &types).map_err(into_driver_error)?,
+            client: &mut self.client,
+            options: self.options.clone(),
+            statement_timeout: self.statement_timeout,
+            max_batch_rows: None,
+            row_count: Rc::new(Cell::new(None)),
         }))
     }
 }
@@ -49,12 +128,48 @@ pub(crate) struct PostgresStatement<'c> {
     pub(crate) client: &'c mut postgres::Client,
     pub(crate) inner: postgres::Statement,
     pub(crate) options: DriverOptionsRef,
+    pub(crate) statement_timeout: Option<Duration>,
+    /// The maximum number of rows of the record batches overriding the one of the options.
+    pub(crate) max_batch_rows: Option<usize>,
+    /// The number of rows returned by the last query, known once its rows have all been fetched.
+    pub(crate) row_count: Rc<Cell<Option<u64>>>,
 }
 
 impl PostgresStatement<'_> {
     fn column_into_field(column: &postgres::Column) -> Field {
         let name = column.name().to_string();
-        let data_type = match *column.type_() {
+        let data_type = Self::type_into_data_type(column.type_());
+        let mut metadata: HashMap<String, String> = HashMap::new();
+        metadata.insert(DATASOURCE_TYPE_METADATA.to_string(), column.type_().to_string());
+        Field::new(name, data_type, true).with_metadata(metadata)
+    }
+
+    /// Get the PostgreSQL type of a parameter given the Arrow data type of its values (see
+    /// [DriverConnection::prepare_typed]).
+    fn data_type_into_type(data_type: &DataType) -> Result<Type> {
+        match data_type {
+            DataType::Boolean => Ok(Type::BOOL),
+            DataType::Int8 => Ok(Type::CHAR),
+            DataType::Int16 => Ok(Type::INT2),
+            DataType::Int32 => Ok(Type::INT4),
+            DataType::Int64 => Ok(Type::INT8),
+            DataType::UInt32 => Ok(Type::OID),
+            DataType::Float32 => Ok(Type::FLOAT4),
+            DataType::Float64 => Ok(Type::FLOAT8),
+            DataType::Utf8 | DataType::LargeUtf8 => Ok(Type::TEXT),
+            DataType::Binary | DataType::LargeBinary => Ok(Type::BYTEA),
+            DataType::Date32 => Ok(Type::DATE),
+            DataType::Time64(_) => Ok(Type::TIME),
+            DataType::Timestamp(_, None) => Ok(Type::TIMESTAMP),
+            DataType::Timestamp(_, Some(_)) => Ok(Type::TIMESTAMPTZ),
+            DataType::FixedSizeBinary(16) => Ok(Type::UUID),
+            _ => Err(squill_core::Error::UnsupportedDataType { data_type: data_type.to_string() }.into()),
+        }
+    }
+
+    /// Get the Arrow data type used for the values of a PostgreSQL type.
+    fn type_into_data_type(type_: &Type) -> DataType {
+        match *type_ {
             postgres_types::Type::BOOL => DataType::Boolean,
             postgres_types::Type::CHAR => DataType::Int8,
             postgres_types::Type::INT2 => DataType::Int16,
@@ -65,7 +180,10 @@ impl PostgresStatement<'_> {
             postgres_types::Type::DATE => DataType::Date32,
             postgres_types::Type::TIME => DataType::Time64(arrow_schema::TimeUnit::Microsecond),
             postgres_types::Type::TIMESTAMP => DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, None),
-            postgres_types::Type::TIMESTAMPTZ => DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, None),
+            // The values of a `TIMESTAMPTZ` are always returned in UTC whatever the `TimeZone` of the session.
+            postgres_types::Type::TIMESTAMPTZ => {
+                DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, Some(TIMESTAMPTZ_TIME_ZONE.into()))
+            }
             postgres_types::Type::INTERVAL => DataType::Interval(arrow_schema::IntervalUnit::MonthDayNano),
             postgres_types::Type::VARCHAR => DataType::Utf8,
             postgres_types::Type::JSON => DataType::Utf8,
@@ -83,40 +201,86 @@ impl PostgresStatement<'_> {
             postgres_types::Type::CID => DataType::UInt32,
             // &postgres_types::Type::ARRAY => DataType::List(Box::new(Self::column_into_field(column.element_type().unwrap()))),
             _ => DataType::Binary,
-        };
-
-        let mut metadata: HashMap<String, String> = HashMap::new();
-        metadata.insert("datasource_type".to_string(), column.type_().to_string());
-        Field::new(name, data_type, true).with_metadata(metadata)
+        }
     }
 }
 
 impl DriverStatement for PostgresStatement<'_> {
-    fn execute(&mut self, _parameters: Option<Parameters>) -> Result<u64> {
-        Ok(self.client.execute(&self.inner, &[]).map_err(into_driver_error)? as u64)
+    fn execute(&mut self, parameters: Option<Parameters>) -> Result<u64> {
+        check_positional(&parameters, self.parameter_count())?;
+        let adapters: Vec<Adapter> = ParametersIterator::new(&parameters).collect();
+        let params: Vec<&(dyn ToSql + Sync)> = adapters.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        let clock = StatementClock::start(self.statement_timeout);
+        Ok(self.client.execute(&self.inner, &params).map_err(|e| clock.into_driver_error(e))?)
     }
 
     fn query<'s>(
         &'s mut self,
         parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>> {
+        if self.inner.columns().is_empty() {
+            let affected_rows = self.execute(parameters)?;
+            self.row_count.set(Some(1));
+            return Ok(Box::new(std::iter::once(affected_rows_batch(affected_rows))));
+        }
+        check_positional(&parameters, self.parameter_count())?;
         let params_iter = ParametersIterator::new(&parameters);
         let schema = self.schema();
-        let res_iter = self.client.query_raw(&self.inner, params_iter).map_err(into_driver_error)?;
-        let iter = PostgresRows { schema, inner: res_iter, options: self.options.clone() };
+        self.row_count.set(None);
+        let clock = StatementClock::start(self.statement_timeout);
+        let res_iter = self.client.query_raw(&self.inner, params_iter).map_err(|e| clock.into_driver_error(e))?;
+        let iter = PostgresRows {
+            schema,
+            clock,
+            inner: res_iter,
+            max_batch_rows: self.max_batch_rows.unwrap_or(self.options.max_batch_rows),
+            row_count: self.row_count.clone(),
+        };
         Ok(Box::new(iter))
     }
 
+    fn prepared_schema(&self) -> Option<SchemaRef> {
+        Some(self.schema())
+    }
+
     fn schema(&self) -> SchemaRef {
         let fields: Vec<Field> = self.inner.columns().iter().map(Self::column_into_field).collect::<Vec<Field>>();
         Arc::new(Schema::new(fields))
     }
+
+    /// The parameters are given to each execution and the cursor is closed once the rows are dropped, only the count
+    /// of the rows of the last query needs to be reset.
+    fn reset(&mut self) -> Result<()> {
+        self.row_count.set(None);
+        Ok(())
+    }
+
+    /// The number of rows is only known once all the rows of the query have been fetched.
+    fn estimated_row_count(&self) -> Option<u64> {
+        self.row_count.get()
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.inner.params().len()
+    }
+
+    /// The types of the parameters are inferred by PostgreSQL when the statement is prepared.
+    fn parameter_types(&self) -> Option<Vec<DataType>> {
+        Some(self.inner.params().iter().map(Self::type_into_data_type).collect())
+    }
+
+    fn set_max_batch_rows(&mut self, max_batch_rows: Option<usize>) {
+        self.max_batch_rows = max_batch_rows;
+    }
 }
 
 struct PostgresRows<'s> {
     schema: SchemaRef,
-    options: DriverOptionsRef,
+    /// The time taken by the query, the statement being executed while its rows are fetched.
+    clock: StatementClock,
+    max_batch_rows: usize,
     inner: postgres::RowIter<'s>,
+    row_count: Rc<Cell<Option<u64>>>,
 }
 
 struct TextValue(String);
@@ -320,11 +484,11 @@ impl Iterator for PostgresRows<'_> {
             .map(|field| arrow_array::builder::make_builder(field.data_type(), 0))
             .collect::<Vec<_>>();
 
-        let max_batch_rows = self.options.max_batch_rows;
+        let max_batch_rows = self.max_batch_rows;
         let mut row_num = 0;
         let inner = &mut self.inner;
         loop {
-            let next_row = inner.next().map_err(into_driver_error);
+            let next_row = inner.next().map_err(|e| self.clock.into_driver_error(e));
             match next_row {
                 Ok(Some(row)) => match Self::append_row(&mut columns, row) {
                     Ok(_) => {
@@ -339,6 +503,8 @@ impl Iterator for PostgresRows<'_> {
                 Err(e) => return Some(Err(e.into())),
             };
         }
+        // The number of rows is known as soon as the last row has been fetched.
+        self.row_count.set(self.inner.rows_affected());
         match row_num {
             0 => None,
             _ => {
@@ -1,6 +1,57 @@
+use postgres::error::SqlState;
 use squill_core::error::Error;
+use std::error::Error as _;
+use std::time::{Duration, Instant};
 
 /// Convert a `postgres::Error` into a `squill_core::error::Error`.
 pub(crate) fn into_driver_error(postgres_error: postgres::Error) -> Error {
-    Error::DriverError { error: Box::new(postgres_error) }
+    match postgres_error.code() {
+        // Raised when the statement is cancelled by the client or `pg_cancel_backend`, but also when it is aborted because
+        // of the `statement_timeout` (see [StatementClock]).
+        Some(&SqlState::QUERY_CANCELED) => Error::Cancelled,
+        Some(&SqlState::T_R_DEADLOCK_DETECTED) => Error::Deadlock { error: Box::new(postgres_error) },
+        Some(&SqlState::T_R_SERIALIZATION_FAILURE) => Error::SerializationFailure { error: Box::new(postgres_error) },
+        // Raised by a write in a read-only transaction (i.e. `default_transaction_read_only` or a standby server).
+        Some(&SqlState::READ_ONLY_SQL_TRANSACTION) => Error::ReadOnly { error: Box::new(postgres_error) },
+        // The connection to the server was lost.
+        None if postgres_error.is_closed() => Error::ConnectionFailed { message: postgres_error.to_string() },
+        // The conversion of a parameter failed, the error raised by the adapter is returned as is.
+        None if postgres_error.source().is_some_and(|e| e.is::<Error>()) => match postgres_error.into_source() {
+            Some(error) => Error::from(error),
+            None => Error::InternalError { error: "Missing source of the error".into() },
+        },
+        _ => Error::DriverError { error: Box::new(postgres_error) },
+    }
+}
+
+/// Tell apart a statement aborted by the `statement_timeout` of the session from a cancelled statement.
+///
+/// PostgreSQL raises `QUERY_CANCELED` in both cases and only the message, translated according to `lc_messages`, tells
+/// them apart. The statement is instead considered as timed out if it ran for at least the `statement_timeout` set when
+/// opening the connection, so a timeout set by the statements themselves (i.e. `SET statement_timeout = 100`) is
+/// reported as [Error::Cancelled].
+#[derive(Clone, Copy)]
+pub(crate) struct StatementClock {
+    started: Instant,
+    statement_timeout: Option<Duration>,
+}
+
+impl StatementClock {
+    /// Start measuring the time taken by a statement.
+    pub(crate) fn start(statement_timeout: Option<Duration>) -> Self {
+        Self { started: Instant::now(), statement_timeout }
+    }
+
+    /// Convert an error raised by the statement, a `QUERY_CANCELED` being an [Error::Timeout] if the
+    /// `statement_timeout` elapsed.
+    pub(crate) fn into_driver_error(self, postgres_error: postgres::Error) -> Error {
+        match postgres_error.code() {
+            Some(&SqlState::QUERY_CANCELED) if self.timed_out() => Error::Timeout,
+            _ => into_driver_error(postgres_error),
+        }
+    }
+
+    fn timed_out(&self) -> bool {
+        self.statement_timeout.is_some_and(|timeout| self.started.elapsed() >= timeout)
+    }
 }
@@ -1,5 +1,8 @@
 use crate::driver::Postgres;
+use crate::errors::into_driver_error;
 use squill_core::driver::{DriverConnection, DriverFactory, DriverOptionsRef, Result};
+use squill_core::uri::{parse_millis, redact_uri, take_query_param, DEFAULT_APPLICATION_NAME, STATEMENT_TIMEOUT_PARAM};
+use squill_core::Error;
 
 pub(crate) struct PostgresFactory {}
 
@@ -9,8 +12,49 @@ impl DriverFactory for PostgresFactory {
     }
 
     /// Open a connection to a PostgreSQL database.
+    ///
+    /// In addition to the parameters supported by PostgreSQL, the URI can have a `statement_timeout` parameter (in
+    /// milliseconds) that is applied to the session using `SET statement_timeout`.
+    ///
+    /// The name of the application reported by the server (i.e. in `pg_stat_activity`) is given by the
+    /// `application_name` parameter, it defaults to `squill`.
+    ///
+    /// A `host` starting with a `/` is the directory of the Unix domain socket of the server (i.e.
+    /// `postgres:///db?host=/var/run/postgresql`), the directory must exist.
+    ///
+    /// Several hosts can be given for failover, either as a comma-separated list in the URI (i.e.
+    /// `postgres://primary:5432,standby:5432/db`) or in the `host` parameter. The hosts are attempted in order until a
+    /// connection succeeds, the error of the last host is returned if all of them fail. With
+    /// `target_session_attrs=read-write` a host only accepting read-only sessions (i.e. a standby) is skipped, and the
+    /// `connect_timeout` parameter (in seconds) applies to each host so an unreachable host doesn't block the next
+    /// ones.
     fn open(&self, uri: &str, options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>> {
-        let client = postgres::Client::connect(uri, postgres::NoTls)?;
-        Ok(Box::new(Postgres { client, options }))
+        // The `statement_timeout` parameter is not recognized by the `postgres` crate and must be removed.
+        let (postgres_uri, statement_timeout) = take_query_param(uri, STATEMENT_TIMEOUT_PARAM)?;
+        let statement_timeout =
+            statement_timeout.map(|value| parse_millis(uri, STATEMENT_TIMEOUT_PARAM, &value)).transpose()?;
+        let mut config = postgres_uri.parse::<postgres::Config>()?;
+        if config.get_application_name().is_none() {
+            config.application_name(DEFAULT_APPLICATION_NAME);
+        }
+        #[cfg(unix)]
+        for host in config.get_hosts() {
+            if let postgres::config::Host::Unix(path) = host {
+                if !path.is_dir() {
+                    return Err(Error::InvalidUri {
+                        uri: redact_uri(uri),
+                        reason: format!("Socket directory not found: {}", path.display()),
+                    }
+                    .into());
+                }
+            }
+        }
+        let mut client = config.connect(postgres::NoTls)?;
+        if let Some(statement_timeout) = statement_timeout {
+            client
+                .batch_execute(&format!("SET statement_timeout = {}", statement_timeout.as_millis()))
+                .map_err(into_driver_error)?;
+        }
+        Ok(Box::new(Postgres { client, options, statement_timeout }))
     }
 }
@@ -1,11 +1,15 @@
 use squill_core::factory::Factory;
 
+pub use driver::{CopyFormat, Postgres};
+pub use options::PostgresConnectOptions;
+
 /// The name of the driver for PostgreSQL.
 pub const DRIVER_NAME: &str = "postgres";
 
 mod driver;
 mod errors;
 mod factory;
+mod options;
 mod values;
 
 pub fn register_driver() {
@@ -17,13 +21,18 @@ pub fn register_driver() {
 
 #[cfg(test)]
 mod postgres_tests {
+    use crate::{CopyFormat, Postgres, PostgresConnectOptions};
     use arrow_array::ArrayRef;
-    use chrono::NaiveDate;
+    use chrono::{NaiveDate, NaiveTime};
     use ctor::ctor;
     use squill_core::assert_some;
     use squill_core::decode::Decode;
-    use squill_core::driver::DriverConnection;
-    use squill_core::{assert_execute_eq, assert_some_ok, factory::Factory};
+    use squill_core::driver::{affected_rows_schema, DriverConnection, AFFECTED_ROWS_COLUMN};
+    use squill_core::parameters::Parameters;
+    use squill_core::values::{TimeUnit, Value};
+    use squill_core::{assert_execute_eq, assert_some_ok, factory::Factory, params};
+    use std::io::{BufRead, Read};
+    use std::time::Duration;
     use tokio_test::assert_ok;
     use uuid::Uuid;
 
@@ -38,12 +47,269 @@ mod postgres_tests {
         assert_ok!(Factory::open(ci_database_uri));
     }
 
+    #[test]
+    fn test_scheme_aliases() {
+        let (_, rest) = env!("CI_POSTGRES_URI").split_once(':').unwrap();
+        assert_ok!(Factory::open(&format!("postgres:{}", rest)));
+        assert_ok!(Factory::open(&format!("postgresql:{}", rest)));
+        assert!(matches!(
+            Factory::open(&format!("postgresql+tls:{}", rest)),
+            Err(squill_core::Error::InvalidUri { reason, .. }) if reason.contains("tls")
+        ));
+    }
+
+    #[test]
+    fn test_unix_socket() {
+        let uri = format!("{}?host=/no/such/directory", env!("CI_POSTGRES_URI"));
+        assert!(
+            matches!(Factory::open(&uri), Err(squill_core::Error::InvalidUri { reason, .. }) if reason.contains("Socket"))
+        );
+
+        // The tests through a socket are only run if its directory is known.
+        let Some(socket_dir) = option_env!("CI_POSTGRES_SOCKET_DIR") else {
+            return;
+        };
+        let ci_database_uri = url::Url::parse(env!("CI_POSTGRES_URI")).unwrap();
+        let uri = format!(
+            "postgres://{}?host={}&user={}&password={}",
+            ci_database_uri.path(),
+            squill_core::uri::encode_component(socket_dir),
+            ci_database_uri.username(),
+            ci_database_uri.password().unwrap_or_default()
+        );
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        // The address of the server is `NULL` when connected through a socket.
+        assert_eq!(assert_ok!(conn.query_scalar::<_, bool>("SELECT inet_server_addr() IS NULL", None)), Some(true));
+    }
+
+    #[test]
+    fn test_connect_options() {
+        let ci_database_uri = url::Url::parse(env!("CI_POSTGRES_URI")).unwrap();
+        let mut options = PostgresConnectOptions::new()
+            .host(ci_database_uri.host_str().unwrap())
+            .user(squill_core::uri::decode_component(ci_database_uri.username()))
+            .dbname(ci_database_uri.path().trim_start_matches('/'))
+            .param("application_name", "squill tests");
+        if let Some(port) = ci_database_uri.port() {
+            options = options.port(port);
+        }
+        if let Some(password) = ci_database_uri.password() {
+            options = options.password(squill_core::uri::decode_component(password));
+        }
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&options.to_uri()));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT current_setting('application_name')", None)),
+            Some("squill tests".to_string())
+        );
+        assert_ok!(options.clone().statement_timeout(Duration::from_secs(1)).connect());
+        assert!(options.dbname("no such database").connect().is_err());
+    }
+
+    #[test]
+    fn test_estimated_row_count() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        let mut stmt = assert_ok!(conn.prepare("SELECT generate_series(1, $1::INT)"));
+        assert_eq!(stmt.estimated_row_count(), None);
+
+        // The rows are returned in several batches, the count is only known once they have all been fetched.
+        let mut rows = assert_ok!(stmt.query_rows(params!(2500)));
+        assert_ok!(assert_some!(rows.next()));
+        drop(rows);
+        assert_eq!(stmt.estimated_row_count(), None);
+        assert_eq!(assert_ok!(stmt.query_rows(params!(2500))).count(), 2500);
+        assert_eq!(stmt.estimated_row_count(), Some(2500));
+
+        // The count is reset by each query.
+        assert_eq!(assert_ok!(stmt.query_rows(params!(0))).count(), 0);
+        assert_eq!(stmt.estimated_row_count(), Some(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        let mut stmt = assert_ok!(conn.prepare("SELECT generate_series($1::INT, 10)"));
+        assert_eq!(assert_ok!(stmt.query_rows(params!(9))).count(), 2);
+        assert_eq!(stmt.estimated_row_count(), Some(2));
+        let mut rows = assert_ok!(stmt.query_rows(params!(1)));
+        assert_eq!(assert_ok!(assert_some!(rows.next())).get::<_, i32>(0), 1);
+        drop(rows);
+
+        // The state of the last query is cleared by the reset.
+        assert_ok!(stmt.reset());
+        assert_eq!(stmt.estimated_row_count(), None);
+        let values: Vec<i32> = assert_ok!(stmt.query_rows(params!(8))).map(|row| row.unwrap().get(0)).collect();
+        assert_eq!(values, vec![8, 9, 10]);
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_maintenance AS SELECT generate_series(1, 100) AS id", None));
+        assert_ok!(conn.vacuum());
+        assert_ok!(conn.analyze(Some("ci_maintenance")));
+        assert_ok!(conn.analyze(None));
+        assert_ok!(conn.optimize());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_maintenance", None)), Some(100));
+    }
+
+    #[test]
+    fn test_session_var() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.set_session_var("app.tenant_id", "it's 42"));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT current_setting('app.tenant_id')", None)),
+            Some("it's 42".to_string())
+        );
+        assert_ok!(conn.reset_session());
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT current_setting('app.tenant_id', true)", None)),
+            Some("".to_string())
+        );
+        assert!(matches!(
+            conn.set_session_var("role = 'admin'; --", "x"),
+            Err(squill_core::Error::InputError { offset: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_copy_out() {
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        assert_execute_eq!(
+            conn,
+            "CREATE TEMPORARY TABLE ci_copy_out AS SELECT id, 'name ' || id AS name FROM generate_series(1, 5000) AS id",
+            5000
+        );
+        let postgres = assert_some!(conn.downcast_mut::<Postgres>());
+
+        let reader = assert_ok!(postgres.copy_out("ci_copy_out", CopyFormat::Csv { header: true }));
+        let lines = std::io::BufReader::new(reader).lines().collect::<std::io::Result<Vec<_>>>().unwrap();
+        assert_eq!(lines.len(), 5001);
+        assert_eq!(lines[0], "id,name");
+        assert_eq!(lines[1], "1,name 1");
+
+        let reader = assert_ok!(postgres.copy_out("SELECT id FROM ci_copy_out WHERE id > 4990;", CopyFormat::Text));
+        assert_eq!(std::io::BufReader::new(reader).lines().count(), 10);
+
+        let mut data = Vec::new();
+        let mut reader = assert_ok!(postgres.copy_out("pg_temp.ci_copy_out", CopyFormat::Binary));
+        assert_ok!(reader.read_to_end(&mut data));
+        assert!(data.starts_with(b"PGCOPY\n"));
+        drop(reader);
+
+        assert!(postgres.copy_out("ci_unknown_table", CopyFormat::Text).is_err());
+    }
+
     #[test]
     fn test_ping() {
         let ci_database_uri = env!("CI_POSTGRES_URI");
         assert_ok!(assert_ok!(Factory::open(ci_database_uri)).ping());
     }
 
+    #[test]
+    fn test_statement_timeout() {
+        let uri = format!("{}?statement_timeout=100", env!("CI_POSTGRES_URI"));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert!(matches!(conn.execute("SELECT pg_sleep(2)", None), Err(squill_core::Error::Timeout)));
+        assert!(matches!(conn.query_row("SELECT pg_sleep(2)", None), Err(squill_core::Error::Timeout)));
+        assert_ok!(conn.query_row("SELECT 1", None));
+
+        // A statement cancelled before the timeout is not reported as a timeout, whatever the message.
+        let cancel =
+            "DO $$ BEGIN RAISE EXCEPTION 'canceling statement due to statement timeout' USING ERRCODE = '57014'; \
+                      END $$";
+        assert!(matches!(conn.execute(cancel, None), Err(squill_core::Error::Cancelled)));
+    }
+
+    #[test]
+    fn test_application_name() {
+        let query = "SELECT current_setting('application_name')";
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>(query, None)), Some("squill".to_string()));
+
+        let uri = format!("{}?application_name=my_app", env!("CI_POSTGRES_URI"));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>(query, None)), Some("my_app".to_string()));
+    }
+
+    #[test]
+    fn test_failover() {
+        let ci_database_uri = url::Url::parse(env!("CI_POSTGRES_URI")).unwrap();
+        let uri = |hosts: &str, params: &str| {
+            format!(
+                "postgres://{}:{}@{}{}?{}",
+                ci_database_uri.username(),
+                ci_database_uri.password().unwrap_or_default(),
+                hosts,
+                ci_database_uri.path(),
+                params
+            )
+        };
+        let good_host = format!("{}:{}", ci_database_uri.host_str().unwrap(), ci_database_uri.port().unwrap_or(5432));
+
+        // Nothing listens on port 1, the connection is refused and the next host is attempted.
+        let hosts = format!("127.0.0.1:1,{}", good_host);
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&uri(&hosts, "connect_timeout=5")));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i32>("SELECT 1", None)), Some(1));
+        assert_ok!(squill_blocking::Connection::open(&uri(&hosts, "target_session_attrs=read-write")));
+
+        // The CI server is a primary, it doesn't accept read-only sessions.
+        assert!(squill_blocking::Connection::open(&uri(&hosts, "target_session_attrs=read-only")).is_err());
+        assert!(squill_blocking::Connection::open(&uri("127.0.0.1:1,127.0.0.1:2", "connect_timeout=5")).is_err());
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        let mut raise = |code: &str| {
+            conn.execute(format!("DO $$ BEGIN RAISE EXCEPTION 'ci' USING ERRCODE = '{}'; END $$", code), None)
+                .unwrap_err()
+        };
+        let error = raise("40P01");
+        assert!(matches!(error, squill_core::Error::Deadlock { .. }));
+        assert!(error.is_retryable());
+        let error = raise("40001");
+        assert!(matches!(error, squill_core::Error::SerializationFailure { .. }));
+        assert!(error.is_retryable());
+        assert!(!raise("23505").is_retryable());
+
+        // A statement cancelled by a user (i.e. `pg_cancel_backend`) is not retried, unlike a statement timeout.
+        let error = raise("57014");
+        assert!(matches!(error, squill_core::Error::Cancelled), "unexpected error: {:?}", error);
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_read_only() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("SET default_transaction_read_only = on", None));
+        let error = conn.execute("CREATE TABLE test_read_only (id INTEGER)", None).unwrap_err();
+        assert!(matches!(error, squill_core::Error::ReadOnly { .. }), "unexpected error: {:?}", error);
+    }
+
+    #[test]
+    fn test_savepoint() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_savepoint (id INTEGER PRIMARY KEY)", None));
+        let mut tx = assert_ok!(conn.begin());
+        assert_ok!(tx.execute("INSERT INTO ci_savepoint (id) VALUES (1)", None));
+        let mut sp = assert_ok!(tx.savepoint());
+        assert_ok!(sp.execute("INSERT INTO ci_savepoint (id) VALUES (2)", None));
+        assert_ok!(sp.rollback_to());
+        assert_ok!(tx.commit());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_savepoint", None)), Some(1));
+    }
+
+    #[test]
+    fn test_execute_many() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_execute_many (id INTEGER PRIMARY KEY, name TEXT)", None));
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO ci_execute_many (id, name) VALUES ($1, $2)"));
+        let parameters = (1..=1000).map(|id: i32| Parameters::Positional(vec![id.into(), format!("e{}", id).into()]));
+        assert_eq!(assert_ok!(stmt.execute_many(parameters)), 1000);
+        drop(stmt);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_execute_many", None)), Some(1000));
+    }
+
     #[test]
     fn test_execute() {
         let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
@@ -53,6 +319,236 @@ mod postgres_tests {
         assert_execute_eq!(conn, "DELETE FROM ci_test WHERE id IN (1, 2)", 2);
     }
 
+    #[test]
+    fn test_rewrite_placeholders() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        conn.set_rewrite_placeholders(true);
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_rewrite (id INTEGER PRIMARY KEY, name TEXT)", None));
+        assert_eq!(assert_ok!(conn.execute("INSERT INTO ci_rewrite (id, name) VALUES (?, ?)", params!(1, "?"))), 1);
+        let row = assert_some!(assert_ok!(conn.query_row("SELECT name FROM ci_rewrite WHERE id = ?::INT", params!(1))));
+        assert_eq!(row.get::<_, String>(0), "?");
+    }
+
+    #[test]
+    fn test_bind_unsupported_data_type() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert!(matches!(
+            conn.execute("SELECT $1::TEXT", Some(Parameters::Positional(vec![Value::UInt64(42)]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "UInt64"
+        ));
+        assert!(matches!(
+            conn.execute("SELECT $1::TEXT", Some(Parameters::Positional(vec![Value::Interval { months: 1, days: 2, nanos: 0 }]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "Interval"
+        ));
+    }
+
+    #[test]
+    fn test_insert_returning_id() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_returning (id SERIAL PRIMARY KEY, name TEXT)", None));
+        assert_eq!(
+            assert_ok!(conn.insert_returning_id("INSERT INTO ci_returning (name) VALUES ($1)", params!("a"))),
+            1
+        );
+        assert_eq!(
+            assert_ok!(
+                conn.insert_returning_id("INSERT INTO ci_returning (name) VALUES ($1) RETURNING id;", params!("b"))
+            ),
+            2
+        );
+
+        // A literal containing "returning" is not a `RETURNING` clause.
+        assert_eq!(
+            assert_ok!(conn.insert_returning_id("INSERT INTO ci_returning (name) VALUES ('not returning')", None)),
+            3
+        );
+    }
+
+    #[test]
+    fn test_bind_uuid() {
+        let uuid = Uuid::parse_str("e5143101-3ced-4a40-a77e-820a7654a2b0").unwrap();
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        assert_execute_eq!(conn, "CREATE TEMPORARY TABLE ci_uuid (id UUID, name TEXT)", 0);
+        assert_execute_eq!(conn, "INSERT INTO ci_uuid (id, name) VALUES ($1, $2)", &[&uuid, &uuid], 1);
+        let mut stmt = assert_ok!(conn.prepare("SELECT id, name FROM ci_uuid WHERE id = $1"));
+        let mut rows = assert_ok!(stmt.query(params!(uuid)));
+        let record_batch = assert_some_ok!(rows.next());
+        assert_eq!(Uuid::decode(record_batch.column(0), 0), uuid);
+        assert_eq!(String::decode(record_batch.column(1), 0), uuid.to_string());
+    }
+
+    #[test]
+    fn test_bind_json() {
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        assert_execute_eq!(conn, "CREATE TEMPORARY TABLE ci_json (id INTEGER, doc JSONB, doc_text JSON)", 0);
+        let doc = Value::from(serde_json::json!({ "a": "hello", "b": [1, 2] }));
+        assert_execute_eq!(conn, "INSERT INTO ci_json (id, doc, doc_text) VALUES ($1, $2, $3)", &[&1, &doc, &doc], 1);
+        let mut stmt = assert_ok!(conn.prepare("SELECT doc->>'a', doc::TEXT, doc_text->'b'->>1 FROM ci_json"));
+        let mut rows = assert_ok!(stmt.query(None));
+        let record_batch = assert_some_ok!(rows.next());
+        assert_eq!(String::decode(record_batch.column(0), 0), "hello");
+        assert_eq!(String::decode(record_batch.column(1), 0), r#"{"a": "hello", "b": [1, 2]}"#);
+        assert_eq!(String::decode(record_batch.column(2), 0), "2");
+    }
+
+    #[test]
+    fn test_bind_time_and_array() {
+        let time = NaiveTime::from_hms_micro_opt(13, 20, 10, 101_202).unwrap();
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        let mut stmt = assert_ok!(conn.prepare("SELECT $1::TIME, cardinality($2::INT4[]), 5 = ANY($3)"));
+        let mut rows = assert_ok!(stmt.query(params!(time, vec![1, 2, 3], vec![4, 5, 6])));
+        let record_batch = assert_some_ok!(rows.next());
+        assert_eq!(NaiveTime::decode(record_batch.column(0), 0), time);
+        assert_eq!(i32::decode(record_batch.column(1), 0), 3);
+        assert!(bool::decode(record_batch.column(2), 0));
+    }
+
+    #[test]
+    fn test_query_dml() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_query_dml (id INTEGER)", None));
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO ci_query_dml (id) VALUES ($1), ($2)"));
+        let row = assert_some!(assert_ok!(stmt.query_row(params!(1, 2))));
+        assert_eq!(row.schema(), affected_rows_schema());
+        assert_eq!(row.get::<_, i64>(AFFECTED_ROWS_COLUMN), 2);
+        assert!(stmt.schema().fields().is_empty());
+
+        // The schema returned along with the record batches is the one of the first record batch.
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(params!(3, 4)));
+        assert_eq!(schema, affected_rows_schema());
+        assert_eq!(batches.count(), 1);
+        drop(stmt);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_query_dml", None)), Some(4));
+    }
+
+    #[test]
+    fn test_dml_cte() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_cte (id INTEGER)", None));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE ci_cte_archive (id INTEGER)", None));
+        assert_ok!(conn.execute("INSERT INTO ci_cte (id) SELECT generate_series(1, 5)", None));
+
+        // A DML CTE returning rows is queried like a `SELECT`.
+        let ids = assert_ok!(conn.query_map_rows(
+            "WITH deleted AS (DELETE FROM ci_cte WHERE id <= $1 RETURNING id) SELECT id FROM deleted ORDER BY id",
+            params!(2),
+            |row| Ok(row.try_get::<_, i32>(0)?)
+        ));
+        assert_eq!(ids, vec![1, 2]);
+
+        // A DML-only CTE reports the rows affected by the outer statement.
+        let statement = "WITH moved AS (DELETE FROM ci_cte WHERE id <= $1 RETURNING id) \
+                         INSERT INTO ci_cte_archive (id) SELECT id FROM moved";
+        assert_eq!(assert_ok!(conn.execute(statement, params!(4))), 2);
+        let row = assert_some!(assert_ok!(conn.query_row(statement, params!(5))));
+        assert_eq!(row.get::<_, i64>(AFFECTED_ROWS_COLUMN), 1);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_cte", None)), Some(0));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM ci_cte_archive", None)), Some(3));
+    }
+
+    #[test]
+    fn test_parameter_count() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        assert_eq!(assert_ok!(conn.prepare("SELECT 1")).parameter_count(), 0);
+        let stmt = assert_ok!(conn.prepare("SELECT $1::INT + 1, $2::TEXT"));
+        assert_eq!(stmt.parameter_count(), 2);
+        assert_eq!(stmt.parameter_types(), Some(vec![arrow_schema::DataType::Int32, arrow_schema::DataType::Utf8]));
+    }
+
+    #[test]
+    fn test_prepare_typed() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        // The types of the parameters cannot be inferred from the statement alone.
+        assert!(conn.prepare("SELECT $1 + $2").is_err());
+        let mut stmt = assert_ok!(
+            conn.prepare_typed("SELECT $1 + $2", &[arrow_schema::DataType::Int64, arrow_schema::DataType::Int64])
+        );
+        assert_eq!(stmt.parameter_types(), Some(vec![arrow_schema::DataType::Int64, arrow_schema::DataType::Int64]));
+        assert_eq!(assert_ok!(stmt.query_scalar::<i64>(params!(40_i64, 2_i64))), Some(42));
+        drop(stmt);
+
+        // The types of the parameters without a hint are still inferred.
+        let mut stmt = assert_ok!(conn.prepare_typed("SELECT $1 || $2::TEXT", &[arrow_schema::DataType::Utf8]));
+        assert_eq!(assert_ok!(stmt.query_scalar::<String>(params!("a", "b"))), Some("ab".to_string()));
+        drop(stmt);
+
+        assert!(matches!(
+            conn.prepare_typed("SELECT $1", &[arrow_schema::DataType::Float16]),
+            Err(squill_core::Error::UnsupportedDataType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reused_placeholder() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(env!("CI_POSTGRES_URI")));
+        let mut stmt = assert_ok!(conn.prepare("SELECT $1::INT + $2::INT, $1::INT * 10"));
+        assert_eq!(stmt.parameter_count(), 2);
+        let row = assert_some!(assert_ok!(stmt.query_row(params!(4, 1))));
+        assert_eq!((row.get::<_, i32>(0), row.get::<_, i32>(1)), (5, 40));
+
+        // The count of the values must match the highest index of the placeholders.
+        assert!(matches!(
+            stmt.query_row(params!(4)),
+            Err(squill_core::Error::InvalidParameterCount { expected: 2, actual: 1 })
+        ));
+        assert!(matches!(
+            stmt.execute(params!(4, 1, 2)),
+            Err(squill_core::Error::InvalidParameterCount { expected: 2, actual: 3 })
+        ));
+        assert!(matches!(
+            stmt.execute(None),
+            Err(squill_core::Error::InvalidParameterCount { expected: 2, actual: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        assert_execute_eq!(conn, "CREATE TEMPORARY TABLE ci_describe (id INTEGER, name TEXT)", 0);
+        let described =
+            assert_ok!(conn.describe("SELECT id, name, $2::BIGINT AS total FROM ci_describe WHERE id > $1"));
+        assert_eq!(described.param_count, 2);
+        assert_eq!(
+            described.schema.fields().iter().map(|field| field.data_type().clone()).collect::<Vec<_>>(),
+            vec![arrow_schema::DataType::Int32, arrow_schema::DataType::Utf8, arrow_schema::DataType::Int64]
+        );
+        assert_eq!(described.schema.field(2).name(), "total");
+        assert!(conn.describe("SELECT * FROM ci_unknown_table").is_err());
+    }
+
+    #[test]
+    fn test_bind_timestamptz() {
+        let datetime = chrono::DateTime::parse_from_rfc3339("2024-07-04T10:21:36.101202+02:00").unwrap();
+        let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
+        assert_execute_eq!(conn, "CREATE TEMPORARY TABLE ci_timestamptz (at TIMESTAMPTZ, at_local TIMESTAMP)", 0);
+        assert_execute_eq!(
+            conn,
+            "INSERT INTO ci_timestamptz (at, at_local) VALUES ($1, $2)",
+            &[&datetime, &datetime],
+            1
+        );
+        let mut stmt =
+            assert_ok!(conn.prepare("SELECT at, at_local, at = '2024-07-04T08:21:36.101202Z' FROM ci_timestamptz"));
+        let mut rows = assert_ok!(stmt.query(None));
+        let record_batch = assert_some_ok!(rows.next());
+
+        // The time zone of a `TIMESTAMPTZ` is recorded in the schema, the instant is preserved.
+        assert_eq!(
+            record_batch.schema().field(0).data_type(),
+            &arrow_schema::DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, Some("+00:00".into()))
+        );
+        assert_eq!(
+            Value::decode(record_batch.column(0), 0),
+            Value::TimestampTz(TimeUnit::Microsecond, datetime.timestamp_micros(), "+00:00".to_string())
+        );
+        assert_eq!(chrono::DateTime::<chrono::FixedOffset>::decode(record_batch.column(0), 0), datetime);
+        assert_eq!(
+            Value::decode(record_batch.column(1), 0),
+            Value::Timestamp(TimeUnit::Microsecond, datetime.timestamp_micros())
+        );
+        assert!(bool::decode(record_batch.column(2), 0));
+    }
+
     #[test]
     fn test_query() {
         let mut conn = assert_ok!(Factory::open(env!("CI_POSTGRES_URI")));
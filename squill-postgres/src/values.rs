@@ -1,7 +1,7 @@
+use squill_core::driver::Result;
 use squill_core::parameters::Parameters;
 use squill_core::values::{TimeUnit, Value};
 use squill_core::Error;
-use std::any::type_name;
 use std::fmt::Debug;
 
 /// The number of microseconds since midnight, January 1st, 2000.
@@ -10,8 +10,10 @@ const EPOCH_2000_IN_MICRO_SEC: i64 = 946684800 * 1_000_000;
 /// The number of days since January 1st, 2000.
 const EPOCH_2000_IN_DAYS: i32 = 10957;
 
-fn get_unsupported_data_type_error<T>() -> Error {
-    Error::UnsupportedDataType { data_type: type_name::<T>().to_string() }
+/// The error returned when a value cannot be bound to a PostgreSQL statement, named after its variant as with the other
+/// drivers (i.e. `UInt64`).
+fn unsupported_data_type(value: &Value) -> Error {
+    Error::UnsupportedDataType { data_type: value.type_name().to_string() }
 }
 
 pub struct Adapter<'a>(pub &'a Value);
@@ -38,12 +40,12 @@ impl<'a> postgres::types::ToSql for Adapter<'a> {
             Value::Int16(value) => value.to_sql(ty, out),
             Value::Int32(value) => value.to_sql(ty, out),
             Value::Int64(value) => value.to_sql(ty, out),
-            Value::Int128(_value) => Err(get_unsupported_data_type_error::<i128>().into()),
-            Value::UInt8(_value) => Err(get_unsupported_data_type_error::<u8>().into()),
-            Value::UInt16(_value) => Err(get_unsupported_data_type_error::<u16>().into()),
-            Value::UInt32(_value) => Err(get_unsupported_data_type_error::<u32>().into()),
-            Value::UInt64(_value) => Err(get_unsupported_data_type_error::<u64>().into()),
-            Value::UInt128(_value) => Err(get_unsupported_data_type_error::<u128>().into()),
+            Value::Int128(_)
+            | Value::UInt8(_)
+            | Value::UInt16(_)
+            | Value::UInt32(_)
+            | Value::UInt64(_)
+            | Value::UInt128(_) => Err(unsupported_data_type(self.0).into()),
             Value::Float32(value) => value.to_sql(ty, out),
             Value::Float64(value) => value.to_sql(ty, out),
             Value::String(value) => value.to_sql(ty, out),
@@ -54,13 +56,14 @@ impl<'a> postgres::types::ToSql for Adapter<'a> {
                 postgres_protocol::types::date_to_sql(EPOCH_2000_IN_DAYS - *value, out);
                 Ok(postgres_types::IsNull::No)
             }
-            Value::Timestamp(unit, value) => {
+            Value::Timestamp(unit, value) | Value::TimestampTz(unit, value, _) => {
                 // Serializes a `TIMESTAMP` or `TIMESTAMPTZ` value.
-                // The value should represent the number of microseconds since midnight, January 1st, 2000.
+                // The value should represent the number of microseconds since midnight, January 1st, 2000. PostgreSQL
+                // stores a `TIMESTAMPTZ` in UTC, so the time zone of the value is not serialized.
                 let micro_secs = match unit {
                     TimeUnit::Second => *value * 1_000_000 - EPOCH_2000_IN_MICRO_SEC, // FIXME: This could overflow
                     TimeUnit::Millisecond => *value * 1_000 - EPOCH_2000_IN_MICRO_SEC, // FIXME: This could overflow
-                    TimeUnit::Microsecond => *value,
+                    TimeUnit::Microsecond => *value - EPOCH_2000_IN_MICRO_SEC,
                     TimeUnit::Nanosecond => *value / 1_000 - EPOCH_2000_IN_MICRO_SEC,
                 };
                 postgres_protocol::types::timestamp_to_sql(micro_secs, out);
@@ -78,24 +81,41 @@ impl<'a> postgres::types::ToSql for Adapter<'a> {
                 postgres_protocol::types::time_to_sql(micro_secs, out);
                 Ok(postgres_types::IsNull::No)
             }
-            Value::Interval { months, days, nanos } => {
-                // Serializes an `INTERVAL` value.
-                // The value should represent the number of microseconds.
-                todo!(
-                    "Interval serialization is not implemented yet: {} months, {} days, {} nanos",
-                    months,
-                    days,
-                    nanos
-                );
+            Value::Interval { .. } | Value::Decimal(_) => Err(unsupported_data_type(self.0).into()),
+            Value::Uuid(value) => {
+                // Serializes an `UUID` value using its 16 bytes, falling back to the string representation if the
+                // parameter is not an `UUID` (i.e. `TEXT`).
+                if *ty == postgres_types::Type::UUID {
+                    postgres_protocol::types::uuid_to_sql(*value.as_bytes(), out);
+                    Ok(postgres_types::IsNull::No)
+                } else {
+                    value.to_string().to_sql(ty, out)
+                }
+            }
+            Value::Json(value) => {
+                // Serializes a `JSON` or `JSONB` value.
+                // `JSONB` is the textual representation of the document prefixed by the version of the format (1).
+                if *ty == postgres_types::Type::JSONB {
+                    out.extend_from_slice(&[1]);
+                }
+                out.extend_from_slice(value.as_bytes());
+                Ok(postgres_types::IsNull::No)
             }
-            Value::Decimal(value) => {
-                todo!("Decimal serialization is not implemented yet: {}", value);
+            Value::List(values) => {
+                // Serializes an array, each element being serialized using the type of the members of the array.
+                if let postgres_types::Kind::Array(_) = ty.kind() {
+                    let adapters: Vec<Adapter> = values.iter().map(Adapter).collect();
+                    adapters.to_sql(ty, out)
+                } else {
+                    Err(Error::InvalidType { expected: ty.to_string(), actual: "List".to_string() }.into())
+                }
             }
         }
     }
 
     fn accepts(_ty: &postgres::types::Type) -> bool {
-        todo!()
+        // The compatibility between the value and the type is checked by `to_sql`.
+        true
     }
 
     fn to_sql_checked(
@@ -110,6 +130,25 @@ impl<'a> postgres::types::ToSql for Adapter<'a> {
     }
 }
 
+/// Check that the parameters can be bound by PostgreSQL, which only supports positional parameters (`$1`, `$2`, ...).
+///
+/// The `expected` count is the number of parameters of the prepared statement, which is the highest index of its
+/// placeholders: a placeholder used several times (i.e. `$1` in `WHERE a = $1 OR b = $1`) is a single parameter bound
+/// to the value at its index.
+pub(crate) fn check_positional(parameters: &Option<Parameters>, expected: usize) -> Result<()> {
+    let actual = match parameters {
+        Some(Parameters::Named(_)) => {
+            return Err(Error::DriverError { error: "Named parameters are not supported by PostgreSQL".into() }.into())
+        }
+        Some(Parameters::Positional(values)) => values.len(),
+        None => 0,
+    };
+    if actual != expected {
+        return Err(Error::InvalidParameterCount { expected, actual }.into());
+    }
+    Ok(())
+}
+
 pub struct ParametersIterator<'p> {
     parameters: &'p Option<Parameters>,
     index: usize,
@@ -136,6 +175,8 @@ impl<'p> Iterator for ParametersIterator<'p> {
                     None
                 }
             }
+            // Rejected by `check_positional` before binding.
+            Some(Parameters::Named(_)) => None,
         }
     }
 }
@@ -145,6 +186,7 @@ impl<'p> ExactSizeIterator for ParametersIterator<'p> {
         match self.parameters {
             None => 0,
             Some(Parameters::Positional(values)) => values.len() - self.index,
+            Some(Parameters::Named(_)) => 0,
         }
     }
 }
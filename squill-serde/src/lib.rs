@@ -1,9 +1,29 @@
 use proc_macro::TokenStream;
 use quote::quote;
 
-/// A procedural macro that generates an implementation of the `Decode` trait for a given type using Serde.
+/// A procedural macro that generates an implementation of the `Decode` trait for a given type.
 ///
-/// The generated implementation assumes that the type can be deserialized from a JSON string.
+/// For a C-like enum (an enum whose variants have no fields), the generated implementation decodes the variant from
+/// its name stored in a string column. The name of a variant can be changed using `#[squill(rename = "...")]` and
+/// decoding a string that doesn't match any variant returns an [Error::InvalidType](squill_core::Error::InvalidType).
+/// Like the other decoders, decoding a null value returns an `InvalidType` and an index out of bounds returns an
+/// [Error::OutOfBounds](squill_core::Error::OutOfBounds).
+///
+/// #example
+/// ```rust,ignore
+/// use squill_drivers::serde::Decode;
+///
+/// #[derive(Debug, Decode)]
+/// enum Status {
+///     #[squill(rename = "active")]
+///     Active,
+///     #[squill(rename = "inactive")]
+///     Inactive,
+/// }
+/// ```
+///
+/// For any other type, the generated implementation uses Serde and assumes that the type can be deserialized from a
+/// JSON string.
 ///
 /// #example
 /// ```rust,ignore
@@ -16,14 +36,79 @@ use quote::quote;
 //     age: u32,
 // }
 /// ```
-#[proc_macro_derive(Decode)]
+#[proc_macro_derive(Decode, attributes(squill))]
 pub fn decode_derive(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-    // Get the name of the type to which the macro is applied
-    let name = input.ident;
+    match &input.data {
+        syn::Data::Enum(data) if data.variants.iter().all(|variant| variant.fields.is_empty()) => {
+            decode_enum_derive(&input.ident, data).unwrap_or_else(|e| e.to_compile_error()).into()
+        }
+        _ => decode_json_derive(&input.ident),
+    }
+}
+
+/// Generate the implementation of the `Decode` trait for a C-like enum.
+fn decode_enum_derive(name: &syn::Ident, data: &syn::DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let mut variant_names = Vec::with_capacity(data.variants.len());
+    let mut variant_idents = Vec::with_capacity(data.variants.len());
+    for variant in data.variants.iter() {
+        let mut variant_name = variant.ident.to_string();
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("squill")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    variant_name = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported squill attribute, expected `rename`"))
+                }
+            })?;
+        }
+        variant_names.push(variant_name);
+        variant_idents.push(&variant.ident);
+    }
+    let type_name = name.to_string();
+
+    Ok(quote! {
+        impl squill_drivers::Decode for #name {
+
+            fn decode(array: &dyn arrow_array::array::Array, index: usize) -> Self {
+                match Self::try_decode(array, index) {
+                    Ok(value) => value,
+                    Err(e) => panic!("Unable to decode (reason: {:?})", e),
+                }
+            }
 
+            fn try_decode(array: &dyn arrow_array::array::Array, index: usize) -> squill_drivers::Result<Self> {
+                use squill_drivers::Error;
+                if index >= array.len() {
+                    return Err(Error::OutOfBounds { index });
+                }
+                // Arrow stores a placeholder (an empty string) in the null slots of a `StringArray`.
+                if array.is_null(index) || array.as_any().is::<arrow_array::NullArray>() {
+                    return Err(Error::InvalidType { expected: "non-null value".to_string(), actual: "null".to_string() });
+                }
+                let value = array
+                    .as_any()
+                    .downcast_ref::<arrow_array::StringArray>()
+                    .ok_or_else(|| Error::InvalidType {
+                        expected: "StringArray".to_string(),
+                        actual: array.data_type().to_string(),
+                    })?
+                    .value(index);
+
+                match value {
+                    #( #variant_names => Ok(Self::#variant_idents), )*
+                    _ => Err(Error::InvalidType { expected: #type_name.to_string(), actual: value.to_string() }),
+                }
+            }
+        }
+    })
+}
+
+/// Generate the implementation of the `Decode` trait for a type deserialized from a JSON string.
+fn decode_json_derive(name: &syn::Ident) -> TokenStream {
     // Generate the implementation of the trait for the type
     let expanded = quote! {
         impl squill_drivers::Decode for #name {
@@ -37,6 +122,13 @@ pub fn decode_derive(input: TokenStream) -> TokenStream {
 
             fn try_decode(array: &dyn arrow_array::array::Array, index: usize) -> squill_drivers::Result<Self> {
                 use squill_drivers::Error;
+                if index >= array.len() {
+                    return Err(Error::OutOfBounds { index });
+                }
+                // Arrow stores a placeholder (an empty string) in the null slots of a `StringArray`.
+                if array.is_null(index) || array.as_any().is::<arrow_array::NullArray>() {
+                    return Err(Error::InvalidType { expected: "non-null value".to_string(), actual: "null".to_string() });
+                }
                 let json_str = array
                     .as_any()
                     .downcast_ref::<arrow_array::StringArray>()
@@ -0,0 +1,46 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// A handle to a BLOB stored in an SQLite database, opened by [crate::Sqlite::open_blob].
+///
+/// The content of the BLOB is read and written incrementally using [Read], [Write] and [Seek] without loading it all
+/// in memory. The size of a BLOB cannot be changed by writing to it, writing past its end writes nothing.
+///
+/// The handle is closed when dropped. If the row of the BLOB is updated or deleted while the handle is open, the handle
+/// expires and any further read or write fails.
+pub struct Blob<'c> {
+    pub(crate) inner: rusqlite::blob::Blob<'c>,
+}
+
+impl Blob<'_> {
+    /// Get the size of the BLOB in bytes.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Check if the BLOB is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl Read for Blob<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Write for Blob<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Seek for Blob<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
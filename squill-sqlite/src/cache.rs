@@ -0,0 +1,72 @@
+use squill_core::driver::CacheStats;
+
+/// The default capacity of the cache of prepared statements (the same as `rusqlite`).
+pub(crate) const DEFAULT_CAPACITY: usize = 16;
+
+/// The bookkeeping of the cache of prepared statements of a connection.
+///
+/// The statements are cached by `rusqlite` (see `prepare_cached`) which doesn't expose any statistics, so this mirrors
+/// its behavior: a statement is removed from the cache while in use and put back as the most recently used once
+/// dropped, evicting the least recently used statement if the capacity is exceeded. The statements are identified by
+/// their trimmed SQL.
+pub(crate) struct StatementCache {
+    capacity: usize,
+
+    /// The keys of the statements in the cache, from the least to the most recently used.
+    keys: Vec<String>,
+
+    hits: u64,
+    misses: u64,
+}
+
+impl StatementCache {
+    pub(crate) fn new() -> Self {
+        Self { capacity: DEFAULT_CAPACITY, keys: Vec::new(), hits: 0, misses: 0 }
+    }
+
+    /// Get the key of a statement.
+    pub(crate) fn key(statement: &str) -> &str {
+        statement.trim()
+    }
+
+    /// Take a statement out of the cache, returns `true` if it was found.
+    pub(crate) fn take(&mut self, key: &str) -> bool {
+        match self.keys.iter().position(|k| k == key) {
+            Some(index) => {
+                self.keys.remove(index);
+                self.hits += 1;
+                true
+            }
+            None => {
+                self.misses += 1;
+                false
+            }
+        }
+    }
+
+    /// Put back a statement in the cache once it is no longer used.
+    pub(crate) fn put(&mut self, key: String) {
+        self.keys.retain(|k| *k != key);
+        self.keys.push(key);
+        self.evict();
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats { hits: self.hits, misses: self.misses, size: self.keys.len() }
+    }
+
+    fn evict(&mut self) {
+        if self.keys.len() > self.capacity {
+            self.keys.drain(..self.keys.len() - self.capacity);
+        }
+    }
+}
@@ -1,9 +1,156 @@
+use crate::blob::Blob;
+use crate::cache::StatementCache;
 use crate::errors::driver_error;
-use crate::statement::SqliteStatement;
+use crate::statement::{is_insert, SqliteStatement};
+use crate::value::{from_sqlite_value, to_sqlite_value};
 use crate::{Sqlite, DRIVER_NAME};
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::ValueRef;
+use rusqlite::DatabaseName;
+use squill_core::driver::CacheStats;
 use squill_core::driver::DriverConnection;
+use squill_core::driver::DriverInterruptHandle;
 use squill_core::driver::DriverStatement;
 use squill_core::driver::Result;
+use squill_core::quote::check_identifier;
+use squill_core::values::Value;
+use std::sync::Arc;
+
+/// The name of the function called by SQLite to evaluate the `REGEXP` operator.
+const REGEXP_FUNCTION: &str = "regexp";
+
+impl Sqlite {
+    /// Register a scalar function that can be called from the statements executed on this connection.
+    ///
+    /// - `name`: the name of the function in SQL, registering a function with the same name and number of arguments
+    ///   replaces the previous one.
+    /// - `n_args`: the number of arguments of the function, or `-1` for a variable number of arguments.
+    /// - `function`: the closure evaluating the function, it is given the arguments converted into [Value] (`INTEGER`
+    ///   as [Value::Int64], `REAL` as [Value::Float64], `TEXT` as [Value::String] and `BLOB` as [Value::Blob]).
+    ///
+    /// The function is only visible from this connection and remains registered until the connection is closed.
+    ///
+    /// The closure is called synchronously by SQLite while a statement is executed, on the thread using the connection.
+    /// Because a connection can be moved to another thread (i.e. the worker of an async connection), the closure must
+    /// be `Send` and `'static`: it cannot borrow anything and must not use the connection it is registered on. An error
+    /// returned by the closure aborts the statement and is reported as a driver error. The function is not flagged as
+    /// deterministic so SQLite won't use it in an index or a `CHECK` constraint.
+    ///
+    /// ```rust,ignore
+    /// let sqlite = conn.downcast_mut::<Sqlite>().unwrap();
+    /// sqlite.create_scalar_function("add_one", 1, |args| match &args[0] {
+    ///     Value::Int64(value) => Ok(Value::Int64(value + 1)),
+    ///     _ => Ok(Value::Null),
+    /// })?;
+    /// ```
+    pub fn create_scalar_function<F>(&mut self, name: &str, n_args: i32, function: F) -> Result<()>
+    where
+        F: Fn(&[Value]) -> Result<Value> + Send + 'static,
+    {
+        self.conn
+            .create_scalar_function(name, n_args, FunctionFlags::SQLITE_UTF8, move |ctx| {
+                let args = (0..ctx.len())
+                    .map(|index| from_sqlite_value(ctx.get_raw(index)))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                let value = function(&args).map_err(rusqlite::Error::UserFunctionError)?;
+                to_sqlite_value(&value)
+            })
+            .map_err(driver_error)?;
+        Ok(())
+    }
+
+    /// Register the function used by the `REGEXP` operator.
+    ///
+    /// SQLite supports the `X REGEXP Y` syntax but doesn't come with an implementation of it. Once registered, the
+    /// operator matches the text `X` against the regular expression `Y` using the syntax of the
+    /// [regex](https://docs.rs/regex) crate. The result is `NULL` if any of the operands is `NULL`.
+    ///
+    /// The regular expressions are compiled once per statement.
+    pub fn register_regexp(&mut self) -> Result<()> {
+        self.conn
+            .create_scalar_function(
+                REGEXP_FUNCTION,
+                2,
+                FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+                |ctx| {
+                    // `X REGEXP Y` is evaluated as `regexp(Y, X)`.
+                    if matches!(ctx.get_raw(0), ValueRef::Null) || matches!(ctx.get_raw(1), ValueRef::Null) {
+                        return Ok(None);
+                    }
+                    let regex: Arc<Regex> =
+                        ctx.get_or_create_aux(0, |pattern| -> Result<Regex> { Ok(Regex::new(pattern.as_str()?)?) })?;
+                    Ok(Some(regex.is_match(ctx.get_raw(1).as_str()?)))
+                },
+            )
+            .map_err(driver_error)?;
+        Ok(())
+    }
+
+    /// Set the maximum number of prepared statements kept in the cache of the connection (16 by default).
+    ///
+    /// The least recently used statements are evicted if the cache holds more statements than the new capacity, a
+    /// capacity of `0` disables the cache.
+    pub fn set_prepared_cache_capacity(&mut self, capacity: usize) {
+        self.conn.set_prepared_statement_cache_capacity(capacity);
+        self.cache.borrow_mut().set_capacity(capacity);
+    }
+
+    /// Set the value of a pragma (i.e. `cache_size` or `foreign_keys`).
+    ///
+    /// The name of the pragma must be a plain identifier (see [squill_core::quote::check_identifier]) since it cannot
+    /// be given as a parameter of the statement.
+    ///
+    /// ```rust,ignore
+    /// let sqlite = conn.downcast_mut::<Sqlite>().unwrap();
+    /// sqlite.set_pragma("cache_size", -4000)?;
+    /// ```
+    pub fn set_pragma<V: Into<Value>>(&mut self, name: &str, value: V) -> Result<()> {
+        check_identifier(name)?;
+        let value = to_sqlite_value(&value.into()).map_err(driver_error)?;
+        self.conn.pragma_update(None, name, value).map_err(driver_error)?;
+        Ok(())
+    }
+
+    /// Get the value of a pragma.
+    ///
+    /// Returns `None` if the pragma doesn't return any value (i.e. a pragma that is unknown to SQLite) or if the value
+    /// is null. Only the first value is returned for the pragmas returning several rows or columns.
+    pub fn get_pragma(&self, name: &str) -> Result<Option<Value>> {
+        check_identifier(name)?;
+        let mut stmt = self.conn.prepare(&format!("PRAGMA {}", name)).map_err(driver_error)?;
+        let mut rows = stmt.query([]).map_err(driver_error)?;
+        match rows.next().map_err(driver_error)? {
+            Some(row) => match from_sqlite_value(row.get_ref(0).map_err(driver_error)?).map_err(driver_error)? {
+                Value::Null => Ok(None),
+                value => Ok(Some(value)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Open a BLOB for incremental I/O.
+    ///
+    /// - `db`: the name of the database containing the table (`main`, `temp` or the name of an attached database).
+    /// - `table`, `column`: the table and the column of the BLOB.
+    /// - `rowid`: the `ROWID` of the row of the BLOB.
+    /// - `read_only`: if `true` the BLOB can only be read, otherwise it can also be written.
+    ///
+    /// The returned [Blob] reads and writes the BLOB in chunks, which avoids loading a large BLOB in memory. The size
+    /// of a BLOB cannot be changed through the handle, a BLOB meant to be written is usually inserted first using
+    /// `zeroblob(N)`.
+    ///
+    /// ```rust,ignore
+    /// let sqlite = conn.downcast_mut::<Sqlite>().unwrap();
+    /// let mut blob = sqlite.open_blob("main", "files", "content", rowid, true)?;
+    /// std::io::copy(&mut blob, &mut file)?;
+    /// ```
+    pub fn open_blob(&self, db: &str, table: &str, column: &str, rowid: i64, read_only: bool) -> Result<Blob<'_>> {
+        let inner =
+            self.conn.blob_open(DatabaseName::Attached(db), table, column, rowid, read_only).map_err(driver_error)?;
+        Ok(Blob { inner })
+    }
+}
 
 impl DriverConnection for Sqlite {
     fn driver_name(&self) -> &str {
@@ -17,7 +164,14 @@ impl DriverConnection for Sqlite {
         // and more about ensuring the internal state of the connection and database instance is healthy.
         // Executing a lightweight, non-intrusive query (like SELECT 1) is a simple and efficient way to validate the
         // connection.
-        match self.conn.execute_batch("SELECT 1") {
+        if let Some(statement_timeout) = &self.statement_timeout {
+            statement_timeout.start();
+        }
+        let result = self.conn.execute_batch("SELECT 1");
+        if let Some(statement_timeout) = &self.statement_timeout {
+            statement_timeout.stop();
+        }
+        match result {
             Ok(_) => Ok(()),
             Err(error) => Err(error.into()),
         }
@@ -27,10 +181,45 @@ impl DriverConnection for Sqlite {
         Ok(())
     }
 
+    /// SQLite supports interrupting the statement running on the connection using `sqlite3_interrupt`.
+    fn interrupt_handle(&self) -> Option<Box<dyn DriverInterruptHandle>> {
+        Some(Box::new(SqliteInterruptHandle(self.conn.get_interrupt_handle())))
+    }
+
+    /// The statements are prepared using the cache of `rusqlite` (see [Sqlite::set_prepared_cache_capacity]).
     fn prepare<'c: 's, 's>(&'c mut self, statement: &str) -> Result<Box<dyn DriverStatement + 's>> {
+        let key = StatementCache::key(statement);
+        let hit = self.cache.borrow_mut().take(key);
+        let inner = self.conn.prepare_cached(statement).map_err(driver_error)?;
+        // An empty statement (i.e. only a comment) has no SQL and is not put back in the cache by `rusqlite`.
+        let cache_key = (hit || inner.expanded_sql().is_some()).then(|| key.to_string());
         Ok(Box::new(SqliteStatement {
-            inner: self.conn.prepare(statement).map_err(driver_error)?,
+            conn: &self.conn,
+            inner,
             options: self.options.clone(),
+            max_batch_rows: None,
+            statement_timeout: self.statement_timeout.clone(),
+            cache: &self.cache,
+            cache_key,
+            is_insert: is_insert(statement),
         }))
     }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        Some(self.cache.borrow().stats())
+    }
+
+    fn clear_prepared_cache(&mut self) {
+        self.conn.flush_prepared_statement_cache();
+        self.cache.borrow_mut().clear();
+    }
+}
+
+/// The handle used to interrupt the statement running on an SQLite connection.
+struct SqliteInterruptHandle(rusqlite::InterruptHandle);
+
+impl DriverInterruptHandle for SqliteInterruptHandle {
+    fn interrupt(&self) {
+        self.0.interrupt();
+    }
 }
@@ -1,3 +1,4 @@
+use crate::timeout::StatementTimeout;
 use rusqlite::ffi::ErrorCode;
 use squill_core::error::Error;
 
@@ -5,12 +6,33 @@ use squill_core::error::Error;
 pub(crate) fn driver_error(rusqlite_error: rusqlite::Error) -> Error {
     match rusqlite_error {
         rusqlite::Error::QueryReturnedNoRows => Error::NotFound,
+        // The value of a parameter cannot be bound, the error raised by the adapter is returned as is.
+        rusqlite::Error::ToSqlConversionFailure(error) if error.is::<Error>() => match error.downcast::<Error>() {
+            Ok(error) => *error,
+            Err(error) => Error::DriverError { error: Box::new(rusqlite::Error::ToSqlConversionFailure(error)) },
+        },
         rusqlite::Error::SqliteFailure(e, _) => match e.code {
             ErrorCode::ConstraintViolation => Error::ConstraintViolation { error: Box::new(rusqlite_error) },
             ErrorCode::DiskFull => Error::StorageFull { error: Box::new(rusqlite_error) },
+            ErrorCode::ReadOnly => Error::ReadOnly { error: Box::new(rusqlite_error) },
             ErrorCode::OutOfMemory => Error::OutOfMemory { error: Box::new(rusqlite_error) },
+            // The database is still locked by another connection once the busy timeout expired.
+            ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => Error::Busy { error: Box::new(rusqlite_error) },
+            // The statement was interrupted, see `statement_error` for the interruptions caused by the statement timeout.
+            ErrorCode::OperationInterrupted => Error::Cancelled,
             _ => Error::DriverError { error: Box::new(rusqlite_error) },
         },
         _ => Error::DriverError { error: Box::new(rusqlite_error) },
     }
 }
+
+/// Convert the error raised while executing a statement into a `squill_core::error::Error`.
+///
+/// A statement interrupted by the progress handler enforcing the statement timeout is reported as [Error::Timeout],
+/// otherwise an interrupted statement (see `DriverConnection::interrupt_handle`) is reported as [Error::Cancelled].
+pub(crate) fn statement_error(rusqlite_error: rusqlite::Error, statement_timeout: Option<&StatementTimeout>) -> Error {
+    match driver_error(rusqlite_error) {
+        Error::Cancelled if statement_timeout.is_some_and(StatementTimeout::is_expired) => Error::Timeout,
+        error => error,
+    }
+}
@@ -1,7 +1,23 @@
+use crate::cache::StatementCache;
+use crate::timeout::StatementTimeout;
 use crate::Sqlite;
 use crate::DRIVER_NAME;
+use rusqlite::ffi::ErrorCode;
 use squill_core::driver::{DriverConnection, DriverFactory, DriverOptionsRef, Result};
+use squill_core::uri::{parse_millis, redact_uri, STATEMENT_TIMEOUT_PARAM};
 use squill_core::Error;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// The name of the URI query parameter to set the busy timeout (in milliseconds).
+pub(crate) const BUSY_TIMEOUT_PARAM: &str = "busy_timeout";
+
+/// The busy timeout used when the URI doesn't have a `busy_timeout` parameter (same as `rusqlite`).
+const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The delay before retrying to open a busy database, doubled after each attempt up to [MAX_OPEN_RETRY_DELAY].
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(10);
+const MAX_OPEN_RETRY_DELAY: Duration = Duration::from_millis(200);
 
 pub(crate) struct SqliteFactory {}
 
@@ -14,6 +30,14 @@ impl DriverFactory for SqliteFactory {
     ///
     /// The URI must be in the format as defined at https://www.sqlite.org/uri.html` except for the scheme that is
     /// expected to be `sqlite` instead of `file`.
+    ///
+    /// In addition to the parameters supported by SQLite, the URI can have:
+    /// - a `statement_timeout` parameter (in milliseconds) after which a running statement is interrupted,
+    /// - a `busy_timeout` parameter (in milliseconds, 5000 by default) during which an access to a database locked by
+    ///   another connection is retried.
+    ///
+    /// Opening a database locked by another connection (i.e. a WAL database being checkpointed by another process) is
+    /// also retried with a short backoff until the busy timeout expires.
     fn open(&self, uri: &str, options: DriverOptionsRef) -> Result<Box<dyn DriverConnection>> {
         // Replace the scheme `sqlite` by `file` as expected by the SQLite driver.
         let mut sqlite_uri = uri.to_string();
@@ -21,8 +45,10 @@ impl DriverFactory for SqliteFactory {
 
         // Parse URI parameters to set the options and connection open flags.
         let mut flags = rusqlite::OpenFlags::SQLITE_OPEN_URI;
+        let mut statement_timeout = None;
+        let mut busy_timeout = None;
         let parsed_uri = url::Url::parse(&sqlite_uri)
-            .map_err(|e| Error::InvalidUri { uri: uri.to_string(), reason: e.to_string() })?;
+            .map_err(|e| Error::InvalidUri { uri: redact_uri(uri), reason: e.to_string() })?;
         parsed_uri.query_pairs().try_for_each(|(key, value)| {
             if key == "mode" {
                 // Despite using `SQLITE_OPEN_URI` the documentation is explicit about the flags that must include
@@ -39,11 +65,15 @@ impl DriverFactory for SqliteFactory {
                     }
                     _ => {
                         return Err(Error::InvalidUri {
-                            uri: uri.to_string(),
+                            uri: redact_uri(uri),
                             reason: "Invalid value for mode".to_string(),
                         })
                     }
                 }
+            } else if key == STATEMENT_TIMEOUT_PARAM {
+                statement_timeout = Some(parse_millis(uri, STATEMENT_TIMEOUT_PARAM, &value)?);
+            } else if key == BUSY_TIMEOUT_PARAM {
+                busy_timeout = Some(parse_millis(uri, BUSY_TIMEOUT_PARAM, &value)?);
             }
             Ok(())
         })?;
@@ -55,6 +85,81 @@ impl DriverFactory for SqliteFactory {
             flags |= rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE;
         }
 
-        Ok(Box::new(Sqlite { conn: rusqlite::Connection::open_with_flags(&sqlite_uri, flags)?, options }))
+        let busy_timeout = busy_timeout.unwrap_or(DEFAULT_BUSY_TIMEOUT);
+        let conn = open_with_retry(busy_timeout, || rusqlite::Connection::open_with_flags(&sqlite_uri, flags))?;
+        conn.busy_timeout(busy_timeout)?;
+        let statement_timeout = statement_timeout.map(|timeout| StatementTimeout::install(&conn, timeout));
+        Ok(Box::new(Sqlite { conn, options, statement_timeout, cache: RefCell::new(StatementCache::new()) }))
+    }
+}
+
+/// Call `open` until it doesn't fail because the database is busy or locked, or until `busy_timeout` expires.
+fn open_with_retry<T, F>(busy_timeout: Duration, mut open: F) -> rusqlite::Result<T>
+where
+    F: FnMut() -> rusqlite::Result<T>,
+{
+    let start = Instant::now();
+    let mut delay = OPEN_RETRY_DELAY;
+    loop {
+        match open() {
+            Err(rusqlite::Error::SqliteFailure(error, _))
+                if matches!(error.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+                    && start.elapsed() + delay <= busy_timeout =>
+            {
+                std::thread::sleep(delay);
+                delay = (delay * 2).min(MAX_OPEN_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn busy_error() -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY), None)
+    }
+
+    #[test]
+    fn test_open_with_retry() {
+        // The open is retried while the database is busy.
+        let mut attempts = 0;
+        let result = open_with_retry(Duration::from_secs(5), || {
+            attempts += 1;
+            match attempts {
+                1 | 2 => Err(busy_error()),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        // Until the busy timeout expires.
+        let mut attempts = 0;
+        let start = Instant::now();
+        let result: rusqlite::Result<()> = open_with_retry(Duration::from_millis(50), || {
+            attempts += 1;
+            Err(busy_error())
+        });
+        assert_eq!(result.unwrap_err().sqlite_error_code(), Some(ErrorCode::DatabaseBusy));
+        assert!(attempts > 1);
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // The other errors are not retried, and neither are busy errors with a busy timeout of 0.
+        let mut attempts = 0;
+        let result: rusqlite::Result<()> = open_with_retry(Duration::from_secs(5), || {
+            attempts += 1;
+            Err(rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN), None))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        let mut attempts = 0;
+        let result: rusqlite::Result<()> = open_with_retry(Duration::ZERO, || {
+            attempts += 1;
+            Err(busy_error())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
     }
 }
@@ -1,11 +1,18 @@
 use squill_core::{driver::DriverOptionsRef, factory::Factory};
 
+mod blob;
+mod cache;
 mod driver;
 mod errors;
 mod factory;
+mod options;
 mod statement;
+mod timeout;
 mod value;
 
+pub use blob::Blob;
+pub use options::{SqliteConnectOptions, SqliteOpenMode};
+
 /// The name of the driver for SQLite.
 pub const DRIVER_NAME: &str = "sqlite";
 
@@ -23,9 +30,20 @@ pub const IN_MEMORY_SPECIAL_FILENAME: &str = ":memory:";
 /// ```
 pub const IN_MEMORY_URI: &str = "sqlite::memory:";
 
-pub(crate) struct Sqlite {
+/// A connection to an SQLite database.
+///
+/// This type is not intended to be used directly but it gives access to the features specific to SQLite when
+/// downcasting a connection (see [squill_core::driver::DriverConnection]).
+///
+/// ```rust,ignore
+/// let mut conn = Factory::open(IN_MEMORY_URI)?;
+/// conn.downcast_mut::<Sqlite>().unwrap().register_regexp()?;
+/// ```
+pub struct Sqlite {
     conn: rusqlite::Connection,
     options: DriverOptionsRef,
+    statement_timeout: Option<timeout::StatementTimeout>,
+    cache: std::cell::RefCell<cache::StatementCache>,
 }
 
 pub fn register_driver() {
@@ -37,12 +55,23 @@ pub fn register_driver() {
 
 #[cfg(test)]
 mod sqlite_tests {
-    use crate::IN_MEMORY_URI;
-    use arrow_array::RecordBatch;
+    use crate::{Sqlite, SqliteConnectOptions, SqliteOpenMode, IN_MEMORY_URI};
+    use arrow_array::{Array, RecordBatch};
     use ctor::ctor;
     use squill_core::decode::{self, Decode};
+    use squill_core::driver::{CacheStats, ExecuteOutcome, AFFECTED_ROWS_COLUMN};
     use squill_core::factory::Factory;
-    use squill_core::{assert_execute_eq, assert_ok, assert_query_decode_eq, assert_some};
+    use squill_core::pagination::KeysetPager;
+    use squill_core::parameters::Parameters;
+    use squill_core::row::IntoMap;
+    use squill_core::values::Value;
+    use squill_core::Error;
+    use squill_core::{
+        assert_execute_eq, assert_ok, assert_query_decode_eq, assert_some, assert_some_ok, execute_named, params,
+        query_named,
+    };
+    use std::collections::HashMap;
+    use std::time::Duration;
 
     #[ctor]
     fn before_all() {
@@ -67,12 +96,730 @@ mod sqlite_tests {
         assert_ok!(Factory::open(&format!("sqlite://{}?mode=ro", Factory::to_uri_path(&file_path))));
     }
 
+    #[test]
+    fn test_read_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let uri = format!("sqlite://{}", Factory::to_uri_path(&temp_dir.path().join("read_only.db")));
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&format!("{}?mode=rwc", uri)));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER)", None));
+        assert_ok!(conn.close());
+
+        let mut conn = assert_ok!(squill_blocking::Connection::open(&format!("{}?mode=ro", uri)));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(0));
+        let error = conn.execute("INSERT INTO employee (id) VALUES (1)", None).unwrap_err();
+        assert!(matches!(error, Error::ReadOnly { .. }), "unexpected error: {:?}", error);
+    }
+
+    #[test]
+    fn test_connect_options() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("my data #1?.db");
+
+        // The database does not exist yet.
+        assert!(SqliteConnectOptions::new(&file_path).mode(SqliteOpenMode::ReadOnly).connect().is_err());
+        let mut conn =
+            assert_ok!(SqliteConnectOptions::new(&file_path).mode(SqliteOpenMode::ReadWriteCreate).connect());
+        assert_execute_eq!(conn, "CREATE TABLE employee (id INTEGER)", 0);
+        assert_ok!(conn.close());
+        assert!(file_path.exists());
+
+        let mut conn = assert_ok!(SqliteConnectOptions::new(&file_path)
+            .mode(SqliteOpenMode::ReadOnly)
+            .statement_timeout(Duration::from_secs(1))
+            .connect());
+        assert_execute_eq!(conn, "SELECT * FROM employee", 0);
+        assert!(conn.prepare("INSERT INTO employee (id) VALUES (1)").and_then(|mut stmt| stmt.execute(None)).is_err());
+
+        let options = SqliteConnectOptions::in_memory().param("cache", "shared").param("cache", "private");
+        assert_eq!(options.to_uri(), "sqlite::memory:?cache=private");
+        assert_ok!(options.connect());
+        assert_eq!(SqliteConnectOptions::new("data.db").to_uri(), "sqlite:data.db");
+    }
+
     #[test]
     fn test_ping() {
         let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
         assert_ok!(conn.ping());
     }
 
+    #[test]
+    fn test_savepoint() {
+        fn count(conn: &mut squill_blocking::Connection) -> i64 {
+            assert_some!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)))
+        }
+
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY)", None));
+
+        // The rollback of an inner savepoint keeps the changes of the outer ones.
+        let mut tx = assert_ok!(conn.begin());
+        assert_ok!(tx.execute("INSERT INTO employee (id) VALUES (1)", None));
+        let mut sp1 = assert_ok!(tx.savepoint());
+        assert_ok!(sp1.execute("INSERT INTO employee (id) VALUES (2)", None));
+        let mut sp2 = assert_ok!(sp1.savepoint());
+        assert_ne!(sp2.name(), "sp_1");
+        assert_ok!(sp2.execute("INSERT INTO employee (id) VALUES (3)", None));
+        assert_ok!(sp2.rollback_to());
+        assert_ok!(sp1.release());
+        {
+            // Dropping a savepoint that is not released rolls it back.
+            let mut sp3 = assert_ok!(tx.savepoint());
+            assert_ok!(sp3.execute("INSERT INTO employee (id) VALUES (4)", None));
+        }
+        assert_ok!(tx.commit());
+        assert_eq!(count(&mut conn), 2);
+
+        // The rollback of the transaction discards everything, including the released savepoints.
+        let mut tx = assert_ok!(conn.begin());
+        assert_ok!(tx.execute("INSERT INTO employee (id) VALUES (5)", None));
+        let mut sp = assert_ok!(tx.savepoint());
+        assert_ok!(sp.execute("INSERT INTO employee (id) VALUES (6)", None));
+        assert_ok!(sp.release());
+        assert_ok!(tx.rollback());
+        assert_eq!(count(&mut conn), 2);
+
+        // Dropping a transaction that is not committed rolls it back.
+        {
+            let mut tx = assert_ok!(conn.begin());
+            assert_ok!(tx.execute("INSERT INTO employee (id) VALUES (7)", None));
+        }
+        assert_eq!(count(&mut conn), 2);
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY, name TEXT)", None));
+        assert_ok!(conn.execute("CREATE INDEX employee_name ON employee (name)", None));
+        assert_ok!(conn.execute("INSERT INTO employee (id, name) VALUES (1, 'Alice'), (2, 'Bob')", None));
+        assert_ok!(conn.vacuum());
+        assert_ok!(conn.analyze(Some("main.employee")));
+        assert_ok!(conn.analyze(None));
+        assert_ok!(conn.optimize());
+        assert!(conn.analyze(Some("unknown_table")).is_err());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2));
+    }
+
+    #[test]
+    fn test_session_var() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let default_cache_size = assert_ok!(conn.query_scalar::<_, i64>("PRAGMA cache_size", None));
+        assert_ok!(conn.set_session_var("cache_size", "-4000"));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("PRAGMA cache_size", None)), Some(-4000));
+        assert_ok!(conn.set_session_var("main.cache_size", "-8000"));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("PRAGMA cache_size", None)), Some(-8000));
+
+        // The pragmas are restored to the value they had before being set.
+        assert_ok!(conn.reset_session());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("PRAGMA cache_size", None)), default_cache_size);
+
+        assert!(matches!(
+            conn.set_session_var("main.cache_size; DROP TABLE employee", "0"),
+            Err(Error::InputError { offset: 5, .. })
+        ));
+        assert!(matches!(conn.set_session_var("", "0"), Err(Error::InputError { .. })));
+        assert!(matches!(conn.set_session_var("main..cache_size", "0"), Err(Error::InputError { .. })));
+    }
+
+    #[test]
+    fn test_open_shared_memory() {
+        let mut conn1 = assert_ok!(squill_blocking::Connection::open_shared_memory("sqlite", "shared db"));
+        let mut conn2 = assert_ok!(squill_blocking::Connection::open_shared_memory("sqlite", "shared db"));
+        assert_ok!(conn1.execute("CREATE TABLE employee (id INTEGER)", None));
+        assert_ok!(conn1.execute("INSERT INTO employee (id) VALUES (1), (2)", None));
+        assert_eq!(assert_ok!(conn2.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2));
+
+        // Another name is another database.
+        let mut conn3 = assert_ok!(squill_blocking::Connection::open_shared_memory("sqlite", "other db"));
+        assert!(conn3.query_row("SELECT COUNT(*) FROM employee", None).is_err());
+
+        assert!(matches!(
+            squill_blocking::Connection::open_shared_memory("postgres", "shared db"),
+            Err(Error::InvalidUri { .. })
+        ));
+    }
+
+    #[test]
+    fn test_health_check() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.health_check(&[]));
+
+        // Some tables are missing.
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY)", None));
+        match conn.health_check(&["employee", "department", "salary"]) {
+            Err(Error::MissingTables { tables }) => assert_eq!(tables, vec!["department", "salary"]),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        // All the tables exist (the names are case-insensitive, views and temporary tables are included).
+        assert_ok!(conn.execute("CREATE VIEW department AS SELECT 1 AS id", None));
+        assert_ok!(conn.execute("CREATE TEMPORARY TABLE salary (id INTEGER)", None));
+        assert_ok!(conn.health_check(&["Employee", "department", "salary"]));
+    }
+
+    #[test]
+    fn test_fetch_all() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER, name TEXT)", None));
+
+        // The schema is returned even if there is no row.
+        let (schema, batches) = assert_ok!(conn.fetch_all("SELECT id, name FROM employee", None));
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(1).name(), "name");
+        assert!(batches.is_empty());
+
+        // The rows are split into batches of 1,000 rows by default.
+        assert_ok!(conn.execute(
+            "INSERT INTO employee (id, name) \
+             WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt LIMIT 2500) SELECT x, 'name' FROM cnt",
+            None
+        ));
+        let (schema, batches) = assert_ok!(conn.fetch_all("SELECT id, name FROM employee WHERE id > ?", params!(100)));
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(batches.iter().map(RecordBatch::num_rows).collect::<Vec<_>>(), vec![1000, 1000, 400]);
+    }
+
+    #[test]
+    fn test_max_batch_rows() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute(
+            "CREATE TABLE employee AS \
+             WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt LIMIT 2500) SELECT x AS id FROM cnt",
+            None
+        ));
+
+        // A single row is fetched by `query_row`.
+        conn.set_metrics_enabled(true);
+        let row = assert_some!(assert_ok!(conn.query_row("SELECT id FROM employee ORDER BY id", None)));
+        assert_eq!(row.get::<_, i64>(0), 1);
+        assert_eq!(assert_some!(conn.metrics()).rows_fetched, 1);
+
+        // The size of the batches can be overridden by statement, `query_row` restores it.
+        let mut stmt = assert_ok!(conn.prepare("SELECT id FROM employee"));
+        let batch_sizes = |stmt: &mut squill_blocking::Statement| {
+            assert_ok!(stmt.query(None)).map(|batch| assert_ok!(batch).num_rows()).collect::<Vec<_>>()
+        };
+        stmt.set_max_batch_rows(Some(700));
+        assert_eq!(batch_sizes(&mut stmt), vec![700, 700, 700, 400]);
+        assert_eq!(assert_ok!(stmt.query_scalar::<i64>(None)), Some(1));
+        assert_eq!(batch_sizes(&mut stmt), vec![700, 700, 700, 400]);
+        stmt.set_max_batch_rows(None);
+        assert_eq!(batch_sizes(&mut stmt), vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn test_parameter_count() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_eq!(assert_ok!(conn.prepare("SELECT 1")).parameter_count(), 0);
+        let stmt = assert_ok!(conn.prepare("SELECT ?, ?"));
+        assert_eq!(stmt.parameter_count(), 2);
+        assert_eq!(stmt.parameter_types(), None);
+    }
+
+    #[test]
+    fn test_query_dml() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER)", None));
+
+        // A statement that doesn't return rows returns the number of rows affected.
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO employee (id) VALUES (?), (?)"));
+        let batches = assert_ok!(assert_ok!(stmt.query(params!(1, 2))).collect::<Result<Vec<_>, _>>());
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema().field(0).name(), AFFECTED_ROWS_COLUMN);
+        assert_eq!(i64::decode(batches[0].column(0), 0), 2);
+        // The statement itself doesn't return rows.
+        assert!(stmt.schema().fields().is_empty());
+        drop(stmt);
+
+        let row = assert_some!(assert_ok!(conn.query_row("UPDATE employee SET id = id + 1 WHERE id > ?", params!(1))));
+        assert_eq!(row.get::<_, i64>(AFFECTED_ROWS_COLUMN), 1);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT SUM(id) FROM employee", None)), Some(4));
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER, name TEXT)", None));
+        assert_ok!(conn.execute("INSERT INTO employee (id, name) VALUES (1, 'Alice')", None));
+
+        let described =
+            assert_ok!(conn.describe("SELECT id, name, 1 + 1 AS two FROM employee WHERE id > ? AND name = ?"));
+        assert_eq!(described.param_count, 2);
+        assert_eq!(described.schema.fields().len(), 3);
+        assert_eq!(described.schema.field(0).data_type(), &arrow_schema::DataType::Int64);
+        assert_eq!(described.schema.field(1).data_type(), &arrow_schema::DataType::Utf8);
+        // The type of an expression is unknown until the rows are fetched.
+        assert_eq!(described.schema.field(2).name(), "two");
+        assert_eq!(described.schema.field(2).data_type(), &arrow_schema::DataType::Null);
+
+        // Describing a statement doesn't execute it.
+        let described = assert_ok!(conn.describe("DELETE FROM employee"));
+        assert_eq!(described.param_count, 0);
+        assert!(described.schema.fields().is_empty());
+        assert_eq!(assert_ok!(conn.query_row("SELECT COUNT(*) FROM employee", None)).unwrap().get::<_, i64>(0), 1);
+
+        assert!(conn.describe("SELECT * FROM unknown_table").is_err());
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_eq!(conn.cache_stats(), CacheStats::default());
+
+        // Repeated statements are found in the cache (the surrounding whitespaces are ignored).
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER, name TEXT)", None));
+        assert_ok!(conn.execute("INSERT INTO employee (id, name) VALUES (?, ?)", params!(1, "Alice")));
+        assert_ok!(conn.execute(" INSERT INTO employee (id, name) VALUES (?, ?)\n", params!(2, "Bob")));
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 1, misses: 2, size: 2 });
+
+        // Distinct statements are not.
+        assert_ok!(conn.query_row("SELECT name FROM employee WHERE id = 1", None));
+        assert_ok!(conn.query_row("SELECT name FROM employee WHERE id = 2", None));
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 1, misses: 4, size: 4 });
+
+        // A statement in use is not in the cache.
+        let stmt = assert_ok!(conn.prepare("SELECT name FROM employee WHERE id = 1"));
+        drop(stmt);
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 2, misses: 4, size: 4 });
+
+        conn.clear_prepared_cache();
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 2, misses: 4, size: 0 });
+        assert_ok!(conn.query_row("SELECT name FROM employee WHERE id = 1", None));
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 2, misses: 5, size: 1 });
+
+        // The least recently used statements are evicted.
+        conn.downcast_mut::<Sqlite>().unwrap().set_prepared_cache_capacity(2);
+        for id in 0..3 {
+            assert_ok!(conn.query_row(format!("SELECT name FROM employee WHERE id = {}", id), None));
+        }
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 3, misses: 7, size: 2 });
+        assert_ok!(conn.query_row("SELECT name FROM employee WHERE id = 0", None));
+        assert_eq!(conn.cache_stats(), CacheStats { hits: 3, misses: 8, size: 2 });
+    }
+
+    #[test]
+    fn test_query_json() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute(
+            "CREATE TABLE employee (id INTEGER, name TEXT, salary REAL, badge BLOB, active BOOLEAN, hired DATE)",
+            None
+        ));
+        assert_ok!(conn.execute(
+            "INSERT INTO employee VALUES (1, 'Alice', 1234.5, x'0102', TRUE, '2024-07-04'), \
+                                         (2, NULL, NULL, NULL, NULL, NULL)",
+            None
+        ));
+        // SQLite has no boolean nor date storage classes, they are stored as an integer and a text.
+        let mut stmt = assert_ok!(conn.prepare("SELECT * FROM employee ORDER BY id"));
+        assert_eq!(
+            assert_ok!(stmt.query_json(None)),
+            vec![
+                serde_json::json!({
+                    "id": 1, "name": "Alice", "salary": 1234.5, "badge": "AQI=", "active": 1, "hired": "2024-07-04"
+                }),
+                serde_json::json!({
+                    "id": 2, "name": null, "salary": null, "badge": null, "active": null, "hired": null
+                }),
+            ]
+        );
+        assert_eq!(assert_ok!(stmt.query_json(None)).len(), 2);
+    }
+
+    #[test]
+    fn test_bind_json() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE documents (id INTEGER, doc TEXT)", None));
+        let doc = serde_json::json!({ "name": "Alice", "tags": ["a", "b"] });
+        assert_ok!(conn.execute("INSERT INTO documents VALUES (?, ?)", params!(1, doc.clone())));
+        assert_ok!(conn.execute("INSERT INTO documents VALUES (?, ?)", params!(2, serde_json::Value::Null)));
+
+        // The document is stored as its serialized form, the JSON `null` as `NULL`.
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT doc FROM documents WHERE id = 1", None)),
+            Some(doc.to_string())
+        );
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT doc ->> '$.tags[1]' FROM documents WHERE id = 1", None)),
+            Some("b".to_string())
+        );
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>("SELECT doc FROM documents WHERE id = 2", None)), None);
+    }
+
+    #[test]
+    fn test_execute_in() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY, name TEXT)", None));
+        for id in 1..=5 {
+            assert_ok!(conn.execute("INSERT INTO employee (id, name) VALUES (?, ?)", params!(id, format!("e{}", id))));
+        }
+        assert_eq!(assert_ok!(conn.execute_in("DELETE FROM employee WHERE id IN (?)", params!(vec![1, 3, 5]))), 3);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2));
+        assert_eq!(
+            assert_ok!(conn.execute_in("DELETE FROM employee WHERE name = ? AND id IN (?)", params!("e2", vec![2, 4]))),
+            1
+        );
+        assert_eq!(assert_ok!(conn.execute_in("DELETE FROM employee WHERE id IN (?)", params!(Vec::<i64>::new()))), 0);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT id FROM employee", None)), Some(4));
+    }
+
+    #[test]
+    fn test_execute_many() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY, name TEXT)", None));
+        let parameters = |ids: std::ops::RangeInclusive<i64>| {
+            ids.map(|id| Parameters::Positional(vec![id.into(), format!("e{}", id).into()]))
+        };
+
+        // Inserting using a loop
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO employee (id, name) VALUES (?, ?)"));
+        let mut affected_rows = 0;
+        for parameters in parameters(1..=1000) {
+            affected_rows += assert_ok!(stmt.execute(Some(parameters)));
+        }
+        assert_eq!(affected_rows, 1000);
+
+        // Inserting using execute_many
+        assert_eq!(assert_ok!(stmt.execute_many(parameters(1001..=2000))), 1000);
+        drop(stmt);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2000));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT name FROM employee WHERE id = 2000", None)),
+            Some("e2000".to_string())
+        );
+
+        // A failure rolls back all the executions.
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO employee (id, name) VALUES (?, ?)"));
+        assert!(stmt.execute_many(parameters(1999..=2010).rev()).is_err());
+        drop(stmt);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2000));
+
+        // Within a transaction, the executions are committed with the transaction.
+        let mut tx = assert_ok!(conn.begin());
+        let mut stmt = assert_ok!(tx.prepare("INSERT INTO employee (id, name) VALUES (?, ?)"));
+        assert_eq!(assert_ok!(stmt.execute_many(parameters(2001..=2010))), 10);
+        drop(stmt);
+        assert_ok!(tx.rollback());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT COUNT(*) FROM employee", None)), Some(2000));
+    }
+
+    #[test]
+    fn test_named_parameters() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER PRIMARY KEY, name TEXT)", None));
+        assert_eq!(
+            assert_ok!(execute_named!(
+                conn,
+                "INSERT INTO employee (id, name) VALUES (:id, :name)",
+                "name" => "Alice",
+                "id" => 1
+            )),
+            1
+        );
+        // The prefix of the placeholder can be given.
+        assert_eq!(
+            assert_ok!(
+                execute_named!(conn, "INSERT INTO employee (id, name) VALUES (@id, :name)", "@id" => 2, ":name" => "Bob")
+            ),
+            1
+        );
+
+        let mut stmt = assert_ok!(conn.prepare("SELECT name FROM employee WHERE id = :id OR name = :name ORDER BY id"));
+        let mut rows = assert_ok!(query_named!(stmt, "id" => 1, "name" => "Bob"));
+        let batch = assert_some_ok!(rows.next());
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(String::decode(batch.column(0), 0), "Alice");
+        assert_eq!(String::decode(batch.column(0), 1), "Bob");
+        drop(rows);
+
+        // All the parameters must be bound with known names.
+        assert!(query_named!(stmt, "id" => 1).is_err());
+        assert!(query_named!(stmt, "id" => 1, "unknown" => "Bob").is_err());
+    }
+
+    #[test]
+    fn test_query_scalar() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT 42", None)), Some(42));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT NULL", None)), None);
+        assert_eq!(assert_ok!(conn.query_scalar::<_, String>("SELECT 'a' WHERE 1 = 0", None)), None);
+        let mut stmt = assert_ok!(conn.prepare("SELECT ? + 1"));
+        assert_eq!(assert_ok!(stmt.query_scalar::<i64>(params!(41))), Some(42));
+    }
+
+    #[test]
+    fn test_into_map() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE settings (key TEXT, value INTEGER)", None));
+        assert_ok!(conn.execute("INSERT INTO settings VALUES ('timeout', 30), ('retries', 3), ('timeout', 60)", None));
+        let mut stmt = assert_ok!(conn.prepare("SELECT key, value FROM settings ORDER BY rowid"));
+        let settings: HashMap<String, i64> = assert_ok!(assert_ok!(stmt.query_rows(None)).into_map());
+        assert_eq!(settings, HashMap::from([("timeout".to_string(), 60), ("retries".to_string(), 3)]));
+        drop(stmt);
+        let mut stmt = assert_ok!(conn.prepare("SELECT value, key FROM settings"));
+        assert!(assert_ok!(stmt.query_rows(None)).into_map::<String, i64>().is_err());
+    }
+
+    #[test]
+    fn test_open_blob() {
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE files (id INTEGER PRIMARY KEY, content BLOB)", None));
+        let content: Vec<u8> = (0..3 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        assert_ok!(conn.execute("INSERT INTO files (id, content) VALUES (1, ?)", params!(content.clone())));
+        assert_ok!(conn.execute("INSERT INTO files (id, content) VALUES (2, zeroblob(16))", None));
+
+        // Read the blob in chunks.
+        let sqlite = assert_some!(conn.downcast_mut::<crate::Sqlite>());
+        let mut blob = assert_ok!(sqlite.open_blob("main", "files", "content", 1, true));
+        assert_eq!(blob.len(), content.len());
+        let (mut read, mut chunk, mut chunks) = (Vec::new(), vec![0u8; 64 * 1024], 0);
+        loop {
+            let n = assert_ok!(blob.read(&mut chunk));
+            if n == 0 {
+                break;
+            }
+            read.extend_from_slice(&chunk[..n]);
+            chunks += 1;
+        }
+        assert_eq!(chunks, 48);
+        assert!(read == content);
+
+        // Seek into the blob.
+        assert_eq!(assert_ok!(blob.seek(SeekFrom::Start(1000))), 1000);
+        let mut buf = [0u8; 4];
+        assert_ok!(blob.read_exact(&mut buf));
+        assert_eq!(buf, content[1000..1004]);
+
+        // A read-only blob cannot be written.
+        assert!(blob.write_all(&[1, 2, 3]).is_err());
+        drop(blob);
+
+        // Write into a blob.
+        let mut blob = assert_ok!(sqlite.open_blob("main", "files", "content", 2, false));
+        assert_ok!(blob.seek(SeekFrom::Start(8)));
+        assert_ok!(blob.write_all(&[1, 2, 3, 4]));
+        drop(blob);
+
+        // The row or the column doesn't exist.
+        assert!(sqlite.open_blob("main", "files", "content", 3, true).is_err());
+        assert!(sqlite.open_blob("main", "files", "missing", 1, true).is_err());
+
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, Vec<u8>>("SELECT content FROM files WHERE id = 2", None)),
+            Some(vec![0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_query_with_schema() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER, name TEXT)", None));
+        assert_ok!(conn.execute("INSERT INTO employee (id, name) VALUES (1, 'Alice'), (2, 'Bob')", None));
+
+        let mut stmt = assert_ok!(conn.prepare("SELECT id, name FROM employee WHERE id > ?"));
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(params!(0)));
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(schema.field(0).data_type(), &arrow_schema::DataType::Int64);
+        assert_eq!(schema.field(1).name(), "name");
+        assert_eq!(schema.field(1).data_type(), &arrow_schema::DataType::Utf8);
+        let batches = assert_ok!(batches.collect::<Result<Vec<_>, _>>());
+        assert_eq!(batches.iter().map(|batch| batch.num_rows()).sum::<usize>(), 2);
+
+        // No rows returned.
+        let (schema, mut batches) = assert_ok!(stmt.query_with_schema(params!(10)));
+        assert_eq!(schema.fields().len(), 2);
+        assert_eq!(schema.field(1).name(), "name");
+        assert!(batches.all(|batch| batch.is_ok_and(|batch| batch.num_rows() == 0)));
+        drop(batches);
+        drop(stmt);
+
+        // The type of an expression is inferred from its values.
+        let mut stmt = assert_ok!(conn.prepare("SELECT id * 2.5 AS score FROM employee"));
+        assert_eq!(stmt.schema().field(0).data_type(), &arrow_schema::DataType::Null);
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(None));
+        assert_eq!(schema.field(0).data_type(), &arrow_schema::DataType::Float64);
+        let batches = assert_ok!(batches.collect::<Result<Vec<_>, _>>());
+        assert_eq!(batches[0].schema(), schema);
+        drop(stmt);
+
+        // An expression mixing integers and reals is promoted to Float64.
+        let mut stmt =
+            assert_ok!(conn.prepare("SELECT CASE WHEN id = 1 THEN 1 ELSE 1.5 END AS ratio FROM employee ORDER BY id"));
+        let (schema, batches) = assert_ok!(stmt.query_with_schema(None));
+        assert_eq!(schema.field(0).data_type(), &arrow_schema::DataType::Float64);
+        let batches = assert_ok!(batches.collect::<Result<Vec<_>, _>>());
+        assert_eq!(batches[0].column(0).as_ref(), &arrow_array::Float64Array::from(vec![1.0, 1.5]) as &dyn Array);
+    }
+
+    #[test]
+    fn test_query_projected() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER, name TEXT, email TEXT, salary REAL)", None));
+        assert_ok!(conn.execute(
+            "INSERT INTO employee VALUES (1, 'Alice', 'alice@example.com', 100.0), (2, 'Bob', 'bob@example.com', 90.0)",
+            None
+        ));
+
+        let mut stmt = assert_ok!(conn.prepare("SELECT * FROM employee ORDER BY id"));
+        let batches = assert_ok!(stmt.query_projected(None, &["name", "id"]));
+        let batches = assert_ok!(batches.collect::<Result<Vec<_>, _>>());
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_columns(), 2);
+        assert_eq!(batch.schema().field(0).name(), "name");
+        assert_eq!(batch.schema().field(1).name(), "id");
+        assert_eq!(String::decode(batch.column(0), 1), "Bob");
+        assert_eq!(i64::decode(batch.column(1), 1), 2);
+
+        assert!(matches!(stmt.query_projected(None, &["missing"]), Err(squill_core::Error::NotFound)));
+    }
+
+    #[test]
+    fn test_server_version() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let version = assert_ok!(conn.server_version());
+        assert!(!version.is_empty());
+        assert!(version.starts_with("3."));
+        // The version is cached.
+        assert_eq!(assert_ok!(conn.server_version()), version);
+    }
+
+    #[test]
+    fn test_create_scalar_function() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let sqlite = assert_some!(conn.downcast_mut::<crate::Sqlite>());
+        assert_ok!(sqlite.create_scalar_function("add_one", 1, |args| match &args[0] {
+            Value::Int64(value) => Ok(Value::Int64(value + 1)),
+            Value::Null => Ok(Value::Null),
+            value => Err(format!("Unexpected argument: {:?}", value).into()),
+        }));
+        assert_ok!(sqlite.create_scalar_function("join_values", -1, |args| {
+            Ok(Value::String(args.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",")))
+        }));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT add_one(41)", None)), Some(42));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT add_one(NULL)", None)), None);
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>("SELECT join_values(1, 'a', 2.5)", None)),
+            Some("1,a,2.5".into())
+        );
+        // The error of the function is reported.
+        assert!(conn.query_scalar::<_, i64>("SELECT add_one('a')", None).is_err());
+
+        // REGEXP
+        assert!(conn.query_scalar::<_, bool>("SELECT 'abc' REGEXP '^a'", None).is_err());
+        assert_ok!(assert_some!(conn.downcast_mut::<crate::Sqlite>()).register_regexp());
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT 'abc' REGEXP '^a.c$'", None)), Some(1));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT 'abc' REGEXP '^b'", None)), Some(0));
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT NULL REGEXP '^b'", None)), None);
+        assert!(conn.query_scalar::<_, i64>("SELECT 'abc' REGEXP '('", None).is_err());
+    }
+
+    #[test]
+    fn test_quote() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let table = conn.quote_identifier(r#"my "table""#);
+        assert_eq!(table, r#""my ""table""""#);
+        assert_ok!(conn.execute(format!("CREATE TABLE {} (value TEXT)", table), None));
+        let literal = conn.quote_literal("it's");
+        assert_ok!(conn.execute(format!("INSERT INTO {} (value) VALUES ({})", table, literal), None));
+        assert_eq!(
+            assert_ok!(conn.query_scalar::<_, String>(format!("SELECT value FROM {}", table), None)),
+            Some("it's".to_string())
+        );
+    }
+
+    #[test]
+    fn test_statement_timeout() {
+        let slow_query = "WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt) SELECT COUNT(*) FROM cnt";
+        let mut conn = assert_ok!(squill_blocking::Connection::open("sqlite::memory:?statement_timeout=50"));
+        assert!(matches!(conn.query_row(slow_query, None), Err(squill_core::Error::Timeout)));
+        // The timeout applies to each statement, the connection remains usable.
+        assert_ok!(conn.query_row("SELECT 1", None));
+        assert_ok!(conn.ping());
+
+        // The deadline of the last statement is cleared once it is completed, it doesn't interrupt the next calls that
+        // don't start a countdown.
+        let mut conn = assert_ok!(squill_blocking::Connection::open("sqlite::memory:?statement_timeout=200"));
+        assert_ok!(conn.execute("CREATE TABLE files (id INTEGER PRIMARY KEY, content BLOB)", None));
+        // Enough rows for the integrity check to run the progress handler.
+        assert_ok!(conn.execute(
+            "WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt WHERE x < 10000)
+             INSERT INTO files (id, content) SELECT x, zeroblob(16) FROM cnt",
+            None
+        ));
+        let mut stmt = assert_ok!(conn.prepare("SELECT id FROM files"));
+        // The rows are dropped before being exhausted.
+        assert_some_ok!(assert_ok!(stmt.query_rows(None)).next());
+        drop(stmt);
+        std::thread::sleep(Duration::from_millis(250));
+        let sqlite = assert_some!(conn.downcast_mut::<Sqlite>());
+        assert_eq!(assert_ok!(sqlite.get_pragma("integrity_check")), Some(Value::String("ok".to_string())));
+        assert_eq!(assert_ok!(sqlite.open_blob("main", "files", "content", 1, true)).len(), 16);
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO files (content) VALUES (?)"));
+        assert_eq!(assert_ok!(stmt.execute_many(vec![params!(vec![1_u8]).unwrap(), params!(vec![2_u8]).unwrap()])), 2);
+
+        assert!(matches!(
+            Factory::open("sqlite::memory:?statement_timeout=abc"),
+            Err(squill_core::Error::InvalidUri { .. })
+        ));
+    }
+
+    #[test]
+    fn test_busy_timeout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = SqliteConnectOptions::new(temp_dir.path().join("busy.db")).mode(SqliteOpenMode::ReadWriteCreate);
+        let mut conn1 = assert_ok!(squill_blocking::Connection::open(&options.to_uri()));
+        assert_eq!(assert_ok!(conn1.query_scalar::<_, i64>("PRAGMA busy_timeout", None)), Some(5000));
+        assert_ok!(conn1.execute("CREATE TABLE employee (id INTEGER)", None));
+
+        let uri = options.busy_timeout(Duration::from_millis(100)).to_uri();
+        let mut conn2 = assert_ok!(squill_blocking::Connection::open(&uri));
+        assert_eq!(assert_ok!(conn2.query_scalar::<_, i64>("PRAGMA busy_timeout", None)), Some(100));
+
+        // The database stays locked by the first connection longer than the busy timeout of the second one.
+        assert_ok!(conn1.execute("BEGIN EXCLUSIVE", None));
+        let start = std::time::Instant::now();
+        let error = conn2.execute("INSERT INTO employee (id) VALUES (1)", None).unwrap_err();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(matches!(error, Error::Busy { .. }), "unexpected error: {:?}", error);
+        assert!(error.is_retryable());
+        assert_ok!(conn1.execute("COMMIT", None));
+        assert_ok!(conn2.execute("INSERT INTO employee (id) VALUES (1)", None));
+
+        assert!(matches!(
+            Factory::open("sqlite::memory:?busy_timeout=abc"),
+            Err(squill_core::Error::InvalidUri { .. })
+        ));
+    }
+
+    #[test]
+    fn test_interrupt_handle() {
+        let endless_query =
+            "WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt) SELECT COUNT(*) FROM cnt";
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let handle = conn.interrupt_handle();
+        assert!(handle.is_supported());
+        let interrupter = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            handle.interrupt();
+        });
+        assert!(matches!(conn.query_row(endless_query, None), Err(Error::Cancelled)));
+        interrupter.join().unwrap();
+        // The connection remains usable.
+        assert_eq!(assert_ok!(conn.query_scalar::<_, i64>("SELECT 1", None)), Some(1));
+
+        // An interruption is not reported as a timeout.
+        let mut conn = assert_ok!(squill_blocking::Connection::open("sqlite::memory:?statement_timeout=60000"));
+        let handle = conn.interrupt_handle();
+        let interrupter = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            handle.interrupt();
+        });
+        assert!(matches!(conn.query_row(endless_query, None), Err(Error::Cancelled)));
+        interrupter.join().unwrap();
+    }
+
     #[test]
     fn test_basics() {
         let conn = assert_ok!(Factory::open(IN_MEMORY_URI));
@@ -180,8 +927,7 @@ mod sqlite_tests {
 
     #[test]
     fn test_bind() {
-        // TODO: test blob
-        // let blob: Vec<u8> = vec![0x00, 0x01, 0x42];
+        let blob: Vec<u8> = vec![0x00, 0x01, 0x42];
         let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
         assert_execute_eq!(conn, "CREATE TABLE test_integer (value INTEGER)", 0);
         assert_execute_eq!(conn, "CREATE TABLE test_text (value VARCHAR)", 0);
@@ -190,10 +936,259 @@ mod sqlite_tests {
         assert_execute_eq!(conn, "INSERT INTO test_integer (value) VALUES (?)", &[&42i64], 1);
         assert_execute_eq!(conn, "INSERT INTO test_text (value) VALUES (?)", &[&"hello"], 1);
         assert_execute_eq!(conn, "INSERT INTO test_real (value) VALUES (?)", &[&42.2f64], 1);
-        //        assert_execute_eq!(conn, "INSERT INTO test_blob (value) VALUES (?)", &[&blob], 1);
+        assert_execute_eq!(conn, "INSERT INTO test_blob (value) VALUES (?)", &[&blob], 1);
         assert_query_decode_eq!(conn, "SELECT value FROM test_integer", i64, 42);
         assert_query_decode_eq!(conn, "SELECT value FROM test_text", String, "hello");
         assert_query_decode_eq!(conn, "SELECT value FROM test_real", f64, 42.2);
-        //        assert_query_decode_eq!(conn, "SELECT value FROM test_real", Vec<u8>, blob);
+        assert_query_decode_eq!(conn, "SELECT value FROM test_blob", Vec<u8>, blob);
+    }
+
+    #[test]
+    fn test_bind_unsupported_data_type() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert!(matches!(
+            conn.execute("SELECT ?", Some(Parameters::Positional(vec![Value::UInt64(42)]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "UInt64"
+        ));
+        assert!(matches!(
+            conn.execute("SELECT ?", Some(Parameters::Positional(vec![Value::Interval { months: 1, days: 0, nanos: 0 }]))),
+            Err(squill_core::Error::UnsupportedDataType { data_type }) if data_type == "Interval"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_async_transaction() {
+        let mut conn =
+            assert_ok!(squill_async::Connection::open("sqlite:memdb_async_tx?mode=memory&cache=shared").await);
+        assert_ok!(conn.execute("CREATE TABLE test_tx (id INTEGER PRIMARY KEY)", None).await);
+
+        // Committed
+        let mut tx = assert_ok!(conn.begin().await);
+        assert_ok!(tx.execute("INSERT INTO test_tx (id) VALUES (?)", params!(1)).await);
+        let mut stmt = assert_ok!(tx.prepare("INSERT INTO test_tx (id) VALUES (?)").await);
+        assert_ok!(stmt.execute(params!(2)).await);
+        drop(stmt);
+        assert_eq!(
+            assert_some!(assert_ok!(tx.query_row("SELECT COUNT(*) FROM test_tx", None).await)).get::<_, i64>(0),
+            2
+        );
+        assert_ok!(tx.commit().await);
+
+        // Rolled back
+        let mut tx = assert_ok!(conn.begin().await);
+        assert_ok!(tx.execute("INSERT INTO test_tx (id) VALUES (3)", None).await);
+        assert_ok!(tx.rollback().await);
+
+        // Rolled back on drop
+        let mut tx = assert_ok!(conn.begin().await);
+        assert_ok!(tx.execute("INSERT INTO test_tx (id) VALUES (4)", None).await);
+        drop(tx);
+
+        let row = assert_some!(assert_ok!(conn.query_row("SELECT COUNT(*), MAX(id) FROM test_tx", None).await));
+        assert_eq!((row.get::<_, i64>(0), row.get::<_, i64>(1)), (2, 2));
+
+        // The connection is no longer in a transaction.
+        assert!(conn.execute("COMMIT", None).await.is_err());
+    }
+
+    #[test]
+    fn test_keyset_pager() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE test_pager (id INTEGER PRIMARY KEY, name TEXT)", None));
+        for id in 1..=25 {
+            assert_ok!(
+                conn.execute("INSERT INTO test_pager (id, name) VALUES (?, ?)", params!(id, format!("name{id}")))
+            );
+        }
+
+        let mut pager = KeysetPager::new("SELECT id, name FROM test_pager", "id", 10);
+        let mut pages: Vec<Vec<i64>> = Vec::new();
+        while let Some(rows) =
+            assert_ok!(pager.next_page(|statement, parameters| conn.query_map_rows(statement, parameters, Ok)))
+        {
+            pages.push(rows.iter().map(|row| row.get::<_, i64>("id")).collect());
+        }
+        assert_eq!(pages.iter().map(|page| page.len()).collect::<Vec<_>>(), vec![10, 10, 5]);
+        assert_eq!(pages.concat(), (1..=25).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn test_insert_returning_id() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE test_returning (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)", None));
+        assert_eq!(
+            assert_ok!(conn.insert_returning_id("INSERT INTO test_returning (name) VALUES (?)", params!("a"))),
+            1
+        );
+        assert_eq!(
+            assert_ok!(conn.insert_returning_id("INSERT INTO test_returning (name) VALUES (?)", params!("b"))),
+            2
+        );
+        assert!(conn.insert_returning_id("INSERT INTO unknown_table (name) VALUES (?)", params!("c")).is_err());
+    }
+
+    #[test]
+    fn test_execute_detailed() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE test_detailed (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)", None));
+        let mut stmt = assert_ok!(conn.prepare("INSERT INTO test_detailed (name) VALUES (?)"));
+        let outcome = assert_ok!(stmt.execute_detailed(params!("a")));
+        assert_eq!(outcome, ExecuteOutcome { rows_affected: 1, last_insert_id: Some(1), warnings: vec![] });
+        assert_eq!(assert_ok!(stmt.execute_detailed(params!("b"))).last_insert_id, Some(2));
+        drop(stmt);
+
+        // No row inserted.
+        let mut stmt = assert_ok!(conn.prepare("UPDATE test_detailed SET name = 'c' WHERE id = ?"));
+        assert_eq!(
+            assert_ok!(stmt.execute_detailed(params!(1))),
+            ExecuteOutcome { rows_affected: 1, last_insert_id: None, warnings: vec![] }
+        );
+        drop(stmt);
+
+        // The rowid is reported even if it is the same as the one of the previous insert into another table.
+        assert_ok!(conn.execute("CREATE TABLE test_detailed_other (id INTEGER PRIMARY KEY, name TEXT)", None));
+        let mut stmt = assert_ok!(conn.prepare(
+            "WITH names(name) AS (SELECT 'x' UNION ALL SELECT 'y') \
+             INSERT INTO test_detailed_other (id, name) SELECT 2, name FROM names LIMIT 1"
+        ));
+        assert_eq!(assert_ok!(stmt.execute_detailed(None)).last_insert_id, Some(2));
+        drop(stmt);
+        let mut stmt = assert_ok!(conn.prepare("UPDATE test_detailed SET name = replace(name, 'c', 'd')"));
+        assert_eq!(assert_ok!(stmt.execute_detailed(None)).last_insert_id, None);
+        drop(stmt);
+
+        // An insert that doesn't insert any row doesn't report the rowid of the previous insert.
+        let mut stmt =
+            assert_ok!(conn.prepare("INSERT INTO test_detailed (id, name) VALUES (1, 'e') ON CONFLICT DO NOTHING"));
+        assert_eq!(
+            assert_ok!(stmt.execute_detailed(None)),
+            ExecuteOutcome { rows_affected: 0, last_insert_id: None, warnings: vec![] }
+        );
+        drop(stmt);
+
+        // The keywords in the comments and the literals are ignored.
+        let mut stmt = assert_ok!(
+            conn.prepare("/* INSERT */ UPDATE test_detailed SET name = 'INSERT' || name -- INSERT\n WHERE id = 1")
+        );
+        assert_eq!(assert_ok!(stmt.execute_detailed(None)).last_insert_id, None);
+        drop(stmt);
+        let mut stmt = assert_ok!(conn.prepare("-- UPDATE\nINSERT INTO test_detailed (name) VALUES ('UPDATE')"));
+        assert_eq!(assert_ok!(stmt.execute_detailed(None)).last_insert_id, Some(3));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare(
+            "WITH RECURSIVE t(value) AS (SELECT 1 UNION ALL SELECT value + 1 FROM t WHERE value < 10) \
+             SELECT value FROM t WHERE value > ?"
+        ));
+        let mut rows = assert_ok!(stmt.query_rows(params!(5)));
+        assert_eq!(assert_some!(rows.next()).unwrap().get::<_, i64>(0), 6);
+        drop(rows);
+
+        // The parameters are cleared by the reset (binding NULL).
+        assert_ok!(stmt.reset());
+        assert_eq!(assert_ok!(stmt.query_rows(None)).count(), 0);
+        let values: Vec<i64> = assert_ok!(stmt.query_rows(params!(8))).map(|row| row.unwrap().get(0)).collect();
+        assert_eq!(values, vec![9, 10]);
+    }
+
+    #[test]
+    fn test_drop_partially_consumed_rows() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        assert_ok!(conn.execute("CREATE TABLE employee (id INTEGER)", None));
+        assert_ok!(conn.execute(
+            "WITH RECURSIVE t(id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM t WHERE id < 10) \
+             INSERT INTO employee SELECT id FROM t",
+            None
+        ));
+        let mut stmt = assert_ok!(conn.prepare("SELECT id FROM employee ORDER BY id"));
+        stmt.set_max_batch_rows(Some(2));
+        let mut rows = assert_ok!(stmt.query_rows(None));
+        assert_eq!(assert_some!(rows.next()).unwrap().get::<_, i64>(0), 1);
+        drop(rows);
+
+        // The statement starts again from the first row.
+        let ids: Vec<i64> = assert_ok!(stmt.query_rows(None)).map(|row| row.unwrap().get(0)).collect();
+        assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+        let mut rows = assert_ok!(stmt.query_rows(None));
+        assert_eq!(assert_some!(rows.next()).unwrap().get::<_, i64>(0), 1);
+        drop(rows);
+        drop(stmt);
+
+        // The statement is kept in the cache of the connection, a table it was still reading could not be dropped.
+        assert_ok!(conn.execute("DROP TABLE employee", None));
+    }
+
+    #[test]
+    fn test_unify_batches() {
+        let mut conn = assert_ok!(squill_blocking::Connection::open(IN_MEMORY_URI));
+        let mut stmt = assert_ok!(conn.prepare(
+            "WITH RECURSIVE t(value) AS (SELECT 1 UNION ALL SELECT value + 1 FROM t WHERE value < 5) \
+             SELECT CASE WHEN value > 2 THEN value END AS value FROM t"
+        ));
+        stmt.set_max_batch_rows(Some(2));
+        let batches = assert_ok!(assert_ok!(stmt.query(None)).collect::<Result<Vec<_>, _>>());
+
+        // The type of the expression is only known once a value is found, after the first record batch.
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].schema().field(0).data_type(), &arrow_schema::DataType::Null);
+        assert_eq!(batches[1].schema().field(0).data_type(), &arrow_schema::DataType::Int64);
+
+        let batches = assert_ok!(squill_core::arrow::unify_batches(&batches));
+        assert!(batches.iter().all(|batch| batch.schema().field(0).data_type() == &arrow_schema::DataType::Int64));
+        let values: Vec<Option<i64>> = batches
+            .iter()
+            .flat_map(|batch| {
+                (0..batch.num_rows()).map(|index| {
+                    (!decode::is_null(batch.column(0), index)).then(|| i64::decode(batch.column(0), index))
+                })
+            })
+            .collect();
+        assert_eq!(values, vec![None, None, Some(3), Some(4), Some(5)]);
+    }
+
+    #[test]
+    fn test_bind_uuid() {
+        let uuid = uuid::Uuid::parse_str("0e089c07-8654-4aab-9c25-4f3c44590251").unwrap();
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        assert_execute_eq!(conn, "CREATE TABLE test_uuid (value TEXT)", 0);
+        assert_execute_eq!(conn, "INSERT INTO test_uuid (value) VALUES (?)", &[&uuid], 1);
+        assert_execute_eq!(conn, "INSERT INTO test_uuid (value) VALUES (?)", &[&uuid::Uuid::nil()], 1);
+        assert_query_decode_eq!(conn, "SELECT value FROM test_uuid WHERE value IS NOT NULL", uuid::Uuid, uuid);
+        assert_query_decode_eq!(conn, "SELECT COUNT(*) FROM test_uuid WHERE value IS NULL", i64, 1);
+    }
+
+    #[test]
+    fn test_pragma() {
+        let mut conn = assert_ok!(Factory::open(IN_MEMORY_URI));
+        let sqlite = assert_some!(conn.downcast_mut::<Sqlite>());
+        assert_ok!(sqlite.set_pragma("cache_size", -4000));
+        assert_eq!(assert_ok!(sqlite.get_pragma("cache_size")), Some(Value::Int64(-4000)));
+        assert_ok!(sqlite.set_pragma("application_id", 42));
+        assert_eq!(assert_ok!(sqlite.get_pragma("application_id")), Some(Value::Int64(42)));
+
+        // Unknown pragmas are ignored by SQLite.
+        assert_eq!(assert_ok!(sqlite.get_pragma("unknown_pragma")), None);
+
+        // The names of the pragmas cannot be quoted so they must be plain identifiers.
+        assert!(sqlite.set_pragma("cache_size = 0; DROP TABLE t", 0).is_err());
+        assert!(sqlite.get_pragma("cache_size;").is_err());
+
+        // The statement timeout of the connection doesn't interrupt the pragmas once the last statement is completed.
+        let mut conn = assert_ok!(Factory::open("sqlite::memory:?statement_timeout=50"));
+        assert_execute_eq!(conn, "CREATE TABLE employee (id INTEGER, name TEXT)", 0);
+        assert_execute_eq!(
+            conn,
+            "WITH RECURSIVE cnt(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM cnt WHERE x < 10000)
+             INSERT INTO employee SELECT x, 'name' || x FROM cnt",
+            10000
+        );
+        std::thread::sleep(Duration::from_millis(100));
+        let sqlite = assert_some!(conn.downcast_mut::<Sqlite>());
+        assert_ok!(sqlite.set_pragma("cache_size", -2000));
+        assert_eq!(assert_ok!(sqlite.get_pragma("cache_size")), Some(Value::Int64(-2000)));
+        assert_eq!(assert_ok!(sqlite.get_pragma("quick_check")), Some(Value::String("ok".to_string())));
     }
 }
@@ -0,0 +1,104 @@
+use crate::factory::BUSY_TIMEOUT_PARAM;
+use crate::{register_driver, DRIVER_NAME, IN_MEMORY_SPECIAL_FILENAME};
+use squill_core::driver::DriverConnection;
+use squill_core::factory::Factory;
+use squill_core::uri::{encode_path, encode_query, STATEMENT_TIMEOUT_PARAM};
+use squill_core::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The mode used to open an SQLite database (the `mode` parameter of the URI).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteOpenMode {
+    /// The database is opened for reading only, it must exist.
+    ReadOnly,
+
+    /// The database is opened for reading and writing, it must exist.
+    ReadWrite,
+
+    /// The database is opened for reading and writing and is created if it does not exist.
+    ReadWriteCreate,
+
+    /// The database is a pure in-memory database that never interacts with the disk.
+    Memory,
+}
+
+impl SqliteOpenMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SqliteOpenMode::ReadOnly => "ro",
+            SqliteOpenMode::ReadWrite => "rw",
+            SqliteOpenMode::ReadWriteCreate => "rwc",
+            SqliteOpenMode::Memory => "memory",
+        }
+    }
+}
+
+/// A builder for the URI of an SQLite database.
+///
+/// The path and the parameters are encoded as expected in an URI, so any file name can be used.
+///
+/// ```rust
+/// use squill_sqlite::{SqliteConnectOptions, SqliteOpenMode};
+///
+/// let options = SqliteConnectOptions::new("/tmp/my data.db").mode(SqliteOpenMode::ReadOnly);
+/// assert_eq!(options.to_uri(), "sqlite:///tmp/my%20data.db?mode=ro");
+/// assert_eq!(SqliteConnectOptions::in_memory().to_uri(), "sqlite::memory:");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteConnectOptions {
+    path: String,
+    params: Vec<(String, String)>,
+}
+
+impl SqliteConnectOptions {
+    /// Create the options to open the database file at the given path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: Factory::to_uri_path(&path.into()), params: Vec::new() }
+    }
+
+    /// Create the options to open a private in-memory database.
+    pub fn in_memory() -> Self {
+        Self { path: IN_MEMORY_SPECIAL_FILENAME.to_string(), params: Vec::new() }
+    }
+
+    /// Set the mode used to open the database.
+    pub fn mode(self, mode: SqliteOpenMode) -> Self {
+        self.param("mode", mode.as_str())
+    }
+
+    /// Set the time after which a running statement is interrupted.
+    pub fn statement_timeout(self, timeout: Duration) -> Self {
+        self.param(STATEMENT_TIMEOUT_PARAM, timeout.as_millis().to_string())
+    }
+
+    /// Set the time during which an access to a database locked by another connection is retried (5s by default).
+    pub fn busy_timeout(self, timeout: Duration) -> Self {
+        self.param(BUSY_TIMEOUT_PARAM, timeout.as_millis().to_string())
+    }
+
+    /// Set a parameter of the URI (i.e. `cache=shared`), replacing any previous value.
+    ///
+    /// See [https://www.sqlite.org/uri.html](https://www.sqlite.org/uri.html) for the parameters supported by SQLite.
+    pub fn param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let key = key.into();
+        self.params.retain(|(k, _)| *k != key);
+        self.params.push((key, value.into()));
+        self
+    }
+
+    /// Get the URI of the database.
+    pub fn to_uri(&self) -> String {
+        // An absolute path needs an empty authority (`sqlite:///path`) to not be taken for an authority.
+        let authority = if self.path.starts_with('/') { "//" } else { "" };
+        format!("{}:{}{}{}", DRIVER_NAME, authority, encode_path(&self.path), encode_query(&self.params))
+    }
+
+    /// Open a connection to the database.
+    ///
+    /// The SQLite driver is registered if it was not already.
+    pub fn connect(&self) -> Result<Box<dyn DriverConnection>> {
+        register_driver();
+        Factory::open(&self.to_uri())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::errors::driver_error;
+use crate::cache::StatementCache;
+use crate::errors::{driver_error, statement_error};
+use crate::timeout::StatementTimeout;
 use crate::value::Adapter;
 use arrow_array::builder::ArrayBuilder;
 use arrow_array::builder::BinaryBuilder;
@@ -11,17 +13,79 @@ use arrow_schema::DataType;
 use arrow_schema::Field;
 use arrow_schema::Schema;
 use arrow_schema::SchemaRef;
+use squill_core::driver::affected_rows_batch;
 use squill_core::driver::DriverOptionsRef;
 use squill_core::driver::DriverStatement;
+use squill_core::driver::ExecuteOutcome;
 use squill_core::driver::Result;
 use squill_core::parameters::Parameters;
+use squill_core::placeholders::skip_literal_or_comment;
 use squill_core::Error;
 use std::cell::RefCell;
 use std::sync::Arc;
 
 pub(crate) struct SqliteStatement<'c> {
-    pub(crate) inner: rusqlite::Statement<'c>,
+    pub(crate) conn: &'c rusqlite::Connection,
+    pub(crate) inner: rusqlite::CachedStatement<'c>,
     pub(crate) options: DriverOptionsRef,
+
+    /// The maximum number of rows of the record batches overriding the one of the options (see
+    /// [DriverStatement::set_max_batch_rows]).
+    pub(crate) max_batch_rows: Option<usize>,
+    pub(crate) statement_timeout: Option<StatementTimeout>,
+    pub(crate) cache: &'c RefCell<StatementCache>,
+
+    /// The key of the statement in the cache, `None` if the statement cannot be cached (i.e. an empty statement).
+    pub(crate) cache_key: Option<String>,
+
+    /// Whether the statement is an `INSERT` (or `REPLACE`), see [is_insert].
+    pub(crate) is_insert: bool,
+}
+
+/// Check if a statement is an `INSERT` or a `REPLACE`, possibly preceded by a `WITH` clause.
+///
+/// The kind of a statement is given by its first keyword outside of parentheses, so the common table expressions of
+/// a `WITH` clause are skipped, and so are the quoted literals and the comments.
+pub(crate) fn is_insert(statement: &str) -> bool {
+    let bytes = statement.as_bytes();
+    let mut depth = 0;
+    let mut word_start = None;
+    let mut i = 0;
+    while i <= bytes.len() {
+        let skipped = if i < bytes.len() { skip_literal_or_comment(bytes, i) } else { None };
+        let in_word = skipped.is_none() && i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_');
+        if let Some(start) = word_start.filter(|_| !in_word) {
+            word_start = None;
+            for keyword in ["INSERT", "REPLACE", "UPDATE", "DELETE", "SELECT", "VALUES"] {
+                if depth == 0 && statement[start..i].eq_ignore_ascii_case(keyword) {
+                    return keyword == "INSERT" || keyword == "REPLACE";
+                }
+            }
+        }
+        if let Some(end) = skipped {
+            i = end + 1;
+            continue;
+        }
+        match bytes.get(i) {
+            _ if in_word => {
+                word_start.get_or_insert(i);
+            }
+            Some(b'(') => depth += 1,
+            Some(b')') => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+impl Drop for SqliteStatement<'_> {
+    fn drop(&mut self) {
+        // The statement is put back in the cache by `rusqlite` when `inner` is dropped.
+        if let Some(key) = self.cache_key.take() {
+            self.cache.borrow_mut().put(key);
+        }
+    }
 }
 
 impl SqliteStatement<'_> {
@@ -39,6 +103,54 @@ impl SqliteStatement<'_> {
                 }
                 Ok(())
             }
+            Parameters::Named(values) => {
+                if expected != values.len() {
+                    return Err(Error::InvalidParameterCount { expected, actual: values.len() }.into());
+                }
+                for (name, value) in values.iter() {
+                    let index = self.parameter_index(name)?;
+                    self.inner.raw_bind_parameter(index, Adapter(value)).map_err(driver_error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the index of a named parameter.
+    ///
+    /// The name can be given without the prefix of the placeholder (`:`, `@` or `$`).
+    fn parameter_index(&self, name: &str) -> Result<usize> {
+        if name.starts_with([':', '@', '$']) {
+            if let Some(index) = self.inner.parameter_index(name).map_err(driver_error)? {
+                return Ok(index);
+            }
+        } else {
+            for prefix in [':', '@', '$'] {
+                if let Some(index) = self.inner.parameter_index(&format!("{}{}", prefix, name)).map_err(driver_error)? {
+                    return Ok(index);
+                }
+            }
+        }
+        Err(Error::DriverError { error: format!("Unknown parameter: {}", name).into() }.into())
+    }
+
+    fn execute_each(&mut self, parameters: Vec<Parameters>) -> Result<u64> {
+        let mut affected_rows = 0;
+        for parameters in parameters {
+            affected_rows += self.execute(Some(parameters))?;
+        }
+        Ok(affected_rows)
+    }
+
+    fn start_timeout(&self) {
+        if let Some(statement_timeout) = &self.statement_timeout {
+            statement_timeout.start();
+        }
+    }
+
+    fn stop_timeout(&self) {
+        if let Some(statement_timeout) = &self.statement_timeout {
+            statement_timeout.stop();
         }
     }
 }
@@ -48,24 +160,77 @@ impl DriverStatement for SqliteStatement<'_> {
         if let Some(parameters) = parameters {
             self.bind(parameters)?;
         }
-        Ok(self.inner.raw_execute().map_err(driver_error)? as u64)
+        self.start_timeout();
+        let result = self.inner.raw_execute().map_err(|e| statement_error(e, self.statement_timeout.as_ref()));
+        self.stop_timeout();
+        Ok(result? as u64)
+    }
+
+    /// The rowid of the last insert is kept by the connection until the next insert, so it is only reported for a
+    /// statement inserting rows (`None` if no row was affected, i.e. `INSERT ... ON CONFLICT DO NOTHING`). An upsert
+    /// (`INSERT ... ON CONFLICT DO UPDATE`) updating a row reports the rowid of the previous insert.
+    fn execute_detailed(&mut self, parameters: Option<Parameters>) -> Result<ExecuteOutcome> {
+        let rows_affected = self.execute(parameters)?;
+        let inserted = rows_affected > 0 && self.is_insert && !self.inner.readonly();
+        let last_insert_id = inserted.then(|| self.conn.last_insert_rowid());
+        Ok(ExecuteOutcome { rows_affected, last_insert_id, warnings: Vec::new() })
+    }
+
+    fn execute_many(&mut self, parameters: Vec<Parameters>) -> Result<u64> {
+        if !self.conn.is_autocommit() {
+            // Already in a transaction, the changes will be committed along with it.
+            return self.execute_each(parameters);
+        }
+        // Without a transaction, SQLite commits (and syncs the database file) after each execution.
+        self.conn.execute_batch("BEGIN").map_err(driver_error)?;
+        match self.execute_each(parameters) {
+            Ok(affected_rows) => {
+                self.conn.execute_batch("COMMIT").map_err(driver_error)?;
+                Ok(affected_rows)
+            }
+            Err(e) => {
+                // The error of the execution is more relevant than a failure of the rollback.
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+
+    /// The statement itself is reset by SQLite once the rows of the last query are dropped, only the bindings need to be
+    /// cleared.
+    fn reset(&mut self) -> Result<()> {
+        self.inner.clear_bindings();
+        Ok(())
     }
 
     fn query<'s>(
         &'s mut self,
         parameters: Option<Parameters>,
     ) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + 's>> {
+        if self.inner.column_count() == 0 {
+            let affected_rows = self.execute(parameters)?;
+            return Ok(Box::new(std::iter::once(affected_rows_batch(affected_rows))));
+        }
         if let Some(parameters) = parameters {
             self.bind(parameters)?;
         }
+        self.start_timeout();
         let schema = self.schema();
         Ok(Box::new(SqliteRows {
             inner: self.inner.raw_query(),
-            options: self.options.clone(),
+            max_batch_rows: self.max_batch_rows.unwrap_or(self.options.max_batch_rows),
             schema: RefCell::new(schema),
+            statement_timeout: self.statement_timeout.clone(),
         }))
     }
 
+    /// The type of the columns without a declared type (i.e. an expression) is `Null`, it is inferred from their first
+    /// non-null value once the rows are fetched (see [DriverStatement::query_with_schema]). A column only having null
+    /// values in the first record batch is still of type `Null` (see [squill_core::arrow::UnifiedBatches]).
+    fn prepared_schema(&self) -> Option<SchemaRef> {
+        Some(self.schema())
+    }
+
     /// Returns the underlying schema of the prepared statement.
     fn schema(&self) -> SchemaRef {
         let fields: Vec<Field> = self
@@ -89,12 +254,29 @@ impl DriverStatement for SqliteStatement<'_> {
             .collect::<Vec<Field>>();
         Arc::new(Schema::new(fields))
     }
+
+    fn parameter_count(&self) -> usize {
+        self.inner.parameter_count()
+    }
+
+    fn set_max_batch_rows(&mut self, max_batch_rows: Option<usize>) {
+        self.max_batch_rows = max_batch_rows;
+    }
 }
 
+/// The record batches of a query.
+///
+/// The rows can be dropped before being exhausted: `rusqlite` resets the statement when dropping `inner`, so the
+/// statement doesn't hold a read lock on the database and can be queried again from its first row.
+///
+/// The type of a column without a declared type (i.e. an expression) is inferred from its first non-null value, so
+/// the first record batches can have a column of type `Null` that has another type in the next ones (see
+/// [squill_core::arrow::unify_batches] to give them the same schema).
 struct SqliteRows<'s> {
     inner: rusqlite::Rows<'s>,
-    options: DriverOptionsRef,
+    max_batch_rows: usize,
     schema: RefCell<SchemaRef>,
+    statement_timeout: Option<StatementTimeout>,
 }
 
 macro_rules! inner_append_value {
@@ -116,20 +298,7 @@ macro_rules! inner_append_value {
                     new_builder.append_value($value);
                     $columns[$index] = Box::new(new_builder);
                     // We also need to alter the schema to reflect the new type
-                    let fields: Vec<Arc<Field>> = $schema
-                        .borrow()
-                        .fields()
-                        .iter()
-                        .enumerate()
-                        .map(|(i, field)| {
-                            if i == $index {
-                                Arc::new(Field::new(field.name(), $DataType, field.is_nullable()))
-                            } else {
-                                field.clone()
-                            }
-                        })
-                        .collect();
-                    $schema.replace(Arc::new(Schema::new(fields)));
+                    SqliteRows::set_column_type($schema, $index, $DataType);
                 } else {
                     panic!(
                         "SQLITE: Unexpected column type (expected {:?}, got {:?}).",
@@ -143,6 +312,30 @@ macro_rules! inner_append_value {
 }
 
 impl SqliteRows<'_> {
+    fn stop_timeout(&self) {
+        if let Some(statement_timeout) = &self.statement_timeout {
+            statement_timeout.stop();
+        }
+    }
+
+    /// Change the type of a column in the schema of the rows.
+    fn set_column_type(schema: &RefCell<SchemaRef>, index: usize, data_type: DataType) {
+        let fields: Vec<Arc<Field>> = schema
+            .borrow()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                if i == index {
+                    Arc::new(Field::new(field.name(), data_type.clone(), field.is_nullable()))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        schema.replace(Arc::new(Schema::new(fields)));
+    }
+
     fn append_value(
         schema: &RefCell<SchemaRef>,
         columns: &mut [Box<dyn ArrayBuilder>],
@@ -154,6 +347,12 @@ impl SqliteRows<'_> {
             match value_ref.data_type() {
                 rusqlite::types::Type::Integer => {
                     let value = value_ref.as_i64()?;
+                    // An expression mixing integers and reals (i.e. `CASE WHEN ... THEN 1 ELSE 1.5 END`) is
+                    // promoted to Float64.
+                    if let Some(float_builder) = columns[index].as_any_mut().downcast_mut::<Float64Builder>() {
+                        float_builder.append_value(value as f64);
+                        continue;
+                    }
                     inner_append_value!(Int64Builder, DataType::Int64, value, columns, index, schema, value_ref);
                 }
                 rusqlite::types::Type::Text => {
@@ -162,6 +361,13 @@ impl SqliteRows<'_> {
                 }
                 rusqlite::types::Type::Real => {
                     let value = value_ref.as_f64()?;
+                    if let Some(int_builder) = columns[index].as_any_mut().downcast_mut::<Int64Builder>() {
+                        // The integers found so far in the column are promoted to Float64.
+                        let mut float_builder = Float64Builder::with_capacity(int_builder.capacity());
+                        float_builder.extend(int_builder.finish().iter().map(|value| value.map(|value| value as f64)));
+                        columns[index] = Box::new(float_builder);
+                        Self::set_column_type(schema, index, DataType::Float64);
+                    }
                     inner_append_value!(Float64Builder, DataType::Float64, value, columns, index, schema, value_ref);
                 }
                 rusqlite::types::Type::Blob => {
@@ -189,6 +395,13 @@ impl SqliteRows<'_> {
     }
 }
 
+/// The statement is completed once its rows are dropped, even if they were not exhausted.
+impl Drop for SqliteRows<'_> {
+    fn drop(&mut self) {
+        self.stop_timeout();
+    }
+}
+
 impl<'c> Iterator for SqliteRows<'c> {
     type Item = Result<arrow_array::RecordBatch>;
 
@@ -208,7 +421,7 @@ impl<'c> Iterator for SqliteRows<'c> {
             })
             .collect();
 
-        let max_batch_rows = self.options.max_batch_rows;
+        let max_batch_rows = self.max_batch_rows;
         let rows = &mut self.inner;
         let mut row_num = 0;
         loop {
@@ -223,8 +436,15 @@ impl<'c> Iterator for SqliteRows<'c> {
                     }
                     Err(error) => return Some(Err(error)),
                 },
-                Ok(None) => break,
-                Err(error) => return Some(Err(error.into())),
+                Ok(None) => {
+                    self.stop_timeout();
+                    break;
+                }
+                Err(error) => {
+                    let error = statement_error(error, self.statement_timeout.as_ref());
+                    self.stop_timeout();
+                    return Some(Err(error.into()));
+                }
             }
         }
         match row_num {
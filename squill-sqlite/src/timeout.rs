@@ -0,0 +1,44 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The number of virtual machine instructions between two checks of the statement timeout.
+const PROGRESS_HANDLER_PERIOD: i32 = 1000;
+
+/// The statement timeout of a connection.
+///
+/// SQLite doesn't have a native statement timeout, the timeout is enforced by a progress handler that interrupts the
+/// statement running when the deadline set by [StatementTimeout::start] is reached. The interrupted statement fails
+/// with `SQLITE_INTERRUPT`.
+///
+/// The deadline must be cleared by [StatementTimeout::stop] once the statement is completed, otherwise the progress
+/// handler would interrupt the next calls on the connection that don't start a countdown (i.e. a pragma).
+#[derive(Clone)]
+pub(crate) struct StatementTimeout {
+    timeout: Duration,
+    deadline: Arc<Mutex<Option<Instant>>>,
+}
+
+impl StatementTimeout {
+    /// Install the statement timeout on the connection.
+    pub(crate) fn install(conn: &rusqlite::Connection, timeout: Duration) -> Self {
+        let statement_timeout = Self { timeout, deadline: Arc::new(Mutex::new(None)) };
+        let handler = statement_timeout.clone();
+        conn.progress_handler(PROGRESS_HANDLER_PERIOD, Some(move || handler.is_expired()));
+        statement_timeout
+    }
+
+    /// Start the countdown for a statement about to be executed.
+    pub(crate) fn start(&self) {
+        *self.deadline.lock().unwrap() = Some(Instant::now() + self.timeout);
+    }
+
+    /// Stop the countdown of the statement that was executed.
+    pub(crate) fn stop(&self) {
+        *self.deadline.lock().unwrap() = None;
+    }
+
+    /// Check if the statement being executed must be interrupted.
+    pub(crate) fn is_expired(&self) -> bool {
+        matches!(*self.deadline.lock().unwrap(), Some(deadline) if Instant::now() >= deadline)
+    }
+}
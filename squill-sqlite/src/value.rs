@@ -1,5 +1,7 @@
-use rusqlite::types::ToSqlOutput;
+use rusqlite::types::{ToSqlOutput, ValueRef};
+use rusqlite::ToSql;
 use squill_core::values::Value;
+use squill_core::Error;
 
 /// Converting a `Value` to a `duckdb::ToSql`.
 ///
@@ -17,20 +19,55 @@ impl<'a> rusqlite::ToSql for Adapter<'a> {
             Value::Int16(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value as i64))),
             Value::Int32(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value as i64))),
             Value::Int64(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value))),
-            Value::Int128(_) => Err(rusqlite::Error::ToSqlConversionFailure("128 bit integers not supported".into())),
+            Value::Int128(_) => Err(unsupported_data_type("Int128")),
             Value::UInt8(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value as i64))),
             Value::UInt16(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value as i64))),
             Value::UInt32(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Integer(*value as i64))),
-            Value::UInt64(_) => {
-                Err(rusqlite::Error::ToSqlConversionFailure("Unsigned 64 bit integers not supported".into()))
-            }
-            Value::UInt128(_) => {
-                Err(rusqlite::Error::ToSqlConversionFailure("Unsigned 128 bit integers not supported".into()))
-            }
+            Value::UInt64(_) => Err(unsupported_data_type("UInt64")),
+            Value::UInt128(_) => Err(unsupported_data_type("UInt128")),
             Value::Float32(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Real(*value as f64))),
             Value::Float64(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Real(*value))),
             Value::String(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Text(value.clone()))),
-            _ => Err(rusqlite::Error::ToSqlConversionFailure("Unsupported value type".into())),
+            Value::Uuid(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Text(value.to_string()))),
+            Value::Json(value) => Ok(ToSqlOutput::Owned(rusqlite::types::Value::Text(value.clone()))),
+            Value::Blob(value) => Ok(ToSqlOutput::Borrowed(ValueRef::Blob(value))),
+            Value::Date32(_) => Err(unsupported_data_type("Date32")),
+            Value::Timestamp(_, _) => Err(unsupported_data_type("Timestamp")),
+            Value::TimestampTz(_, _, _) => Err(unsupported_data_type("TimestampTz")),
+            Value::Time64(_, _) => Err(unsupported_data_type("Time64")),
+            Value::Interval { .. } => Err(unsupported_data_type("Interval")),
+            Value::Decimal(_) => Err(unsupported_data_type("Decimal")),
+            Value::List(_) => Err(unsupported_data_type("List")),
         }
     }
 }
+
+/// The error returned when a value cannot be bound to a SQLite statement.
+///
+/// The `Error::UnsupportedDataType` is wrapped into a `rusqlite::Error` and unwrapped by
+/// [driver_error](crate::errors::driver_error).
+fn unsupported_data_type(data_type: &str) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(Box::new(Error::UnsupportedDataType { data_type: data_type.to_string() }))
+}
+
+/// Convert a value given by SQLite (i.e. the argument of a function) into a `Value`.
+pub(crate) fn from_sqlite_value(value: ValueRef<'_>) -> rusqlite::Result<Value> {
+    match value {
+        ValueRef::Null => Ok(Value::Null),
+        ValueRef::Integer(value) => Ok(Value::Int64(value)),
+        ValueRef::Real(value) => Ok(Value::Float64(value)),
+        ValueRef::Text(value) => Ok(Value::String(
+            std::str::from_utf8(value).map_err(|e| rusqlite::Error::UserFunctionError(Box::new(e)))?.to_string(),
+        )),
+        ValueRef::Blob(value) => Ok(Value::Blob(value.to_vec())),
+    }
+}
+
+/// Convert a `Value` into an owned SQLite value (i.e. the result of a function).
+pub(crate) fn to_sqlite_value(value: &Value) -> rusqlite::Result<rusqlite::types::Value> {
+    match Adapter(value).to_sql()? {
+        ToSqlOutput::Owned(value) => Ok(value),
+        ToSqlOutput::Borrowed(value) => Ok(value.into()),
+        _ => Err(rusqlite::Error::ToSqlConversionFailure("Unsupported value type".into())),
+    }
+}
@@ -18,11 +18,11 @@ pub use squill_core::decode::Decode;
 pub use squill_core::error::Error;
 pub use squill_core::factory::Factory;
 pub use squill_core::parameters::Parameters;
-pub use squill_core::row::Row;
+pub use squill_core::row::{IntoMap, Row};
 pub use squill_core::Result;
 
 // Re-export the macros.
-pub use squill_core::{execute, params};
+pub use squill_core::{execute, execute_named, params, query, query_named};
 
 #[cfg(feature = "async-conn")]
 pub mod async_conn {
@@ -30,11 +30,16 @@ pub mod async_conn {
     pub use squill_async::RecordBatchStream;
     pub use squill_async::RowStream;
     pub use squill_async::Statement;
+    pub use squill_async::Transaction;
 }
 
 #[cfg(feature = "blocking-conn")]
 pub mod blocking_conn {
     pub use squill_blocking::Connection;
+    pub use squill_blocking::InterruptHandle;
+    pub use squill_blocking::Metrics;
+    pub use squill_blocking::OwnedRows;
+    pub use squill_blocking::PeekableRows;
     pub use squill_blocking::Rows;
     pub use squill_blocking::Statement;
 }
@@ -44,10 +49,12 @@ pub mod sqlite {
     pub use squill_sqlite::DRIVER_NAME;
     pub use squill_sqlite::IN_MEMORY_SPECIAL_FILENAME;
     pub use squill_sqlite::IN_MEMORY_URI;
+    pub use squill_sqlite::{SqliteConnectOptions, SqliteOpenMode};
 }
 
 #[cfg(feature = "duckdb")]
 pub mod duckdb {
+    pub use squill_duckdb::DuckDbConnectOptions;
     pub use squill_duckdb::DRIVER_NAME;
 }
 
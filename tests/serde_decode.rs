@@ -0,0 +1,45 @@
+use arrow_array::StringArray;
+use serde::Deserialize;
+use squill_drivers::serde::Decode;
+use squill_drivers::{Decode as _, Error};
+
+#[derive(Debug, PartialEq, Decode)]
+enum Status {
+    #[squill(rename = "active")]
+    Active,
+    #[squill(rename = "on-hold")]
+    OnHold,
+    Closed,
+}
+
+#[test]
+fn test_decode_enum() {
+    let array = StringArray::from(vec!["active", "on-hold", "Closed", "closed"]);
+    assert_eq!(Status::decode(&array, 0), Status::Active);
+    assert_eq!(Status::decode(&array, 1), Status::OnHold);
+    assert_eq!(Status::decode(&array, 2), Status::Closed);
+    assert!(matches!(Status::try_decode(&array, 3), Err(Error::InvalidType { .. })));
+
+    // A null is not decoded as the placeholder stored by Arrow.
+    let array = StringArray::from(vec![Some("active"), None]);
+    assert!(matches!(Status::try_decode(&array, 1), Err(Error::InvalidType { actual, .. }) if actual == "null"));
+    assert!(matches!(Status::try_decode(&array, 2), Err(Error::OutOfBounds { index: 2 })));
+
+    // Only string columns can be decoded.
+    let array = arrow_array::Int32Array::from(vec![1]);
+    assert!(matches!(Status::try_decode(&array, 0), Err(Error::InvalidType { .. })));
+}
+
+#[derive(Debug, PartialEq, Deserialize, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[test]
+fn test_decode_json() {
+    let array = StringArray::from(vec![Some(r#"{"name": "Alice", "age": 30}"#), None]);
+    assert_eq!(Person::decode(&array, 0), Person { name: "Alice".to_string(), age: 30 });
+    assert!(matches!(Person::try_decode(&array, 1), Err(Error::InvalidType { actual, .. }) if actual == "null"));
+    assert!(matches!(Person::try_decode(&array, 2), Err(Error::OutOfBounds { index: 2 })));
+}
@@ -0,0 +1,5 @@
+#[test]
+fn test_decode_enum_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/decode_enum_*.rs");
+}
@@ -0,0 +1,9 @@
+use squill_drivers::serde::Decode;
+
+#[derive(Decode)]
+enum Status {
+    #[squill(rename = 1)]
+    Active,
+}
+
+fn main() {}
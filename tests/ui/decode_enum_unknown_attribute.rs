@@ -0,0 +1,9 @@
+use squill_drivers::serde::Decode;
+
+#[derive(Decode)]
+enum Status {
+    #[squill(name = "active")]
+    Active,
+}
+
+fn main() {}